@@ -128,6 +128,18 @@ yargs(hideBin(process.argv))
                 console.log("Transferring fees")
                 ix = bridge.transfer_fees_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
                 break
+            case 5:
+                console.log("Setting governance emitter")
+                ix = bridge.set_governance_emitter_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
+            case 6:
+                console.log("Setting guardian set expiration")
+                ix = bridge.set_guardian_set_expiration_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
+            case 7:
+                console.log("Setting message retention")
+                ix = bridge.set_message_retention_ix(bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
             default:
                 throw new Error("unknown governance action")
         }
@@ -235,6 +235,10 @@ yargs(hideBin(process.argv))
                 console.log("Upgrading contract")
                 ix = token_bridge.upgrade_contract_ix(token_bridge_id.toString(), bridge_id.toString(), from.publicKey.toString(), from.publicKey.toString(), vaa);
                 break
+            case 3:
+                console.log("Setting governance emitter")
+                ix = token_bridge.set_governance_emitter_ix(token_bridge_id.toString(), bridge_id.toString(), from.publicKey.toString(), vaa);
+                break
             default:
                 throw new Error("unknown governance action")
         }
@@ -87,6 +87,9 @@ func (c ConsistencyLevel) Commitment() (rpc.CommitmentType, error) {
 const (
 	postMessageInstructionNumAccounts = 9
 	postMessageInstructionID          = 0x01
+	// finalizeMessage and postMessageDerived use the same account layout as postMessage.
+	finalizeMessageInstructionID      = 0x0d
+	postMessageDerivedInstructionID   = 0x12
 )
 
 // PostMessageData represents the user-supplied, untrusted instruction data
@@ -97,6 +100,13 @@ type PostMessageData struct {
 	ConsistencyLevel ConsistencyLevel
 }
 
+// FinalizeMessageData represents the user-supplied, untrusted instruction data
+// for finalizing messages whose payload was written in chunks.
+type FinalizeMessageData struct {
+	Nonce            uint32
+	ConsistencyLevel ConsistencyLevel
+}
+
 func NewSolanaWatcher(
 	wsUrl, rpcUrl string,
 	contractAddress solana.PublicKey,
@@ -389,7 +399,9 @@ func (s *SolanaWatcher) processInstruction(ctx context.Context, logger *zap.Logg
 		return false, nil
 	}
 
-	if inst.Data[0] != postMessageInstructionID {
+	if inst.Data[0] != postMessageInstructionID &&
+		inst.Data[0] != finalizeMessageInstructionID &&
+		inst.Data[0] != postMessageDerivedInstructionID {
 		return false, nil
 	}
 
@@ -399,15 +411,30 @@ func (s *SolanaWatcher) processInstruction(ctx context.Context, logger *zap.Logg
 	}
 
 	// Decode instruction data (UNTRUSTED)
-	var data PostMessageData
-	if err := borsh.Deserialize(&data, inst.Data[1:]); err != nil {
-		return false, fmt.Errorf("failed to deserialize instruction data: %w", err)
-	}
+	var consistencyLevel ConsistencyLevel
+	if inst.Data[0] == postMessageInstructionID || inst.Data[0] == postMessageDerivedInstructionID {
+		var data PostMessageData
+		if err := borsh.Deserialize(&data, inst.Data[1:]); err != nil {
+			return false, fmt.Errorf("failed to deserialize instruction data: %w", err)
+		}
 
-	logger.Info("post message data", zap.Any("deserialized_data", data),
-		zap.Stringer("signature", signature), zap.Uint64("slot", slot), zap.Int("idx", idx))
+		logger.Info("post message data", zap.Any("deserialized_data", data),
+			zap.Stringer("signature", signature), zap.Uint64("slot", slot), zap.Int("idx", idx))
+
+		consistencyLevel = data.ConsistencyLevel
+	} else {
+		var data FinalizeMessageData
+		if err := borsh.Deserialize(&data, inst.Data[1:]); err != nil {
+			return false, fmt.Errorf("failed to deserialize instruction data: %w", err)
+		}
+
+		logger.Info("finalize message data", zap.Any("deserialized_data", data),
+			zap.Stringer("signature", signature), zap.Uint64("slot", slot), zap.Int("idx", idx))
+
+		consistencyLevel = data.ConsistencyLevel
+	}
 
-	level, err := data.ConsistencyLevel.Commitment()
+	level, err := consistencyLevel.Commitment()
 	if err != nil {
 		return false, fmt.Errorf("failed to determine commitment: %w", err)
 	}
@@ -560,6 +587,9 @@ type (
 		EmitterChain        uint16
 		EmitterAddress      vaa.Address
 		Payload             []byte
+		// Newer accounts are followed by the payer to refund and the slot the message was posted
+		// in. Older accounts don't have them and the guardian doesn't need them, so decoding stops
+		// at the payload.
 	}
 )
 
@@ -58,5 +58,4 @@ pkgs.mkShell {
   );
   DOCKER_BUILDKIT = 1;
   PROTOC = "${pkgs.protobuf}/bin/protoc";
-  EMITTER_ADDRESS = "changeme";
 }
@@ -59,9 +59,11 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
     Info,
+    Versioned,
 };
 use solitaire_client::Derive;
 
@@ -81,6 +83,9 @@ fn command_deploy_bridge(
     initial_guardians: Vec<[u8; 20]>,
     guardian_expiration: u32,
     message_fee: u64,
+    governance_chain: u16,
+    governance_emitter: [u8; 32],
+    message_retention_slots: u64,
 ) -> CommmandResult {
     println!("Initializing Wormhole bridge {}", bridge);
 
@@ -94,6 +99,9 @@ fn command_deploy_bridge(
         message_fee,
         guardian_expiration,
         initial_guardians.as_slice(),
+        governance_chain,
+        governance_emitter,
+        message_retention_slots,
     )
     .unwrap();
     println!("config account: {}, ", ix.accounts[0].pubkey.to_string());
@@ -124,7 +132,8 @@ fn command_post_message(
         .get_account(&Bridge::<'_, { AccountState::Initialized }>::key(
             None, bridge,
         ))?;
-    let bridge_config = BridgeData::try_from_slice(bridge_config_account.data.as_slice())?;
+    let bridge_config: Versioned<BridgeData> =
+        from_account_data(bridge_config_account.data.as_slice())?;
     let fee = bridge_config.config.fee;
     println!("Message fee: {} lamports", fee);
 
@@ -235,6 +244,10 @@ fn main() {
                         .help("Specify the bridge program address"),
                 )
         )
+        .subcommand(
+            SubCommand::with_name("idl")
+                .about("Print the JSON IDL describing the bridge instructions, accounts and errors"),
+        )
         .subcommand(
             SubCommand::with_name("create-bridge")
                 .about("Create a new bridge")
@@ -274,6 +287,33 @@ fn main() {
                         .index(4)
                         .required(true)
                         .help("Initial message posting fee"),
+                )
+                .arg(
+                    Arg::with_name("governance_chain")
+                        .validator(is_u16)
+                        .value_name("GOVERNANCE_CHAIN")
+                        .takes_value(true)
+                        .index(5)
+                        .required(true)
+                        .help("Chain ID of the governance emitter"),
+                )
+                .arg(
+                    Arg::with_name("governance_emitter")
+                        .validator(is_hex)
+                        .value_name("GOVERNANCE_EMITTER")
+                        .takes_value(true)
+                        .index(6)
+                        .required(true)
+                        .help("Address of the governance emitter, 32 bytes hex encoded"),
+                )
+                .arg(
+                    Arg::with_name("message_retention_slots")
+                        .validator(is_u64)
+                        .value_name("MESSAGE_RETENTION_SLOTS")
+                        .takes_value(true)
+                        .index(7)
+                        .required(true)
+                        .help("Number of slots after which posted message accounts can be closed"),
                 ),
         )
         .subcommand(
@@ -361,15 +401,25 @@ fn main() {
             let guardian_expiration: u32 =
                 value_of(arg_matches, "guardian_set_expiration").unwrap();
             let msg_fee: u64 = value_of(arg_matches, "message_fee").unwrap();
+            let governance_chain: u16 = value_of(arg_matches, "governance_chain").unwrap();
+            let governance_str: String = value_of(arg_matches, "governance_emitter").unwrap();
+            let governance_data = hex::decode(governance_str).unwrap();
+            let message_retention_slots: u64 =
+                value_of(arg_matches, "message_retention_slots").unwrap();
 
             let mut guardian = [0u8; 20];
             guardian.copy_from_slice(&initial_data);
+            let mut governance_emitter = [0u8; 32];
+            governance_emitter.copy_from_slice(&governance_data);
             command_deploy_bridge(
                 &config,
                 &bridge,
                 vec![guardian],
                 guardian_expiration,
                 msg_fee,
+                governance_chain,
+                governance_emitter,
+                message_retention_slots,
             )
         }
         ("upgrade-authority", Some(arg_matches)) => {
@@ -379,6 +429,14 @@ fn main() {
 
             Ok(None)
         }
+        ("idl", Some(_)) => {
+            println!(
+                "{}",
+                bridge::instruction::idl::<bridge::error::Error>().to_json()
+            );
+
+            Ok(None)
+        }
         ("post-message", Some(arg_matches)) => {
             let bridge = pubkey_of(arg_matches, "bridge").unwrap();
             let data_str: String = value_of(arg_matches, "data").unwrap();
@@ -443,6 +501,20 @@ where
     }
 }
 
+pub fn is_u16<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u16>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
 pub fn is_u32<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
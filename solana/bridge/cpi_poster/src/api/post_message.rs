@@ -5,7 +5,7 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct PostMessage<'b> {
     /// Bridge config needed for fee calculation.
     pub bridge: Mut<Info<'b>>,
@@ -25,15 +25,13 @@ pub struct PostMessage<'b> {
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
 
-    pub clock: Info<'b>,
-
     pub bridge_program: Info<'b>,
 }
 
 impl<'b> InstructionContext<'b> for PostMessage<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
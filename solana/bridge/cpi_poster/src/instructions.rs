@@ -21,7 +21,7 @@ pub fn post_message(
         bridge_id, payer, emitter, message, nonce, payload, commitment,
     )?;
     let mut accounts = ix.accounts;
-    accounts.insert(7, AccountMeta::new_readonly(bridge_id, false));
+    accounts.insert(6, AccountMeta::new_readonly(bridge_id, false));
     let mut data = ix.data;
     data[0] = 0;
 
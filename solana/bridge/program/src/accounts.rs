@@ -6,6 +6,7 @@ pub mod posted_message;
 pub mod posted_vaa;
 pub mod sequence;
 pub mod signature_set;
+pub mod vaa_buffer;
 
 pub use bridge::*;
 pub use claim::*;
@@ -15,3 +16,21 @@ pub use posted_message::*;
 pub use posted_vaa::*;
 pub use sequence::*;
 pub use signature_set::*;
+pub use vaa_buffer::*;
+
+use solitaire::Discriminated;
+
+/// Tags of the account types of the bridge. Data starting with one of them is never read as a
+/// legacy account of another type.
+pub const ACCOUNT_TAGS: &[&[u8]] = &[
+    BridgeData::DISCRIMINATOR,
+    BridgeExtensionData::DISCRIMINATOR,
+    ClaimData::DISCRIMINATOR,
+    GuardianSetData::DISCRIMINATOR,
+    PendingMessageData::DISCRIMINATOR,
+    PostedMessageData::DISCRIMINATOR,
+    PostedVAAData::DISCRIMINATOR,
+    SequenceTracker::DISCRIMINATOR,
+    SignatureSetData::DISCRIMINATOR,
+    VAABufferData::DISCRIMINATOR,
+];
@@ -9,17 +9,34 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
+    processors::seeded::AccountSize,
     AccountOwner,
     AccountState,
     Data,
     Derive,
+    Discriminated,
+    Keyed,
+    Migrate,
     Owned,
+    Versioned,
+};
+use std::{
+    io::{
+        Error,
+        ErrorKind,
+    },
+    str::FromStr,
 };
 
-pub type Bridge<'a, const State: AccountState> = Derive<Data<'a, BridgeData, { State }>, "Bridge">;
+use crate::CHAIN_ID_SOLANA;
 
-#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub type Bridge<'a, const State: AccountState> =
+    Derive<Data<'a, Versioned<BridgeData>, { State }>, "Bridge">;
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize, Discriminated)]
+#[discriminator(legacy(crate::accounts::ACCOUNT_TAGS))]
 pub struct BridgeData {
     /// The current guardian set index, used to decide which signature sets to accept.
     pub guardian_set_index: u32,
@@ -27,10 +44,33 @@ pub struct BridgeData {
     /// Lamports in the collection account
     pub last_lamports: u64,
 
-    /// Bridge configuration, which is set once upon initialization.
+    /// Bridge configuration, which is set upon initialization and updated through governance.
     pub config: BridgeConfig,
 }
 
+impl BridgeData {
+    /// Configuration of the bridge, with the settings of a legacy bridge that its account has no
+    /// room for read from its extension.
+    pub fn current_config(
+        &self,
+        extension: &Data<'_, BridgeExtensionData, { AccountState::MaybeInitialized }>,
+    ) -> BridgeConfig {
+        let mut config = self.config.clone();
+        if extension.is_initialized() {
+            config.governance_chain = extension.governance_chain;
+            config.governance_emitter = extension.governance_emitter;
+            config.message_retention_slots = extension.message_retention_slots;
+        }
+        config
+    }
+}
+
+/// Whether the account of the bridge has room for the current layout. Legacy bridge accounts are
+/// sized for `BridgeDataV0` and can't grow.
+pub fn has_room(bridge: &Bridge<'_, { AccountState::Initialized }>) -> bool {
+    bridge.info().data_len() >= bridge.size()
+}
+
 #[cfg(not(feature = "cpi"))]
 impl Owned for BridgeData {
     fn owner(&self) -> AccountOwner {
@@ -41,8 +81,6 @@ impl Owned for BridgeData {
 #[cfg(feature = "cpi")]
 impl Owned for BridgeData {
     fn owner(&self) -> AccountOwner {
-        use std::str::FromStr;
-        use solana_program::pubkey::Pubkey;
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
@@ -56,4 +94,100 @@ pub struct BridgeConfig {
 
     /// Amount of lamports that needs to be paid to the protocol to post a message
     pub fee: u64,
+
+    /// Chain the governance emitter lives on, governance VAAs from any other chain are rejected.
+    pub governance_chain: u16,
+
+    /// Address of the emitter that is allowed to issue governance VAAs.
+    pub governance_emitter: [u8; 32],
+
+    /// Number of slots a posted message must exist before its account can be closed.
+    pub message_retention_slots: u64,
+}
+
+/// Settings of a legacy bridge that its account has no room for, those added to `BridgeConfig`
+/// since. The account is created the first time governance changes one of them, until then the
+/// legacy values `BridgeData::migrate` fills in apply.
+pub type BridgeExtension<'a, const State: AccountState> =
+    Derive<Data<'a, BridgeExtensionData, { State }>, "BridgeExtension">;
+
+#[derive(Clone, Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize, Discriminated)]
+pub struct BridgeExtensionData {
+    /// Chain the governance emitter lives on.
+    pub governance_chain: u16,
+
+    /// Address of the emitter that is allowed to issue governance VAAs.
+    pub governance_emitter: [u8; 32],
+
+    /// Number of slots a posted message must exist before its account can be closed.
+    pub message_retention_slots: u64,
+}
+
+impl Owned for BridgeExtensionData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Layout of bridges deployed before governance was stored on-chain. Their accounts are sized for
+/// it and can't grow.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct BridgeDataV0 {
+    guardian_set_index: u32,
+    last_lamports: u64,
+    guardian_set_expiration_time: u32,
+    fee: u64,
+}
+
+/// Governance of bridges deployed before it was stored on-chain, which the program was built with.
+/// Upgrading such a bridge requires building with `EMITTER_ADDRESS` set to its emitter.
+fn legacy_governance_emitter() -> std::io::Result<[u8; 32]> {
+    option_env!("EMITTER_ADDRESS")
+        .and_then(|emitter| Pubkey::from_str(emitter).ok())
+        .map(|emitter| emitter.to_bytes())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "no legacy governance emitter"))
+}
+
+impl Migrate for BridgeData {
+    const VERSION: u8 = 1;
+
+    /// Legacy bridges were governed from Solana by the emitter they were built with, and posted
+    /// messages could not be closed.
+    fn migrate(version: u8, data: &mut &[u8]) -> std::io::Result<Self> {
+        match version {
+            0 => {
+                let v0 = BridgeDataV0::deserialize(data)?;
+                Ok(BridgeData {
+                    guardian_set_index: v0.guardian_set_index,
+                    last_lamports: v0.last_lamports,
+                    config: BridgeConfig {
+                        guardian_set_expiration_time: v0.guardian_set_expiration_time,
+                        fee: v0.fee,
+                        governance_chain: CHAIN_ID_SOLANA,
+                        governance_emitter: legacy_governance_emitter()?,
+                        message_retention_slots: u64::MAX,
+                    },
+                })
+            }
+            _ => Err(ErrorKind::InvalidData.into()),
+        }
+    }
+
+    fn downgrade(&self) -> Option<Vec<u8>> {
+        let migrated = self.config.governance_chain == CHAIN_ID_SOLANA
+            && legacy_governance_emitter().ok() == Some(self.config.governance_emitter)
+            && self.config.message_retention_slots == u64::MAX;
+        if !migrated {
+            return None;
+        }
+
+        BridgeDataV0 {
+            guardian_set_index: self.guardian_set_index,
+            last_lamports: self.last_lamports,
+            guardian_set_expiration_time: self.config.guardian_set_expiration_time,
+            fee: self.config.fee,
+        }
+        .try_to_vec()
+        .ok()
+    }
 }
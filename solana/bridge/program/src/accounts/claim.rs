@@ -14,12 +14,23 @@ use solitaire::{
     AccountOwner,
     AccountState,
     Data,
+    Discriminated,
     Owned,
 };
 
 pub type Claim<'a, const State: AccountState> = Data<'a, ClaimData, { State }>;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+#[discriminator(legacy(crate::accounts::ACCOUNT_TAGS))]
 pub struct ClaimData {
     pub claimed: bool,
 }
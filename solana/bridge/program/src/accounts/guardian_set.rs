@@ -1,7 +1,10 @@
 //! GuardianSet represents an account containing information about the current active guardians
 //! responsible for signing wormhole VAAs.
 
-use crate::types::GuardianPublicKey;
+use crate::types::{
+    GuardianPublicKey,
+    Trailing,
+};
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -10,17 +13,20 @@ use serde::{
     Deserialize,
     Serialize,
 };
+use solana_program::pubkey::Pubkey;
 use solitaire::{
     processors::seeded::Seeded,
     AccountOwner,
     AccountState,
     Data,
+    Discriminated,
     Owned,
 };
 
 pub type GuardianSet<'b, const State: AccountState> = Data<'b, GuardianSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize, Discriminated)]
+#[discriminator(legacy(crate::accounts::ACCOUNT_TAGS))]
 pub struct GuardianSetData {
     /// Index representing an incrementing version number for this guardian set.
     pub index: u32,
@@ -33,6 +39,10 @@ pub struct GuardianSetData {
 
     /// Expiration time when VAAs issued by this set are no longer valid.
     pub expiration_time: u32,
+
+    /// Account that paid for this guardian set, refunded when it is closed after expiry. Absent in
+    /// sets created before refunds, which can't be closed.
+    pub payer: Trailing<Pubkey>,
 }
 
 /// GuardianSet account PDAs are indexed by their version number.
@@ -1,3 +1,4 @@
+use crate::types::Trailing;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -8,10 +9,13 @@ use serde::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    processors::seeded::Seeded,
     AccountOwner,
     AccountState,
     Data,
+    Discriminated,
     Owned,
+    Viewable,
 };
 use std::{
     io::Write,
@@ -23,6 +27,25 @@ use std::{
 
 pub type PostedMessage<'a, const State: AccountState> = Data<'a, PostedMessageData, { State }>;
 
+/// Messages posted through `post_message_derived` live at a PDA of their emitter and sequence
+/// instead of a keypair account.
+pub struct MessageDerivationData<'a> {
+    pub emitter_key: &'a Pubkey,
+    pub sequence: u64,
+}
+
+impl<'a, 'b, const State: AccountState> Seeded<&MessageDerivationData<'b>>
+    for PostedMessage<'a, { State }>
+{
+    fn seeds(data: &MessageDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "Message".as_bytes().to_vec(),
+            data.emitter_key.to_bytes().to_vec(),
+            data.sequence.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
 // This is using the same payload as the PostedVAA for backwards compatibility.
 // This will be deprecated in a future release.
 #[repr(transparent)]
@@ -59,24 +82,52 @@ pub struct MessageData {
 
     /// Message payload
     pub payload: Vec<u8>,
+
+    /// Account that paid for this account, refunded when it is closed. Absent in accounts created
+    /// before refunds, which can't be closed.
+    pub payer: Trailing<Pubkey>,
+
+    /// Slot in which the account was created, absent along with `payer`.
+    pub posted_slot: Trailing<u64>,
+}
+
+/// The fields of `MessageData` stored in front of its payload, read by `View`s of messages and
+/// VAAs without deserializing the payload.
+#[derive(Default, BorshDeserialize, Clone)]
+pub struct MessageHeader {
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
 }
 
 impl BorshSerialize for PostedMessageData {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        writer.write(b"msg")?;
         BorshSerialize::serialize(&self.0, writer)
     }
 }
 
 impl BorshDeserialize for PostedMessageData {
     fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
-        *buf = &buf[3..];
         Ok(PostedMessageData(
             <MessageData as BorshDeserialize>::deserialize(buf)?,
         ))
     }
 }
 
+impl Discriminated for PostedMessageData {
+    const DISCRIMINATOR: &'static [u8] = b"msg";
+}
+
+impl Viewable for PostedMessageData {
+    type Header = MessageHeader;
+}
+
 impl Deref for PostedMessageData {
     type Target = MessageData;
 
@@ -117,3 +168,63 @@ impl Owned for PostedMessageData {
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
+
+pub type PendingMessage<'a, const State: AccountState> = Data<'a, PendingMessageData, { State }>;
+
+/// A message whose payload is still being written in chunks. It is stored under its own prefix so
+/// guardians never pick it up, finalizing switches the prefix to that of a PostedMessage.
+#[derive(Default, Clone)]
+pub struct PendingMessageData {
+    pub message: MessageData,
+
+    /// Set by `finalize_message`, not part of the serialized account data.
+    pub finalized: bool,
+}
+
+impl BorshSerialize for PendingMessageData {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.message, writer)
+    }
+}
+
+impl BorshDeserialize for PendingMessageData {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        Ok(PendingMessageData {
+            message: <MessageData as BorshDeserialize>::deserialize(buf)?,
+            finalized: false,
+        })
+    }
+}
+
+/// Messages that were already finalized carry the tag of a PostedMessage and are refused.
+impl Discriminated for PendingMessageData {
+    const DISCRIMINATOR: &'static [u8] = b"msp";
+
+    fn discriminator(&self) -> &'static [u8] {
+        if self.finalized {
+            PostedMessageData::DISCRIMINATOR
+        } else {
+            Self::DISCRIMINATOR
+        }
+    }
+}
+
+impl Deref for PendingMessageData {
+    type Target = MessageData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+impl DerefMut for PendingMessageData {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.message
+    }
+}
+
+impl Owned for PendingMessageData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
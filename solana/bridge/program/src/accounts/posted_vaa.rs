@@ -1,4 +1,7 @@
-use crate::MessageData;
+use crate::{
+    MessageData,
+    MessageHeader,
+};
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -8,7 +11,9 @@ use solitaire::{
     AccountOwner,
     AccountState,
     Data,
+    Discriminated,
     Owned,
+    Viewable,
 };
 use std::{
     io::Write,
@@ -35,20 +40,26 @@ pub struct PostedVAAData(pub MessageData);
 
 impl BorshSerialize for PostedVAAData {
     fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        writer.write(b"vaa")?;
         BorshSerialize::serialize(&self.0, writer)
     }
 }
 
 impl BorshDeserialize for PostedVAAData {
     fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
-        *buf = &buf[3..];
         Ok(PostedVAAData(
             <MessageData as BorshDeserialize>::deserialize(buf)?,
         ))
     }
 }
 
+impl Discriminated for PostedVAAData {
+    const DISCRIMINATOR: &'static [u8] = b"vaa";
+}
+
+impl Viewable for PostedVAAData {
+    type Header = MessageHeader;
+}
+
 impl Deref for PostedVAAData {
     type Target = MessageData;
 
@@ -89,3 +100,89 @@ impl Owned for PostedVAAData {
         AccountOwner::Other(Pubkey::from_str(env!("BRIDGE_ADDRESS")).unwrap())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Trailing;
+    use solana_program::{
+        account_info::AccountInfo,
+        pubkey::Pubkey,
+    };
+    use solitaire::{
+        Context,
+        Peel,
+        SolitaireError,
+        View,
+    };
+    use std::io::ErrorKind;
+
+    fn message() -> MessageData {
+        MessageData {
+            vaa_version: 1,
+            consistency_level: 2,
+            vaa_time: 3,
+            vaa_signature_account: Pubkey::new_unique(),
+            submission_time: 4,
+            nonce: 5,
+            sequence: 6,
+            emitter_chain: 7,
+            emitter_address: [8; 32],
+            payload: vec![9; 100],
+            payer: Trailing(Some(Pubkey::new_unique())),
+            posted_slot: Trailing(Some(10)),
+        }
+    }
+
+    /// Peel a view of an account of the program holding `data`.
+    fn view(data: &mut [u8], check: impl FnOnce(solitaire::Result<View<PostedVAAData>>)) {
+        let (key, program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lamports = 1;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, data, &program, false, 0);
+        let accounts = [info];
+        let mut iter = accounts.iter();
+        let mut ctx = Context::new(&program, &mut iter, &(), "PostVAA.vaa");
+        check(View::peel(&mut ctx));
+    }
+
+    #[test]
+    fn test_view_header() {
+        // The header has to match the fields serialized in front of the payload.
+        let message = message();
+        let mut data = [
+            PostedVAAData::DISCRIMINATOR,
+            &PostedVAAData(message.clone()).try_to_vec().unwrap(),
+        ]
+        .concat();
+        view(&mut data, |vaa| {
+            let vaa = vaa.unwrap();
+            assert_eq!(vaa.vaa_version, message.vaa_version);
+            assert_eq!(vaa.consistency_level, message.consistency_level);
+            assert_eq!(vaa.vaa_time, message.vaa_time);
+            assert_eq!(vaa.vaa_signature_account, message.vaa_signature_account);
+            assert_eq!(vaa.submission_time, message.submission_time);
+            assert_eq!(vaa.nonce, message.nonce);
+            assert_eq!(vaa.sequence, message.sequence);
+            assert_eq!(vaa.emitter_chain, message.emitter_chain);
+            assert_eq!(vaa.emitter_address, message.emitter_address);
+            assert_eq!(&*vaa.payload(), &message.payload[..]);
+        });
+    }
+
+    #[test]
+    fn test_view_truncated_payload() {
+        // Accounts ending within the payload can't be viewed.
+        let mut data = [
+            PostedVAAData::DISCRIMINATOR,
+            &PostedVAAData(message()).try_to_vec().unwrap(),
+        ]
+        .concat();
+        let end = data.len() - 32 - 8 - 1;
+        view(&mut data[..end], |vaa| {
+            assert!(matches!(
+                vaa,
+                Err(SolitaireError::IoError(e)) if e.kind() == ErrorKind::UnexpectedEof
+            ));
+        });
+    }
+}
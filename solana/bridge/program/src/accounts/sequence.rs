@@ -8,12 +8,16 @@ use solitaire::{
     AccountState,
     AccountOwner,
     Data,
+    Discriminated,
     Owned,
 };
 
 pub type Sequence<'b> = Data<'b, SequenceTracker, { AccountState::MaybeInitialized }>;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize)]
+/// Legacy trackers hold nothing but the sequence, which may start with the bytes of any tag. No
+/// account of another type is as short, so their length alone tells them apart.
+#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Discriminated)]
+#[discriminator(legacy)]
 pub struct SequenceTracker {
     pub sequence: u64,
 }
@@ -4,16 +4,40 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
+use crate::types::Trailing;
+use solana_program::pubkey::Pubkey;
 use solitaire::{
+    processors::seeded::Seeded,
     AccountOwner,
     AccountState,
     Data,
+    Discriminated,
     Owned,
 };
 
 pub type SignatureSet<'b, const State: AccountState> = Data<'b, SignatureSetData, { State }>;
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+/// Derivation data for signature sets that live at a PDA rather than a keypair account, allowing
+/// any relayer to contribute signatures to the same set.
+pub struct SignatureSetDerivationData {
+    pub hash: [u8; 32],
+    pub guardian_set_index: u32,
+}
+
+impl<'a, const State: AccountState> Seeded<&SignatureSetDerivationData>
+    for SignatureSet<'a, { State }>
+{
+    fn seeds(data: &SignatureSetDerivationData) -> Vec<Vec<u8>> {
+        vec![
+            "SignatureSet".as_bytes().to_vec(),
+            data.hash.to_vec(),
+            data.guardian_set_index.to_be_bytes().to_vec(),
+        ]
+    }
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Discriminated)]
+#[discriminator(legacy(crate::accounts::ACCOUNT_TAGS))]
 pub struct SignatureSetData {
     /// Signatures of validators
     pub signatures: Vec<bool>,
@@ -23,6 +47,10 @@ pub struct SignatureSetData {
 
     /// Index of the guardian set
     pub guardian_set_index: u32,
+
+    /// Account that paid for this signature set, refunded when it is closed. Absent in sets created
+    /// before refunds, which can't be closed.
+    pub payer: Trailing<Pubkey>,
 }
 
 impl Owned for SignatureSetData {
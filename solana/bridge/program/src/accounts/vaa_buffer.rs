@@ -0,0 +1,28 @@
+//! VAABuffer accounts hold a VAA body that is too large to fit into a single transaction. The body
+//! is written in chunks and then consumed by `post_vaa_from_buffer`.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solitaire::{
+    AccountOwner,
+    AccountState,
+    Data,
+    Discriminated,
+    Owned,
+};
+
+pub type VAABuffer<'b, const State: AccountState> = Data<'b, VAABufferData, { State }>;
+
+#[derive(Default, BorshSerialize, BorshDeserialize, Discriminated)]
+pub struct VAABufferData {
+    /// Serialized VAA body, sized on creation and filled in by subsequent writes.
+    pub body: Vec<u8>,
+}
+
+impl Owned for VAABufferData {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
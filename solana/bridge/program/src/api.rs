@@ -1,11 +1,15 @@
+pub mod close;
 pub mod governance;
 pub mod initialize;
 pub mod post_message;
 pub mod post_vaa;
+pub mod vaa_buffer;
 pub mod verify_signature;
 
+pub use close::*;
 pub use governance::*;
 pub use initialize::*;
 pub use post_message::*;
 pub use post_vaa::*;
+pub use vaa_buffer::*;
 pub use verify_signature::*;
@@ -0,0 +1,159 @@
+use solitaire::*;
+
+use crate::{
+    accounts::{
+        Bridge,
+        BridgeExtension,
+        GuardianSet,
+        GuardianSetDerivationData,
+        PendingMessageData,
+        PostedMessageData,
+        PostedVAA,
+        PostedVAADerivationData,
+        SignatureSet,
+    },
+    error::Error::{
+        AccountNotExpired,
+        InvalidPayer,
+        MathOverflow,
+    },
+    types::Trailing,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+    sysvar::clock::Clock,
+};
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct CloseSignatureSet<'b> {
+    /// Signature set to close
+    pub signature_set: Close<Mut<SignatureSet<'b, { AccountState::Initialized }>>, "payer">,
+
+    /// VAA posted with the signature set's hash, proving the set is no longer needed.
+    #[seeds(&PostedVAADerivationData { payload_hash: signature_set.hash.to_vec() })]
+    pub posted_vaa: PostedVAA<'b, { AccountState::Initialized }>,
+
+    /// Original payer of the signature set, receives the refund.
+    pub payer: Mut<Info<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for CloseSignatureSet<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct CloseSignatureSetData {}
+
+pub fn close_signature_set(
+    _ctx: &ExecutionContext,
+    accs: &mut CloseSignatureSet,
+    _data: CloseSignatureSetData,
+) -> Result<()> {
+    check_payer(&accs.payer, &accs.signature_set.payer)
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct ClosePostedMessage<'b> {
+    /// Bridge config holding the retention period.
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
+    /// Message to close, posted or still pending. Parsed by the handler as it can be either.
+    pub message: Close<Mut<Info<'b>>, "payer">,
+
+    /// Original payer of the message, receives the refund.
+    pub payer: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'b> InstructionContext<'b> for ClosePostedMessage<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct ClosePostedMessageData {}
+
+pub fn close_posted_message(
+    ctx: &ExecutionContext,
+    accs: &mut ClosePostedMessage,
+    _data: ClosePostedMessageData,
+) -> Result<()> {
+    if accs.message.owner != ctx.program_id {
+        return Err(SolitaireError::InvalidOwner(*accs.message.owner));
+    }
+
+    // PostedVAA accounts share the message layout, their tag makes sure only (pending) messages
+    // are closed.
+    let message = {
+        let data = accs.message.data.borrow();
+        match from_account_data::<PostedMessageData>(&data) {
+            Ok(message) => message.0,
+            Err(_) => from_account_data::<PendingMessageData>(&data)?.message,
+        }
+    };
+
+    let closable_slot = message
+        .posted_slot
+        .0
+        .ok_or(InvalidPayer)?
+        .checked_add(
+            accs.bridge
+                .current_config(&accs.extension)
+                .message_retention_slots,
+        )
+        .ok_or(MathOverflow)?;
+    if accs.clock.slot < closable_slot {
+        return Err(AccountNotExpired.into());
+    }
+
+    check_payer(&accs.payer, &message.payer)
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct CloseGuardianSet<'b> {
+    /// Bridge config
+    pub bridge: Bridge<'b, { AccountState::Initialized }>,
+
+    /// Expired guardian set to close
+    #[seeds(&GuardianSetDerivationData { index: guardian_set.index })]
+    pub guardian_set: Close<Mut<GuardianSet<'b, { AccountState::Initialized }>>, "payer">,
+
+    /// Original payer of the guardian set, receives the refund.
+    pub payer: Mut<Info<'b>>,
+
+    pub clock: Sysvar<'b, Clock>,
+}
+
+impl<'b> InstructionContext<'b> for CloseGuardianSet<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct CloseGuardianSetData {}
+
+pub fn close_guardian_set(
+    _ctx: &ExecutionContext,
+    accs: &mut CloseGuardianSet,
+    _data: CloseGuardianSetData,
+) -> Result<()> {
+    // The active set never expires, only sets that were replaced can be closed.
+    if accs.guardian_set.index == accs.bridge.guardian_set_index
+        || accs.guardian_set.expiration_time == 0
+        || accs.clock.unix_timestamp <= accs.guardian_set.expiration_time as i64
+    {
+        return Err(AccountNotExpired.into());
+    }
+
+    check_payer(&accs.payer, &accs.guardian_set.payer)
+}
+
+/// Closed accounts are refunded to the payer recorded in them only, the `Close` layer moves the
+/// lamports once the instruction succeeds. Accounts created before payers were recorded have no one
+/// to refund and stay open.
+fn check_payer(recipient: &AccountInfo, payer: &Trailing<Pubkey>) -> Result<()> {
+    if payer.0 != Some(*recipient.key) {
+        return Err(InvalidPayer.into());
+    }
+    Ok(())
+}
@@ -15,7 +15,10 @@ use solitaire::{
 
 use crate::{
     accounts::{
+        has_room,
         Bridge,
+        BridgeConfig,
+        BridgeExtension,
         GuardianSet,
         GuardianSetDerivationData,
     },
@@ -24,36 +27,66 @@ use crate::{
         InvalidGovernanceKey,
         InvalidGovernanceWithdrawal,
         InvalidGuardianSetUpgrade,
+        MessageRetentionTooShort,
+        TooManyGuardians,
     },
+    events::Event,
     types::{
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGovernanceEmitter,
+        GovernancePayloadSetGuardianSetExpiration,
         GovernancePayloadSetMessageFee,
+        GovernancePayloadSetMessageRetention,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
+        Trailing,
     },
     vaa::ClaimableVAA,
     DeserializePayload,
-    CHAIN_ID_SOLANA,
+    MAX_LEN_GUARDIAN_KEYS,
+    MIN_MESSAGE_RETENTION_SLOTS,
 };
 
-fn verify_governance<'a, T>(vaa: &ClaimableVAA<'a, T>) -> Result<()>
+fn verify_governance<'a, T>(config: &BridgeConfig, vaa: &ClaimableVAA<'a, T>) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!(
-        "{}",
-        Pubkey::new_from_array(vaa.message.meta().emitter_address)
-    );
-    // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.message.meta().emitter_chain != CHAIN_ID_SOLANA {
+    // Fail if the emitter is not the known governance key, or the emitting chain is not the
+    // governance chain recorded in the bridge config.
+    if vaa.message.meta().emitter_address != config.governance_emitter
+        || vaa.message.meta().emitter_chain != config.governance_chain
+    {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
     }
 }
 
-#[derive(FromAccounts)]
+/// Store the settings legacy bridge accounts have no room for. They go to the bridge extension
+/// instead, which is created the first time one of them changes.
+fn store_extended_config(
+    ctx: &ExecutionContext,
+    bridge: &mut Mut<Bridge<'_, { AccountState::Initialized }>>,
+    extension: &mut Mut<BridgeExtension<'_, { AccountState::MaybeInitialized }>>,
+    payer: &Pubkey,
+    config: BridgeConfig,
+) -> Result<()> {
+    if has_room(bridge) {
+        bridge.config = config;
+        return Ok(());
+    }
+
+    extension.governance_chain = config.governance_chain;
+    extension.governance_emitter = config.governance_emitter;
+    extension.message_retention_slots = config.message_retention_slots;
+    if !extension.is_initialized() {
+        extension.create(ctx, payer, Exempt)?;
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts, ToInstruction)]
 pub struct UpgradeContract<'b> {
     /// Payer for account creation (vaa-claim)
     pub payer: Mut<Signer<Info<'b>>>,
@@ -61,6 +94,9 @@ pub struct UpgradeContract<'b> {
     /// Bridge config
     pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
 
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
     /// GuardianSet change VAA
     pub vaa: ClaimableVAA<'b, GovernancePayloadUpgrade>,
 
@@ -89,7 +125,7 @@ pub struct UpgradeContract<'b> {
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -97,8 +133,7 @@ pub fn upgrade_contract(
     accs: &mut UpgradeContract,
     _data: UpgradeContractData,
 ) -> Result<()> {
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(ctx.program_id)?;
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     let upgrade_ix = solana_program::bpf_loader_upgradeable::upgrade(
@@ -118,7 +153,27 @@ pub fn upgrade_contract(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+/// Reject guardian sets that could never produce a verifiable quorum: empty sets, sets larger than
+/// a signer map can address, and sets with zero or duplicate keys.
+fn check_guardian_set(keys: &[[u8; 20]]) -> Result<()> {
+    if keys.len() > MAX_LEN_GUARDIAN_KEYS {
+        return Err(TooManyGuardians.into());
+    }
+
+    if keys.is_empty() {
+        return Err(InvalidGuardianSetUpgrade.into());
+    }
+
+    for (i, key) in keys.iter().enumerate() {
+        if *key == [0u8; 20] || keys[..i].contains(key) {
+            return Err(InvalidGuardianSetUpgrade.into());
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(FromAccounts, ToInstruction)]
 pub struct UpgradeGuardianSet<'b> {
     /// Payer for account creation (vaa-claim)
     pub payer: Mut<Signer<Info<'b>>>,
@@ -126,20 +181,27 @@ pub struct UpgradeGuardianSet<'b> {
     /// Bridge config
     pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
 
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
     /// GuardianSet change VAA
     pub vaa: ClaimableVAA<'b, GovernancePayloadGuardianSetChange>,
 
     /// Old guardian set
+    #[seeds(&GuardianSetDerivationData {
+        index: vaa.new_guardian_set_index.checked_sub(1).ok_or(InvalidGuardianSetUpgrade)?,
+    })]
     pub guardian_set_old: Mut<GuardianSet<'b, { AccountState::Initialized }>>,
 
     /// New guardian set
+    #[seeds(&GuardianSetDerivationData { index: vaa.new_guardian_set_index })]
     pub guardian_set_new: Mut<GuardianSet<'b, { AccountState::Uninitialized }>>,
 }
 
 impl<'b> InstructionContext<'b> for UpgradeGuardianSet<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct UpgradeGuardianSetData {}
 
 pub fn upgrade_guardian_set(
@@ -148,29 +210,22 @@ pub fn upgrade_guardian_set(
     _data: UpgradeGuardianSetData,
 ) -> Result<()> {
     // Enforce single increments when upgrading.
-    if accs.guardian_set_old.index != accs.vaa.new_guardian_set_index - 1 {
+    let old_index = accs
+        .vaa
+        .new_guardian_set_index
+        .checked_sub(1)
+        .ok_or(InvalidGuardianSetUpgrade)?;
+    if accs.guardian_set_old.index != old_index {
         return Err(InvalidGuardianSetUpgrade.into());
     }
 
     // Confirm that the version the bridge has active is the previous version.
-    if accs.bridge.guardian_set_index != accs.vaa.new_guardian_set_index - 1 {
+    if accs.bridge.guardian_set_index != old_index {
         return Err(InvalidGuardianSetUpgrade.into());
     }
 
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(ctx.program_id)?;
-    accs.guardian_set_old.verify_derivation(
-        ctx.program_id,
-        &GuardianSetDerivationData {
-            index: accs.vaa.new_guardian_set_index - 1,
-        },
-    )?;
-    accs.guardian_set_new.verify_derivation(
-        ctx.program_id,
-        &GuardianSetDerivationData {
-            index: accs.vaa.new_guardian_set_index,
-        },
-    )?;
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
+    check_guardian_set(&accs.vaa.new_guardian_set)?;
 
     accs.vaa.claim(ctx, accs.payer.key)?;
 
@@ -182,6 +237,7 @@ pub fn upgrade_guardian_set(
     accs.guardian_set_new.index = accs.vaa.new_guardian_set_index;
     accs.guardian_set_new.creation_time = accs.vaa.meta().vaa_time;
     accs.guardian_set_new.keys = accs.vaa.new_guardian_set.clone();
+    accs.guardian_set_new.payer = Trailing(Some(*accs.payer.key));
 
     // Create new guardian set
     // This is done after populating it to properly allocate space according to key vec length.
@@ -197,10 +253,16 @@ pub fn upgrade_guardian_set(
     // Set guardian set index
     accs.bridge.guardian_set_index = accs.vaa.new_guardian_set_index;
 
+    Event::GuardianSetUpgraded {
+        old_index: accs.guardian_set_old.index,
+        new_index: accs.guardian_set_new.index,
+    }
+    .emit();
+
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct SetFees<'b> {
     /// Payer for account creation (vaa-claim)
     pub payer: Mut<Signer<Info<'b>>>,
@@ -208,6 +270,9 @@ pub struct SetFees<'b> {
     /// Bridge config
     pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
 
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
     /// Governance VAA
     pub vaa: ClaimableVAA<'b, GovernancePayloadSetMessageFee>,
 }
@@ -215,19 +280,57 @@ pub struct SetFees<'b> {
 impl<'b> InstructionContext<'b> for SetFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct SetFeesData {}
 
 pub fn set_fees(ctx: &ExecutionContext, accs: &mut SetFees, _data: SetFeesData) -> Result<()> {
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(ctx.program_id)?;
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
     accs.bridge.config.fee = accs.vaa.fee.as_u64();
 
+    Event::FeesSet {
+        fee: accs.bridge.config.fee,
+    }
+    .emit();
+
+    Ok(())
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct SetGuardianSetExpiration<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
+    /// Governance VAA
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetGuardianSetExpiration>,
+}
+
+impl<'b> InstructionContext<'b> for SetGuardianSetExpiration<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct SetGuardianSetExpirationData {}
+
+/// Changes how long a replaced guardian set stays valid, applying from the next rotation on.
+pub fn set_guardian_set_expiration(
+    ctx: &ExecutionContext,
+    accs: &mut SetGuardianSetExpiration,
+    _data: SetGuardianSetExpirationData,
+) -> Result<()> {
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+    accs.bridge.config.guardian_set_expiration_time = accs.vaa.expiration_time;
+
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct TransferFees<'b> {
     /// Payer for account creation (vaa-claim)
     pub payer: Mut<Signer<Info<'b>>>,
@@ -235,6 +338,9 @@ pub struct TransferFees<'b> {
     /// Bridge config
     pub bridge: Bridge<'b, { AccountState::Initialized }>,
 
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: BridgeExtension<'b, { AccountState::MaybeInitialized }>,
+
     /// Governance VAA
     pub vaa: ClaimableVAA<'b, GovernancePayloadTransferFees>,
 
@@ -251,7 +357,7 @@ pub struct TransferFees<'b> {
 impl<'b> InstructionContext<'b> for TransferFees<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct TransferFeesData {}
 
 pub fn transfer_fees(
@@ -264,8 +370,7 @@ pub fn transfer_fees(
         return Err(InvalidFeeRecipient.into());
     }
 
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(ctx.program_id)?;
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
 
     if accs
         .fee_collector
@@ -290,5 +395,97 @@ pub fn transfer_fees(
     let seeds = seeds.as_slice();
     invoke_signed(&transfer_ix, ctx.accounts, &[seeds])?;
 
+    Event::FeesTransferred {
+        recipient: *accs.recipient.key,
+        amount: accs.vaa.amount.as_u64(),
+    }
+    .emit();
+
     Ok(())
 }
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct SetGovernanceEmitter<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: Mut<BridgeExtension<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Governance VAA
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetGovernanceEmitter>,
+}
+
+impl<'b> InstructionContext<'b> for SetGovernanceEmitter<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct SetGovernanceEmitterData {}
+
+pub fn set_governance_emitter(
+    ctx: &ExecutionContext,
+    accs: &mut SetGovernanceEmitter,
+    _data: SetGovernanceEmitterData,
+) -> Result<()> {
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    let mut config = accs.bridge.current_config(&accs.extension);
+    config.governance_chain = accs.vaa.governance_chain;
+    config.governance_emitter = accs.vaa.governance_emitter;
+    store_extended_config(
+        ctx,
+        &mut accs.bridge,
+        &mut accs.extension,
+        accs.payer.key,
+        config,
+    )
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct SetMessageRetention<'b> {
+    /// Payer for account creation (vaa-claim)
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Bridge config
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Settings of legacy bridges that the bridge account has no room for
+    pub extension: Mut<BridgeExtension<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Governance VAA
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetMessageRetention>,
+}
+
+impl<'b> InstructionContext<'b> for SetMessageRetention<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct SetMessageRetentionData {}
+
+/// Changes how many slots posted messages are kept before their accounts can be closed, which
+/// also applies to messages already posted.
+pub fn set_message_retention(
+    ctx: &ExecutionContext,
+    accs: &mut SetMessageRetention,
+    _data: SetMessageRetentionData,
+) -> Result<()> {
+    verify_governance(&accs.bridge.current_config(&accs.extension), &accs.vaa)?;
+    if accs.vaa.message_retention_slots < MIN_MESSAGE_RETENTION_SLOTS {
+        return Err(MessageRetentionTooShort.into());
+    }
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    let mut config = accs.bridge.current_config(&accs.extension);
+    config.message_retention_slots = accs.vaa.message_retention_slots;
+    store_extended_config(
+        ctx,
+        &mut accs.bridge,
+        &mut accs.extension,
+        accs.payer.key,
+        config,
+    )
+}
@@ -6,8 +6,14 @@ use crate::{
         GuardianSet,
         GuardianSetDerivationData,
     },
-    error::Error::TooManyGuardians,
+    api::ForeignAddress,
+    error::Error::{
+        MessageRetentionTooShort,
+        TooManyGuardians,
+    },
+    types::Trailing,
     MAX_LEN_GUARDIAN_KEYS,
+    MIN_MESSAGE_RETENTION_SLOTS,
 };
 use solana_program::sysvar::clock::Clock;
 use solitaire::{
@@ -17,7 +23,7 @@ use solitaire::{
 
 type Payer<'a> = Signer<Info<'a>>;
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct Initialize<'b> {
     /// Bridge config.
     pub bridge: Mut<Bridge<'b, { AccountState::Uninitialized }>>,
@@ -38,7 +44,7 @@ pub struct Initialize<'b> {
 impl<'b> InstructionContext<'b> for Initialize<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct InitializeData {
     /// Period for how long a guardian set is valid after it has been replaced by a new one.  This
     /// guarantees that VAAs issued by that set can still be submitted for a certain period.  In
@@ -50,6 +56,16 @@ pub struct InitializeData {
 
     /// Initial Guardian Set
     pub initial_guardians: Vec<[u8; 20]>,
+
+    /// Chain the governance emitter lives on.
+    pub governance_chain: u16,
+
+    /// Address of the emitter that is allowed to issue governance VAAs.
+    pub governance_emitter: ForeignAddress,
+
+    /// Number of slots a posted message must exist before its account can be closed, at least
+    /// `MIN_MESSAGE_RETENTION_SLOTS`.
+    pub message_retention_slots: u64,
 }
 
 pub fn initialize(
@@ -63,10 +79,15 @@ pub fn initialize(
         return Err(TooManyGuardians.into());
     }
 
+    if data.message_retention_slots < MIN_MESSAGE_RETENTION_SLOTS {
+        return Err(MessageRetentionTooShort.into());
+    }
+
     // Allocate initial guardian set with the provided keys.
     accs.guardian_set.index = index;
     accs.guardian_set.creation_time = accs.clock.unix_timestamp as u32;
     accs.guardian_set.keys.extend(&data.initial_guardians);
+    accs.guardian_set.payer = Trailing(Some(*accs.payer.key));
 
     // Initialize Guardian Set
     accs.guardian_set.create(
@@ -82,6 +103,9 @@ pub fn initialize(
     accs.bridge.config = BridgeConfig {
         guardian_set_expiration_time: data.guardian_set_expiration_time,
         fee: data.fee,
+        governance_chain: data.governance_chain,
+        governance_emitter: data.governance_emitter,
+        message_retention_slots: data.message_retention_slots,
     };
 
     // Initialize the fee collector account so it's rent exempt and will keep funds
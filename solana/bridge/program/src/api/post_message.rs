@@ -2,19 +2,30 @@ use crate::{
     accounts::{
         Bridge,
         FeeCollector,
+        MessageData,
+        MessageDerivationData,
+        PendingMessage,
         PostedMessage,
         Sequence,
         SequenceDerivationData,
     },
     error::Error::{
         InsufficientFees,
+        InvalidEmitter,
         MathOverflow,
+        MessageOverflow,
+    },
+    events::Event,
+    types::{
+        ConsistencyLevel,
+        Trailing,
     },
-    types::ConsistencyLevel,
     CHAIN_ID_SOLANA,
+    MAX_LEN_MESSAGE_PAYLOAD,
 };
 use solana_program::{
     msg,
+    pubkey::Pubkey,
     sysvar::clock::Clock,
 };
 use solitaire::{
@@ -34,7 +45,7 @@ impl<'a> From<&PostMessage<'a>> for SequenceDerivationData<'a> {
     }
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct PostMessage<'b> {
     /// Bridge config needed for fee calculation.
     pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
@@ -53,14 +64,12 @@ pub struct PostMessage<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<FeeCollector<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'b> InstructionContext<'b> for PostMessage<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
 pub struct PostMessageData {
     /// Unique nonce for this message
     pub nonce: u32,
@@ -84,25 +93,344 @@ pub fn post_message(
     accs.sequence
         .verify_derivation(ctx.program_id, &(&*accs).into())?;
 
-    let fee = accs.bridge.config.fee;
-    // Fee handling, checking previously known balance allows us to not care who is the payer of
-    // this submission.
-    if accs
-        .fee_collector
+    check_fees(&mut accs.bridge, &accs.fee_collector)?;
+
+    // Init sequence tracker if it does not exist yet.
+    if !accs.sequence.is_initialized() {
+        trace!("Initializing Sequence account to 0.");
+        accs.sequence
+            .create(&(&*accs).into(), ctx, accs.payer.key, Exempt)?;
+    }
+
+    // DO NOT REMOVE - CRITICAL OUTPUT
+    msg!("Sequence: {}", accs.sequence.sequence);
+    Event::MessagePosted {
+        emitter: *accs.emitter.key,
+        sequence: accs.sequence.sequence,
+        message: *accs.message.info().key,
+    }
+    .emit();
+
+    // Initialize transfer
+    set_message_details(
+        &mut accs.message,
+        accs.emitter.key,
+        accs.sequence.sequence,
+        accs.payer.key,
+        &Sysvar::<Clock>::get()?,
+        data,
+    );
+
+    // Create message account
+    let size = accs.message.size();
+    let ix = solana_program::system_instruction::create_account(
+        accs.payer.key,
+        accs.message.info().key,
+        Exempt.amount(size)?,
+        size as u64,
+        ctx.program_id,
+    );
+    solana_program::program::invoke(&ix, ctx.accounts)?;
+
+    // Bump sequence number
+    trace!("New Sequence: {}", accs.sequence.sequence + 1);
+    accs.sequence.sequence += 1;
+
+    Ok(())
+}
+
+impl<'a> From<&PostMessageDerived<'a>> for SequenceDerivationData<'a> {
+    fn from(accs: &PostMessageDerived<'a>) -> Self {
+        SequenceDerivationData {
+            emitter_key: accs.emitter.key,
+        }
+    }
+}
+
+/// Accounts are laid out exactly like `PostMessage` so guardians can treat both the same way.
+#[derive(FromAccounts, ToInstruction)]
+pub struct PostMessageDerived<'b> {
+    /// Bridge config needed for fee calculation.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Account to store the posted message, derived from the emitter and its next sequence.
+    pub message: Mut<UninitializedMessage<'b>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<FeeCollector<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for PostMessageDerived<'b> {
+}
+
+/// Same as `post_message`, but the message account is a PDA so emitters calling in over CPI don't
+/// need to provide an extra signer.
+pub fn post_message_derived(
+    ctx: &ExecutionContext,
+    accs: &mut PostMessageDerived,
+    data: PostMessageData,
+) -> Result<()> {
+    trace!("Message Address: {}", accs.message.info().key);
+    trace!("Emitter Address: {}", accs.emitter.info().key);
+    trace!("Nonce: {}", data.nonce);
+
+    accs.sequence
+        .verify_derivation(ctx.program_id, &(&*accs).into())?;
+
+    let message_derivation = MessageDerivationData {
+        emitter_key: accs.emitter.key,
+        sequence: accs.sequence.sequence,
+    };
+    accs.message
+        .verify_derivation(ctx.program_id, &message_derivation)?;
+
+    check_fees(&mut accs.bridge, &accs.fee_collector)?;
+
+    // Init sequence tracker if it does not exist yet.
+    if !accs.sequence.is_initialized() {
+        trace!("Initializing Sequence account to 0.");
+        accs.sequence
+            .create(&(&*accs).into(), ctx, accs.payer.key, Exempt)?;
+    }
+
+    // DO NOT REMOVE - CRITICAL OUTPUT
+    msg!("Sequence: {}", accs.sequence.sequence);
+    Event::MessagePosted {
+        emitter: *accs.emitter.key,
+        sequence: accs.sequence.sequence,
+        message: *accs.message.info().key,
+    }
+    .emit();
+
+    set_message_details(
+        &mut accs.message,
+        accs.emitter.key,
+        accs.sequence.sequence,
+        accs.payer.key,
+        &Sysvar::<Clock>::get()?,
+        data,
+    );
+    accs.message
+        .create(&message_derivation, ctx, accs.payer.key, Exempt)?;
+
+    // Bump sequence number
+    trace!("New Sequence: {}", accs.sequence.sequence + 1);
+    accs.sequence.sequence += 1;
+
+    Ok(())
+}
+
+fn set_message_details(
+    message: &mut MessageData,
+    emitter: &Pubkey,
+    sequence: u64,
+    payer: &Pubkey,
+    clock: &Clock,
+    data: PostMessageData,
+) {
+    trace!("Setting Message Details");
+    message.submission_time = clock.unix_timestamp as u32;
+    message.emitter_chain = CHAIN_ID_SOLANA;
+    message.emitter_address = emitter.to_bytes();
+    message.nonce = data.nonce;
+    message.payload = data.payload;
+    message.sequence = sequence;
+    message.consistency_level = match data.consistency_level {
+        ConsistencyLevel::Confirmed => 1,
+        ConsistencyLevel::Finalized => 32,
+    };
+    message.payer = Trailing(Some(*payer));
+    message.posted_slot = Trailing(Some(clock.slot));
+}
+
+/// Fee handling, checking previously known balance allows us to not care who is the payer of
+/// this submission.
+fn check_fees<'b>(
+    bridge: &mut Mut<Bridge<'b, { AccountState::Initialized }>>,
+    fee_collector: &Mut<FeeCollector<'b>>,
+) -> Result<()> {
+    let fee = bridge.config.fee;
+    if fee_collector
         .lamports()
-        .checked_sub(accs.bridge.last_lamports)
+        .checked_sub(bridge.last_lamports)
         .ok_or(MathOverflow)?
         < fee
     {
         trace!(
             "Expected fee not found: fee, last_lamports, collector: {} {} {}",
             fee,
-            accs.bridge.last_lamports,
-            accs.fee_collector.lamports(),
+            bridge.last_lamports,
+            fee_collector.lamports(),
         );
         return Err(InsufficientFees.into());
     }
-    accs.bridge.last_lamports = accs.fee_collector.lamports();
+    bridge.last_lamports = fee_collector.lamports();
+    Ok(())
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct InitializeMessage<'b> {
+    /// Account to store the message while its payload is written
+    pub message: Signer<Mut<PendingMessage<'b, { AccountState::Uninitialized }>>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+impl<'b> InstructionContext<'b> for InitializeMessage<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
+pub struct InitializeMessageData {
+    /// Total length of the payload that will be written, at most `MAX_LEN_MESSAGE_PAYLOAD`
+    pub payload_len: u32,
+}
+
+pub fn initialize_message(
+    ctx: &ExecutionContext,
+    accs: &mut InitializeMessage,
+    data: InitializeMessageData,
+) -> Result<()> {
+    trace!("Message Address: {}", accs.message.info().key);
+    trace!("Emitter Address: {}", accs.emitter.info().key);
+
+    if data.payload_len as usize > MAX_LEN_MESSAGE_PAYLOAD {
+        return Err(MessageOverflow.into());
+    }
+
+    accs.message.emitter_chain = CHAIN_ID_SOLANA;
+    accs.message.emitter_address = accs.emitter.key.to_bytes();
+    accs.message.payload = vec![0u8; data.payload_len as usize];
+    accs.message.payer = Trailing(Some(*accs.payer.key));
+    accs.message.posted_slot = Trailing(Some(Sysvar::<Clock>::get()?.slot));
+
+    // Create message account, sized for the full payload up front.
+    let size = accs.message.size();
+    let ix = solana_program::system_instruction::create_account(
+        accs.payer.key,
+        accs.message.info().key,
+        Exempt.amount(size)?,
+        size as u64,
+        ctx.program_id,
+    );
+    solana_program::program::invoke(&ix, ctx.accounts)?;
+
+    Ok(())
+}
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct WriteMessage<'b> {
+    /// Message the payload chunk is written into
+    pub message: Mut<PendingMessage<'b, { AccountState::Initialized }>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+}
+
+impl<'b> InstructionContext<'b> for WriteMessage<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
+pub struct WriteMessageData {
+    /// Offset into the payload at which this chunk is written
+    pub offset: u32,
+
+    /// Chunk of the message payload
+    pub data: Vec<u8>,
+}
+
+pub fn write_message(
+    _ctx: &ExecutionContext,
+    accs: &mut WriteMessage,
+    data: WriteMessageData,
+) -> Result<()> {
+    if accs.message.emitter_address != accs.emitter.key.to_bytes() {
+        return Err(InvalidEmitter.into());
+    }
+
+    // Chunks must fit into the payload that was allocated when the message was initialized.
+    let start = data.offset as usize;
+    let end = start.checked_add(data.data.len()).ok_or(MessageOverflow)?;
+    if end > accs.message.payload.len() {
+        return Err(MessageOverflow.into());
+    }
+
+    accs.message.payload[start..end].copy_from_slice(&data.data);
+
+    Ok(())
+}
+
+impl<'a> From<&FinalizeMessage<'a>> for SequenceDerivationData<'a> {
+    fn from(accs: &FinalizeMessage<'a>) -> Self {
+        SequenceDerivationData {
+            emitter_key: accs.emitter.key,
+        }
+    }
+}
+
+/// Accounts are laid out exactly like `PostMessage` so guardians can treat both the same way.
+#[derive(FromAccounts, ToInstruction)]
+pub struct FinalizeMessage<'b> {
+    /// Bridge config needed for fee calculation.
+    pub bridge: Mut<Bridge<'b, { AccountState::Initialized }>>,
+
+    /// Message to finalize
+    pub message: Mut<PendingMessage<'b, { AccountState::Initialized }>>,
+
+    /// Emitter of the VAA
+    pub emitter: Signer<MaybeMut<Info<'b>>>,
+
+    /// Tracker for the emitter sequence
+    pub sequence: Mut<Sequence<'b>>,
+
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Account to collect tx fee
+    pub fee_collector: Mut<FeeCollector<'b>>,
+}
+
+impl<'b> InstructionContext<'b> for FinalizeMessage<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
+pub struct FinalizeMessageData {
+    /// Unique nonce for this message
+    pub nonce: u32,
+
+    /// Commitment Level required for an attestation to be produced
+    pub consistency_level: ConsistencyLevel,
+}
+
+pub fn finalize_message(
+    ctx: &ExecutionContext,
+    accs: &mut FinalizeMessage,
+    data: FinalizeMessageData,
+) -> Result<()> {
+    trace!("Message Address: {}", accs.message.info().key);
+    trace!("Emitter Address: {}", accs.emitter.info().key);
+    trace!("Nonce: {}", data.nonce);
+
+    if accs.message.emitter_address != accs.emitter.key.to_bytes() {
+        return Err(InvalidEmitter.into());
+    }
+
+    accs.sequence
+        .verify_derivation(ctx.program_id, &(&*accs).into())?;
+
+    check_fees(&mut accs.bridge, &accs.fee_collector)?;
 
     // Init sequence tracker if it does not exist yet.
     if !accs.sequence.is_initialized() {
@@ -113,30 +441,25 @@ pub fn post_message(
 
     // DO NOT REMOVE - CRITICAL OUTPUT
     msg!("Sequence: {}", accs.sequence.sequence);
+    Event::MessagePosted {
+        emitter: *accs.emitter.key,
+        sequence: accs.sequence.sequence,
+        message: *accs.message.info().key,
+    }
+    .emit();
 
-    // Initialize transfer
+    // Finalize the message, from here on it is stored as a regular PostedMessage.
     trace!("Setting Message Details");
-    accs.message.submission_time = accs.clock.unix_timestamp as u32;
-    accs.message.emitter_chain = CHAIN_ID_SOLANA;
-    accs.message.emitter_address = accs.emitter.key.to_bytes();
+    let clock = Sysvar::<Clock>::get()?;
+    accs.message.submission_time = clock.unix_timestamp as u32;
     accs.message.nonce = data.nonce;
-    accs.message.payload = data.payload;
     accs.message.sequence = accs.sequence.sequence;
     accs.message.consistency_level = match data.consistency_level {
         ConsistencyLevel::Confirmed => 1,
         ConsistencyLevel::Finalized => 32,
     };
-
-    // Create message account
-    let size = accs.message.size();
-    let ix = solana_program::system_instruction::create_account(
-        accs.payer.key,
-        accs.message.info().key,
-        Exempt.amount(size),
-        size as u64,
-        ctx.program_id,
-    );
-    solana_program::program::invoke(&ix, ctx.accounts)?;
+    accs.message.posted_slot = Trailing(Some(clock.slot));
+    accs.message.finalized = true;
 
     // Bump sequence number
     trace!("New Sequence: {}", accs.sequence.sequence + 1);
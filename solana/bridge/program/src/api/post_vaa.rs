@@ -17,15 +17,19 @@ use crate::{
         PostedVAA,
         PostedVAADerivationData,
         SignatureSet,
+        VAABuffer,
     },
     error::Error::{
         GuardianSetMismatch,
         PostVAAConsensusFailed,
         PostVAAGuardianSetExpired,
     },
+    events::Event,
+    types::Trailing,
 };
 use byteorder::{
     BigEndian,
+    ReadBytesExt,
     WriteBytesExt,
 };
 use serde::{
@@ -33,13 +37,14 @@ use serde::{
     Serialize,
 };
 use sha3::Digest;
-use solana_program::program_error::ProgramError;
-use solitaire::{
-    processors::seeded::Seeded,
-    CreationLamports::Exempt,
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
 };
+use solitaire::CreationLamports::Exempt;
 use std::io::{
     Cursor,
+    Read,
     Write,
 };
 
@@ -51,9 +56,15 @@ impl From<&PostVAAData> for GuardianSetDerivationData {
     }
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
+#[instruction(PostVAAData)]
+#[strict]
 pub struct PostVAA<'b> {
     /// Information about the current guardian set.
+    #[seeds(
+        &GuardianSetDerivationData { index: data.guardian_set_index },
+        bump = data.guardian_set_bump.0
+    )]
     pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
 
     /// Bridge Info
@@ -63,13 +74,11 @@ pub struct PostVAA<'b> {
     pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
 
     /// Message the VAA is associated with.
+    #[seeds(&PostedVAADerivationData { payload_hash: signature_set.hash.to_vec() })]
     pub message: Mut<PostedVAA<'b, { AccountState::MaybeInitialized }>>,
 
     /// Account used to pay for auxillary instructions.
     pub payer: Mut<Signer<Info<'b>>>,
-
-    /// Clock used for timestamping.
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'b> InstructionContext<'b> for PostVAA<'b> {
@@ -85,7 +94,7 @@ pub struct Signature {
 
 pub type ForeignAddress = [u8; 32];
 
-#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize, IdlSchema)]
 pub struct PostVAAData {
     // Header part
     pub version: u8,
@@ -99,34 +108,108 @@ pub struct PostVAAData {
     pub sequence: u64,
     pub consistency_level: u8,
     pub payload: Vec<u8>,
+
+    /// Bump of the guardian set derivation, which spares the program searching for it. Not part
+    /// of the VAA.
+    #[serde(default)]
+    pub guardian_set_bump: Trailing<u8>,
 }
 
 pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) -> Result<()> {
-    let msg_derivation = PostedVAADerivationData {
-        payload_hash: accs.signature_set.hash.to_vec(),
-    };
+    process_vaa(
+        ctx,
+        &accs.guardian_set,
+        &accs.signature_set,
+        &mut accs.message,
+        accs.payer.key,
+        vaa,
+    )
+}
 
-    accs.message
-        .verify_derivation(ctx.program_id, &msg_derivation)?;
-    accs.guardian_set
-        .verify_derivation(ctx.program_id, &(&vaa).into())?;
+#[derive(FromAccounts, ToInstruction)]
+#[instruction(PostVAAFromBufferData)]
+#[strict]
+pub struct PostVAAFromBuffer<'b> {
+    /// Information about the current guardian set.
+    #[seeds(
+        &GuardianSetDerivationData { index: data.guardian_set_index },
+        bump = data.guardian_set_bump.0
+    )]
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Bridge Info
+    pub bridge_info: Bridge<'b, { AccountState::Initialized }>,
 
-    if accs.message.is_initialized() {
+    /// Signature Info
+    pub signature_set: SignatureSet<'b, { AccountState::Initialized }>,
+
+    /// Buffer holding the serialized VAA body.
+    pub buffer: VAABuffer<'b, { AccountState::Initialized }>,
+
+    /// Message the VAA is associated with.
+    #[seeds(&PostedVAADerivationData { payload_hash: signature_set.hash.to_vec() })]
+    pub message: Mut<PostedVAA<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Account used to pay for auxillary instructions.
+    pub payer: Mut<Signer<Info<'b>>>,
+}
+
+impl<'b> InstructionContext<'b> for PostVAAFromBuffer<'b> {
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize, IdlSchema)]
+pub struct PostVAAFromBufferData {
+    // Header part, the body is read from the buffer account.
+    pub version: u8,
+    pub guardian_set_index: u32,
+
+    /// Bump of the guardian set derivation, which spares the program searching for it.
+    pub guardian_set_bump: Trailing<u8>,
+}
+
+pub fn post_vaa_from_buffer(
+    ctx: &ExecutionContext,
+    accs: &mut PostVAAFromBuffer,
+    data: PostVAAFromBufferData,
+) -> Result<()> {
+    let vaa = parse_body(data.version, data.guardian_set_index, &accs.buffer.body)?;
+    process_vaa(
+        ctx,
+        &accs.guardian_set,
+        &accs.signature_set,
+        &mut accs.message,
+        accs.payer.key,
+        vaa,
+    )
+}
+
+/// Shared by `post_vaa` and `post_vaa_from_buffer`, verifies the VAA against its signature set
+/// and persists it into a PostedVAA account.
+fn process_vaa<'r>(
+    ctx: &ExecutionContext,
+    guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
+    signature_set: &SignatureSet<'r, { AccountState::Initialized }>,
+    message: &mut Mut<PostedVAA<'r, { AccountState::MaybeInitialized }>>,
+    payer: &Pubkey,
+    vaa: PostVAAData,
+) -> Result<()> {
+    if message.is_initialized() {
         return Ok(());
     }
 
     // Verify any required invariants before we process the instruction.
-    check_active(&accs.guardian_set, &accs.clock)?;
-    check_valid_sigs(&accs.guardian_set, &accs.signature_set)?;
-    check_integrity(&vaa, &accs.signature_set)?;
+    let clock = Sysvar::<Clock>::get()?;
+    check_active(guardian_set, &clock)?;
+    check_valid_sigs(guardian_set, signature_set)?;
+    check_integrity(&vaa, signature_set)?;
 
     // Count the number of signatures currently present.
-    let signature_count: usize = accs.signature_set.signatures.iter().filter(|v| **v).count();
+    let signature_count: usize = signature_set.signatures.iter().filter(|v| **v).count();
 
     // Calculate how many signatures are required to reach consensus. This calculation is in
     // expanded form to ease auditing.
     let required_consensus_count = {
-        let len = accs.guardian_set.keys.len();
+        let len = guardian_set.keys.len();
         // Fixed point number transformation with one decimal to deal with rounding.
         let len = (len * 10) / 3;
         // Multiplication by two to get a 2/3 quorum.
@@ -140,26 +223,67 @@ pub fn post_vaa(ctx: &ExecutionContext, accs: &mut PostVAA, vaa: PostVAAData) ->
     }
 
     // Persist VAA data
-    accs.message.nonce = vaa.nonce;
-    accs.message.emitter_chain = vaa.emitter_chain;
-    accs.message.emitter_address = vaa.emitter_address;
-    accs.message.sequence = vaa.sequence;
-    accs.message.payload = vaa.payload;
-    accs.message.consistency_level = vaa.consistency_level;
-    accs.message.vaa_version = vaa.version;
-    accs.message.vaa_time = vaa.timestamp;
-    accs.message.vaa_signature_account = *accs.signature_set.info().key;
-    accs.message
-        .create(&msg_derivation, ctx, accs.payer.key, Exempt)?;
+    message.nonce = vaa.nonce;
+    message.emitter_chain = vaa.emitter_chain;
+    message.emitter_address = vaa.emitter_address;
+    message.sequence = vaa.sequence;
+    message.payload = vaa.payload;
+    message.consistency_level = vaa.consistency_level;
+    message.vaa_version = vaa.version;
+    message.vaa_time = vaa.timestamp;
+    message.vaa_signature_account = *signature_set.info().key;
+    message.payer = Trailing(Some(*payer));
+    message.posted_slot = Trailing(Some(clock.slot));
+    let msg_derivation = PostedVAADerivationData {
+        payload_hash: signature_set.hash.to_vec(),
+    };
+    message.create(&msg_derivation, ctx, payer, Exempt)?;
+
+    Event::VAAPosted {
+        emitter_chain: message.emitter_chain,
+        emitter_address: message.emitter_address,
+        sequence: message.sequence,
+        hash: signature_set.hash,
+        message: *message.info().key,
+    }
+    .emit();
 
     Ok(())
 }
 
+/// Rebuild a PostVAAData from a serialized VAA body, the inverse of the serialization performed in
+/// `check_integrity`.
+fn parse_body(version: u8, guardian_set_index: u32, body: &[u8]) -> Result<PostVAAData> {
+    let mut c = Cursor::new(body);
+    let timestamp = c.read_u32::<BigEndian>()?;
+    let nonce = c.read_u32::<BigEndian>()?;
+    let emitter_chain = c.read_u16::<BigEndian>()?;
+    let mut emitter_address = ForeignAddress::default();
+    c.read_exact(&mut emitter_address)?;
+    let sequence = c.read_u64::<BigEndian>()?;
+    let consistency_level = c.read_u8()?;
+    let mut payload = Vec::new();
+    c.read_to_end(&mut payload)?;
+
+    Ok(PostVAAData {
+        version,
+        guardian_set_index,
+        timestamp,
+        nonce,
+        emitter_chain,
+        emitter_address,
+        sequence,
+        consistency_level,
+        payload,
+        guardian_set_bump: Trailing(None),
+    })
+}
+
 /// A guardian set must not have expired.
 #[inline(always)]
 fn check_active<'r>(
     guardian_set: &GuardianSet<'r, { AccountState::Initialized }>,
-    clock: &Sysvar<'r, Clock>,
+    clock: &Clock,
 ) -> Result<()> {
     // IMPORTANT - this is a fix for mainnet wormhole
     // The initial guardian set was never expired so we block it here.
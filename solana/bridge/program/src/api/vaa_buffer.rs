@@ -0,0 +1,69 @@
+use solitaire::*;
+
+use crate::{
+    accounts::VAABuffer,
+    error::Error::VAABufferOverflow,
+    MAX_LEN_VAA_BODY,
+};
+use solitaire::CreationLamports::Exempt;
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct WriteVAABuffer<'b> {
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Buffer holding the VAA body, created on the first write.
+    pub buffer: Mut<Signer<VAABuffer<'b, { AccountState::MaybeInitialized }>>>,
+}
+
+impl<'b> InstructionContext<'b> for WriteVAABuffer<'b> {
+}
+
+#[derive(Default, BorshSerialize, BorshDeserialize, IdlSchema)]
+pub struct WriteVAABufferData {
+    /// Total length of the VAA body, only used when the buffer is created. At most
+    /// `MAX_LEN_VAA_BODY`.
+    pub length: u32,
+
+    /// Offset into the body at which this chunk is written.
+    pub offset: u32,
+
+    /// Chunk of the serialized VAA body.
+    pub data: Vec<u8>,
+}
+
+pub fn write_vaa_buffer(
+    ctx: &ExecutionContext,
+    accs: &mut WriteVAABuffer,
+    data: WriteVAABufferData,
+) -> Result<()> {
+    if !accs.buffer.is_initialized() {
+        if data.length as usize > MAX_LEN_VAA_BODY {
+            return Err(VAABufferOverflow.into());
+        }
+        accs.buffer.body = vec![0u8; data.length as usize];
+
+        let size = accs.buffer.size();
+        let ix = solana_program::system_instruction::create_account(
+            accs.payer.key,
+            accs.buffer.info().key,
+            Exempt.amount(size)?,
+            size as u64,
+            ctx.program_id,
+        );
+        solana_program::program::invoke(&ix, ctx.accounts)?;
+    }
+
+    // Chunks must fit into the space that was allocated when the buffer was created.
+    let start = data.offset as usize;
+    let end = start
+        .checked_add(data.data.len())
+        .ok_or(VAABufferOverflow)?;
+    if end > accs.buffer.body.len() {
+        return Err(VAABufferOverflow.into());
+    }
+
+    accs.buffer.body[start..end].copy_from_slice(&data.data);
+
+    Ok(())
+}
@@ -4,22 +4,27 @@ use crate::{
     GuardianSet,
     GuardianSetDerivationData,
     SignatureSet,
+    SignatureSetData,
+    SignatureSetDerivationData,
     error::Error::{
         GuardianSetMismatch,
         InstructionAtWrongIndex,
         InvalidHash,
         InvalidSecpInstruction,
     },
-    MAX_LEN_GUARDIAN_KEYS,
+    types::Trailing,
 };
 use byteorder::ByteOrder;
-use solana_program::program_error::ProgramError;
+use solana_program::{
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
 use solitaire::{
     processors::seeded::Seeded,
     CreationLamports::Exempt,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct VerifySignatures<'b> {
     /// Payer for account creation
     pub payer: Mut<Signer<Info<'b>>>,
@@ -31,12 +36,40 @@ pub struct VerifySignatures<'b> {
     pub signature_set: Mut<Signer<SignatureSet<'b, { AccountState::MaybeInitialized }>>>,
 
     /// Instruction reflection account (special sysvar)
+    #[key(solana_program::sysvar::instructions::id())]
     pub instruction_acc: Info<'b>,
 }
 
 impl<'b> InstructionContext<'b> for VerifySignatures<'b> {
 }
 
+#[derive(FromAccounts, ToInstruction)]
+pub struct VerifySignaturesDerived<'b> {
+    /// Payer for account creation
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    /// Guardian set of the signatures
+    pub guardian_set: GuardianSet<'b, { AccountState::Initialized }>,
+
+    /// Signature Account, derived from the VAA body hash and guardian set index.
+    pub signature_set: Mut<SignatureSet<'b, { AccountState::MaybeInitialized }>>,
+
+    /// Instruction reflection account (special sysvar)
+    #[key(solana_program::sysvar::instructions::id())]
+    pub instruction_acc: Info<'b>,
+}
+
+impl<'b> InstructionContext<'b> for VerifySignaturesDerived<'b> {
+}
+
+impl From<&VerifySignaturesDerived<'_>> for GuardianSetDerivationData {
+    fn from(data: &VerifySignaturesDerived<'_>) -> Self {
+        GuardianSetDerivationData {
+            index: data.guardian_set.index,
+        }
+    }
+}
+
 impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     fn from(data: &VerifySignatures<'_>) -> Self {
         GuardianSetDerivationData {
@@ -45,24 +78,85 @@ impl From<&VerifySignatures<'_>> for GuardianSetDerivationData {
     }
 }
 
-#[derive(Default, BorshSerialize, BorshDeserialize)]
+#[derive(Default, BorshSerialize, BorshDeserialize, IdlSchema)]
 pub struct VerifySignaturesData {
-    /// instruction indices of signers (-1 for missing)
-    pub signers: [i8; MAX_LEN_GUARDIAN_KEYS],
+    /// Indices of the secp256k1 instructions within the transaction that carry the signatures.
+    /// If empty, the instruction directly preceding this one is used.
+    pub secp_instructions: Vec<u8>,
+
+    /// signature indices of signers (-1 for missing), indexed by position in the guardian set.
+    /// Signatures are numbered across all secp instructions in the order they are listed above.
+    /// May be shorter than the guardian set, missing trailing entries are treated as -1.
+    pub signers: Vec<i8>,
 }
 
 /// SigInfo contains metadata about signers in a VerifySignature ix
 struct SigInfo {
     /// index of the signer in the guardianset
     signer_index: u8,
-    /// index of the signature in the secp instruction
+    /// index of the signature across all referenced secp instructions
     sig_index: u8,
 }
 
-struct SecpInstructionPart<'a> {
-    address: &'a [u8],
-    msg_offset: u16,
-    msg_size: u16,
+struct SecpInstructionPart {
+    address: [u8; 20],
+    message: Vec<u8>,
+}
+
+/// Parses the signature descriptions of a secp256k1 instruction at `secp_ix_index`. All offsets
+/// must point into the instruction itself.
+fn parse_secp_instruction(
+    secp_ix_index: u8,
+    data: &[u8],
+    parts: &mut Vec<SecpInstructionPart>,
+) -> Result<()> {
+    if data.len() < 2 {
+        return Err(InvalidSecpInstruction.into());
+    }
+
+    let sig_len = data[0];
+    let mut index = 1;
+
+    for _ in 0..sig_len {
+        if data.len() < index + 11 {
+            return Err(InvalidSecpInstruction.into());
+        }
+
+        let _sig_offset = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let sig_ix = data[index];
+        index += 1;
+        let address_offset = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let address_ix = data[index];
+        index += 1;
+        let msg_offset = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let msg_size = byteorder::LE::read_u16(&data[index..index + 2]) as usize;
+        index += 2;
+        let msg_ix = data[index];
+        index += 1;
+
+        if address_ix != secp_ix_index || msg_ix != secp_ix_index || sig_ix != secp_ix_index {
+            return Err(InvalidSecpInstruction.into());
+        }
+
+        let address = data
+            .get(address_offset..address_offset + 20)
+            .ok_or(InvalidSecpInstruction)?;
+        let message = data
+            .get(msg_offset..msg_offset + msg_size)
+            .ok_or(InvalidSecpInstruction)?;
+
+        let mut part = SecpInstructionPart {
+            address: [0u8; 20],
+            message: message.to_vec(),
+        };
+        part.address.copy_from_slice(address);
+        parts.push(part);
+    }
+
+    Ok(())
 }
 
 pub fn verify_signatures(
@@ -73,6 +167,116 @@ pub fn verify_signatures(
     accs.guardian_set
         .verify_derivation(ctx.program_id, &(&*accs).into())?;
 
+    let (msg_hash, signers) = load_signers(&accs.guardian_set, &accs.instruction_acc, data)?;
+
+    if !accs.signature_set.is_initialized() {
+        init_signature_set(
+            &mut accs.signature_set,
+            &accs.guardian_set,
+            msg_hash,
+            accs.payer.key,
+        );
+
+        let size = accs.signature_set.size();
+        let ix = solana_program::system_instruction::create_account(
+            accs.payer.key,
+            accs.signature_set.info().key,
+            Exempt.amount(size)?,
+            size as u64,
+            ctx.program_id,
+        );
+        solana_program::program::invoke(&ix, ctx.accounts)?;
+    } else {
+        check_signature_set(&accs.signature_set, &accs.guardian_set, msg_hash)?;
+    }
+
+    // Overwritten content should be zeros except double signs by the signer or harmless replays
+    for signer in signers {
+        accs.signature_set.signatures[signer] = true;
+    }
+
+    Ok(())
+}
+
+/// Same as `verify_signatures`, but the signature set lives at a PDA derived from the VAA body
+/// hash and guardian set index so that several relayers can fill the same set.
+pub fn verify_signatures_derived(
+    ctx: &ExecutionContext,
+    accs: &mut VerifySignaturesDerived,
+    data: VerifySignaturesData,
+) -> Result<()> {
+    accs.guardian_set
+        .verify_derivation(ctx.program_id, &(&*accs).into())?;
+
+    let (msg_hash, signers) = load_signers(&accs.guardian_set, &accs.instruction_acc, data)?;
+
+    let derivation_data = SignatureSetDerivationData {
+        hash: msg_hash,
+        guardian_set_index: accs.guardian_set.index,
+    };
+    accs.signature_set
+        .verify_derivation(ctx.program_id, &derivation_data)?;
+
+    if !accs.signature_set.is_initialized() {
+        init_signature_set(
+            &mut accs.signature_set,
+            &accs.guardian_set,
+            msg_hash,
+            accs.payer.key,
+        );
+        accs.signature_set
+            .create(&derivation_data, ctx, accs.payer.key, Exempt)?;
+    } else {
+        check_signature_set(&accs.signature_set, &accs.guardian_set, msg_hash)?;
+    }
+
+    for signer in signers {
+        accs.signature_set.signatures[signer] = true;
+    }
+
+    Ok(())
+}
+
+fn init_signature_set(
+    signature_set: &mut SignatureSetData,
+    guardian_set: &GuardianSet<'_, { AccountState::Initialized }>,
+    msg_hash: [u8; 32],
+    payer: &Pubkey,
+) {
+    signature_set.signatures = vec![false; guardian_set.keys.len()];
+    signature_set.guardian_set_index = guardian_set.index;
+    signature_set.hash = msg_hash;
+    signature_set.payer = Trailing(Some(*payer));
+}
+
+/// If the account already existed, check that the parameters match
+fn check_signature_set(
+    signature_set: &SignatureSetData,
+    guardian_set: &GuardianSet<'_, { AccountState::Initialized }>,
+    msg_hash: [u8; 32],
+) -> Result<()> {
+    if signature_set.guardian_set_index != guardian_set.index {
+        return Err(GuardianSetMismatch.into());
+    }
+
+    if signature_set.hash != msg_hash {
+        return Err(InvalidHash.into());
+    }
+
+    Ok(())
+}
+
+/// Reads the secp256k1 instructions referenced by `data` and checks the recovered addresses
+/// against the guardian set. Returns the signed hash and the guardian indices that signed it.
+fn load_signers(
+    guardian_set: &GuardianSet<'_, { AccountState::Initialized }>,
+    instruction_acc: &Info<'_>,
+    data: VerifySignaturesData,
+) -> Result<([u8; 32], Vec<usize>)> {
+    if data.signers.len() > guardian_set.keys.len() {
+        return Err(ProgramError::InvalidArgument.into());
+    }
+
     let sig_infos: Vec<SigInfo> = data
         .signers
         .iter()
@@ -90,130 +294,77 @@ pub fn verify_signatures(
         .collect();
 
     let current_instruction = solana_program::sysvar::instructions::load_current_index(
-        &accs.instruction_acc.try_borrow_mut_data()?,
+        &instruction_acc.try_borrow_mut_data()?,
     );
-    if current_instruction == 0 {
-        return Err(InstructionAtWrongIndex.into());
-    }
-
-    // The previous ix must be a secp verification instruction
-    let secp_ix_index = (current_instruction - 1) as u8;
-    let secp_ix = solana_program::sysvar::instructions::load_instruction_at(
-        secp_ix_index as usize,
-        &accs.instruction_acc.try_borrow_mut_data()?,
-    )
-    .map_err(|_| ProgramError::InvalidAccountData)?;
-
-    // Check that the instruction is actually for the secp program
-    if secp_ix.program_id != solana_program::secp256k1_program::id() {
-        return Err(InvalidSecpInstruction.into());
-    }
-
-    let secp_data_len = secp_ix.data.len();
-    if secp_data_len < 2 {
-        return Err(InvalidSecpInstruction.into());
-    }
 
-    let sig_len = secp_ix.data[0];
-    let mut index = 1;
-
-    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::with_capacity(sig_len as usize);
-    for i in 0..sig_len {
-        let _sig_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let sig_ix = secp_ix.data[index];
-        index += 1;
-        let address_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]) as usize;
-        index += 2;
-        let address_ix = secp_ix.data[index];
-        index += 1;
-        let msg_offset = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_size = byteorder::LE::read_u16(&secp_ix.data[index..index + 2]);
-        index += 2;
-        let msg_ix = secp_ix.data[index];
-        index += 1;
+    // Default to the instruction directly preceding this one.
+    let secp_ix_indices = if data.secp_instructions.is_empty() {
+        if current_instruction == 0 {
+            return Err(InstructionAtWrongIndex.into());
+        }
+        vec![(current_instruction - 1) as u8]
+    } else {
+        data.secp_instructions
+    };
 
-        if address_ix != secp_ix_index || msg_ix != secp_ix_index || sig_ix != secp_ix_index {
+    let mut secp_ixs: Vec<SecpInstructionPart> = Vec::new();
+    for (i, secp_ix_index) in secp_ix_indices.iter().enumerate() {
+        // Referencing the same instruction twice would let a signature be counted twice.
+        if secp_ix_indices[..i].contains(secp_ix_index) {
             return Err(InvalidSecpInstruction.into());
         }
 
-        let address: &[u8] = &secp_ix.data[address_offset..address_offset + 20];
+        let secp_ix = solana_program::sysvar::instructions::load_instruction_at(
+            *secp_ix_index as usize,
+            &instruction_acc.try_borrow_mut_data()?,
+        )
+        .map_err(|_| ProgramError::InvalidAccountData)?;
 
-        // Make sure that all messages are equal
-        if i > 0 {
-            if msg_offset != secp_ixs[0].msg_offset || msg_size != secp_ixs[0].msg_size {
-                return Err(InvalidSecpInstruction.into());
-            }
+        // Check that the instruction is actually for the secp program
+        if secp_ix.program_id != solana_program::secp256k1_program::id() {
+            return Err(InvalidSecpInstruction.into());
         }
-        secp_ixs.push(SecpInstructionPart {
-            address,
-            msg_offset,
-            msg_size,
-        });
+
+        parse_secp_instruction(*secp_ix_index, &secp_ix.data, &mut secp_ixs)?;
     }
 
-    if sig_infos.len() != secp_ixs.len() {
+    if secp_ixs.is_empty() || sig_infos.len() != secp_ixs.len() {
         return Err(ProgramError::InvalidArgument.into());
     }
 
     // Data must be a hash
-    if secp_ixs[0].msg_size != 32 {
+    if secp_ixs[0].message.len() != 32 {
         return Err(ProgramError::InvalidArgument.into());
     }
 
-    // Extract message which is encoded in Solana Secp256k1 instruction data.
-    let message = &secp_ix.data
-        [secp_ixs[0].msg_offset as usize..(secp_ixs[0].msg_offset + secp_ixs[0].msg_size) as usize];
+    // Make sure that all messages are equal
+    if secp_ixs.iter().any(|p| p.message != secp_ixs[0].message) {
+        return Err(InvalidSecpInstruction.into());
+    }
 
-    // Hash the message part, which contains the serialized VAA body.
+    // Extract message which is encoded in Solana Secp256k1 instruction data.
     let mut msg_hash: [u8; 32] = [0u8; 32];
-    msg_hash.copy_from_slice(message);
-
-    if !accs.signature_set.is_initialized() {
-        accs.signature_set.signatures = vec![false; accs.guardian_set.keys.len()];
-        accs.signature_set.guardian_set_index = accs.guardian_set.index;
-        accs.signature_set.hash = msg_hash;
-
-        let size = accs.signature_set.size();
-        let ix = solana_program::system_instruction::create_account(
-            accs.payer.key,
-            accs.signature_set.info().key,
-            Exempt.amount(size),
-            size as u64,
-            ctx.program_id,
-        );
-        solana_program::program::invoke(&ix, ctx.accounts)?;
-    } else {
-        // If the account already existed, check that the parameters match
-        if accs.signature_set.guardian_set_index != accs.guardian_set.index {
-            return Err(GuardianSetMismatch.into());
-        }
+    msg_hash.copy_from_slice(&secp_ixs[0].message);
 
-        if accs.signature_set.hash != msg_hash {
-            return Err(InvalidHash.into());
-        }
-    }
-
-    // Write sigs of checked addresses into sig_state
+    // Check the addresses of all referenced signatures
+    let mut signers = Vec::with_capacity(sig_infos.len());
     for s in sig_infos {
-        if s.signer_index > accs.guardian_set.num_guardians() {
+        if s.signer_index as usize >= guardian_set.keys.len() {
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        if s.sig_index + 1 > sig_len {
+        if s.sig_index as usize >= secp_ixs.len() {
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        let key = accs.guardian_set.keys[s.signer_index as usize];
+        let key = guardian_set.keys[s.signer_index as usize];
         // Check key in ix
         if key != secp_ixs[s.sig_index as usize].address {
             return Err(ProgramError::InvalidArgument.into());
         }
 
-        // Overwritten content should be zeros except double signs by the signer or harmless replays
-        accs.signature_set.signatures[s.signer_index as usize] = true;
+        signers.push(s.signer_index as usize);
     }
 
-    Ok(())
+    Ok((msg_hash, signers))
 }
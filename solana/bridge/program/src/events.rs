@@ -0,0 +1,56 @@
+//! Typed events emitted by the bridge. Each event is Borsh encoded and written to the program log
+//! as a hex string, allowing off-chain consumers to follow the bridge without scraping free-form
+//! log lines.
+
+use crate::api::ForeignAddress;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use solana_program::{
+    msg,
+    pubkey::Pubkey,
+};
+
+/// Prefix of the log line carrying an event, as written by the program.
+pub const EVENT_PREFIX: &str = "Event: ";
+
+/// Prefix of the log line carrying an event, as it appears in transaction metadata. Clients decode
+/// events with `solitaire_client::decode_events`.
+pub const EVENT_LOG_PREFIX: &str = "Program log: Event: ";
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A message was posted by an emitter and is ready to be observed by the guardians.
+    MessagePosted {
+        emitter: Pubkey,
+        sequence: u64,
+        message: Pubkey,
+    },
+
+    /// A VAA was verified and stored.
+    VAAPosted {
+        emitter_chain: u16,
+        emitter_address: ForeignAddress,
+        sequence: u64,
+        hash: [u8; 32],
+        message: Pubkey,
+    },
+
+    /// The active guardian set was replaced.
+    GuardianSetUpgraded { old_index: u32, new_index: u32 },
+
+    /// The message fee was changed through governance.
+    FeesSet { fee: u64 },
+
+    /// Collected fees were transferred out through governance.
+    FeesTransferred { recipient: Pubkey, amount: u64 },
+}
+
+impl Event {
+    /// Write the event to the program log.
+    pub fn emit(&self) {
+        let data = self.try_to_vec().unwrap();
+        msg!("{}{}", EVENT_PREFIX, hex::encode(data));
+    }
+}
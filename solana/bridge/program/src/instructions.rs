@@ -25,26 +25,44 @@ use std::io::{
 use crate::{
     accounts::{
         Bridge,
+        BridgeExtension,
         Claim,
         ClaimDerivationData,
         FeeCollector,
         GuardianSet,
         GuardianSetDerivationData,
+        MessageDerivationData,
+        PostedMessage,
         PostedVAA,
         PostedVAADerivationData,
         Sequence,
         SequenceDerivationData,
+        SignatureSet,
+        SignatureSetDerivationData,
     },
-    types::ConsistencyLevel,
+    types::{
+        ConsistencyLevel,
+        Trailing,
+    },
+    CloseGuardianSetData,
+    ClosePostedMessageData,
+    CloseSignatureSetData,
+    FinalizeMessageData,
     InitializeData,
+    InitializeMessageData,
     PostMessageData,
     PostVAAData,
+    PostVAAFromBufferData,
     SetFeesData,
+    SetGovernanceEmitterData,
+    SetGuardianSetExpirationData,
+    SetMessageRetentionData,
     TransferFeesData,
     UpgradeContractData,
     UpgradeGuardianSetData,
     VerifySignaturesData,
-    CHAIN_ID_SOLANA,
+    WriteMessageData,
+    WriteVAABufferData,
 };
 
 pub fn initialize(
@@ -53,6 +71,9 @@ pub fn initialize(
     fee: u64,
     guardian_set_expiration_time: u32,
     initial_guardians: &[[u8; 20]],
+    governance_chain: u16,
+    governance_emitter: [u8; 32],
+    message_retention_slots: u64,
 ) -> solitaire::Result<Instruction> {
     let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
@@ -78,6 +99,9 @@ pub fn initialize(
                 initial_guardians: initial_guardians.to_vec(),
                 fee,
                 guardian_set_expiration_time,
+                governance_chain,
+                governance_emitter,
+                message_retention_slots,
             },
         )
             .try_to_vec()?,
@@ -112,7 +136,6 @@ pub fn post_message(
             AccountMeta::new(sequence, false),
             AccountMeta::new(payer, true),
             AccountMeta::new(fee_collector, false),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
@@ -129,6 +152,155 @@ pub fn post_message(
     })
 }
 
+/// Post a message into the account derived from the emitter and `sequence`, which must be the
+/// emitter's next sequence number.
+pub fn post_message_derived(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    sequence: u64,
+    nonce: u32,
+    payload: Vec<u8>,
+    commitment: ConsistencyLevel,
+) -> solitaire::Result<Instruction> {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let sequence_key = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+    let message = message_address(program_id, emitter, sequence);
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence_key, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::PostMessageDerived,
+            PostMessageData {
+                nonce,
+                payload,
+                consistency_level: commitment,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
+/// Address of the message posted by `emitter` with `sequence` through `post_message_derived`.
+pub fn message_address(program_id: Pubkey, emitter: Pubkey, sequence: u64) -> Pubkey {
+    PostedMessage::<'_, { AccountState::Uninitialized }>::key(
+        &MessageDerivationData {
+            emitter_key: &emitter,
+            sequence,
+        },
+        &program_id,
+    )
+}
+
+pub fn initialize_message(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    payload_len: u32,
+) -> solitaire::Result<Instruction> {
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(message, true),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::InitializeMessage,
+            InitializeMessageData { payload_len },
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn write_message(
+    program_id: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    offset: u32,
+    data: Vec<u8>,
+) -> solitaire::Result<Instruction> {
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(emitter, true),
+        ],
+
+        data: (
+            crate::instruction::Instruction::WriteMessage,
+            WriteMessageData { offset, data },
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn finalize_message(
+    program_id: Pubkey,
+    payer: Pubkey,
+    emitter: Pubkey,
+    message: Pubkey,
+    nonce: u32,
+    commitment: ConsistencyLevel,
+) -> solitaire::Result<Instruction> {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let fee_collector = FeeCollector::<'_>::key(None, &program_id);
+    let sequence = Sequence::<'_>::key(
+        &SequenceDerivationData {
+            emitter_key: &emitter,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new_readonly(emitter, true),
+            AccountMeta::new(sequence, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new(fee_collector, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::FinalizeMessage,
+            FinalizeMessageData {
+                nonce,
+                consistency_level: commitment,
+            },
+        )
+            .try_to_vec()?,
+    })
+}
+
 pub fn verify_signatures(
     program_id: Pubkey,
     payer: Pubkey,
@@ -159,19 +331,68 @@ pub fn verify_signatures(
     })
 }
 
-pub fn post_vaa(
+pub fn verify_signatures_derived(
     program_id: Pubkey,
     payer: Pubkey,
-    signature_set: Pubkey,
-    vaa: PostVAAData,
-) -> Instruction {
-    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    guardian_set_index: u32,
+    body_hash: [u8; 32],
+    data: VerifySignaturesData,
+) -> solitaire::Result<Instruction> {
     let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
         &GuardianSetDerivationData {
-            index: vaa.guardian_set_index,
+            index: guardian_set_index,
         },
         &program_id,
     );
+    let signature_set = signature_set_address(program_id, guardian_set_index, body_hash);
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (crate::instruction::Instruction::VerifySignaturesDerived, data).try_to_vec()?,
+    })
+}
+
+/// Address of the derived signature set for a VAA body hash, as used by
+/// `verify_signatures_derived`.
+pub fn signature_set_address(
+    program_id: Pubkey,
+    guardian_set_index: u32,
+    body_hash: [u8; 32],
+) -> Pubkey {
+    SignatureSet::<'_, { AccountState::Uninitialized }>::key(
+        &SignatureSetDerivationData {
+            hash: body_hash,
+            guardian_set_index,
+        },
+        &program_id,
+    )
+}
+
+pub fn post_vaa(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    mut vaa: PostVAAData,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (guardian_set, guardian_set_bump) =
+        GuardianSet::<'_, { AccountState::Uninitialized }>::key_and_bump(
+            &GuardianSetDerivationData {
+                index: vaa.guardian_set_index,
+            },
+            &program_id,
+        );
+    vaa.guardian_set_bump = Trailing(Some(guardian_set_bump));
 
     let msg_derivation_data = &PostedVAADerivationData {
         payload_hash: hash_vaa(&vaa).to_vec(),
@@ -189,7 +410,6 @@ pub fn post_vaa(
             AccountMeta::new_readonly(signature_set, false),
             AccountMeta::new(message, false),
             AccountMeta::new(payer, true),
-            AccountMeta::new_readonly(sysvar::clock::id(), false),
             AccountMeta::new_readonly(sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
         ],
@@ -200,20 +420,104 @@ pub fn post_vaa(
     }
 }
 
+pub fn write_vaa_buffer(
+    program_id: Pubkey,
+    payer: Pubkey,
+    buffer: Pubkey,
+    length: u32,
+    offset: u32,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(buffer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::WriteVAABuffer,
+            WriteVAABufferData {
+                length,
+                offset,
+                data,
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn post_vaa_from_buffer(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    buffer: Pubkey,
+    vaa: &PostVAAData,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let (guardian_set, guardian_set_bump) =
+        GuardianSet::<'_, { AccountState::Uninitialized }>::key_and_bump(
+            &GuardianSetDerivationData {
+                index: vaa.guardian_set_index,
+            },
+            &program_id,
+        );
+
+    let msg_derivation_data = &PostedVAADerivationData {
+        payload_hash: hash_vaa(vaa).to_vec(),
+    };
+
+    let message =
+        PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(&msg_derivation_data, &program_id);
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(guardian_set, false),
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(signature_set, false),
+            AccountMeta::new_readonly(buffer, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::PostVAAFromBuffer,
+            PostVAAFromBufferData {
+                version: vaa.version,
+                guardian_set_index: vaa.guardian_set_index,
+                guardian_set_bump: Trailing(Some(guardian_set_bump)),
+            },
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn upgrade_contract(
     program_id: Pubkey,
     payer: Pubkey,
     payload_message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     new_contract: Pubkey,
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
     let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
+            emitter_chain,
             sequence,
         },
         &program_id,
@@ -232,6 +536,7 @@ pub fn upgrade_contract(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(extension, false),
             AccountMeta::new_readonly(payload_message, false),
             AccountMeta::new(claim, false),
             AccountMeta::new_readonly(upgrade_authority, false),
@@ -259,16 +564,19 @@ pub fn upgrade_guardian_set(
     payer: Pubkey,
     payload_message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     old_index: u32,
     new_index: u32,
     sequence: u64,
 ) -> Instruction {
     let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
-            sequence: sequence,
+            emitter_chain,
+            sequence,
         },
         &program_id,
     );
@@ -289,6 +597,7 @@ pub fn upgrade_guardian_set(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(extension, false),
             AccountMeta::new_readonly(payload_message, false),
             AccountMeta::new(claim, false),
             AccountMeta::new(guardian_set_old, false),
@@ -310,13 +619,16 @@ pub fn set_fees(
     payer: Pubkey,
     message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     sequence: u64,
 ) -> Instruction {
     let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
+            emitter_chain,
             sequence,
         },
         &program_id,
@@ -328,6 +640,7 @@ pub fn set_fees(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(extension, false),
             AccountMeta::new_readonly(message, false),
             AccountMeta::new(claim, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -339,19 +652,63 @@ pub fn set_fees(
     }
 }
 
+pub fn set_guardian_set_expiration(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(extension, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGuardianSetExpiration,
+            SetGuardianSetExpirationData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
 pub fn transfer_fees(
     program_id: Pubkey,
     payer: Pubkey,
     message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     sequence: u64,
     recipient: Pubkey,
 ) -> Instruction {
     let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
+            emitter_chain,
             sequence,
         },
         &program_id,
@@ -365,6 +722,7 @@ pub fn transfer_fees(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(extension, false),
             AccountMeta::new_readonly(message, false),
             AccountMeta::new(claim, false),
             AccountMeta::new(fee_collector, false),
@@ -382,6 +740,177 @@ pub fn transfer_fees(
     }
 }
 
+pub fn set_governance_emitter(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(extension, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGovernanceEmitter,
+            SetGovernanceEmitterData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn set_message_retention(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+    emitter: Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new(extension, false),
+            AccountMeta::new_readonly(message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetMessageRetention,
+            SetMessageRetentionData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+pub fn close_signature_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    signature_set: Pubkey,
+    body_hash: [u8; 32],
+) -> solitaire::Result<Instruction> {
+    let posted_vaa = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body_hash.to_vec(),
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(signature_set, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+            AccountMeta::new(payer, false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseSignatureSet,
+            CloseSignatureSetData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn close_posted_message(
+    program_id: Pubkey,
+    payer: Pubkey,
+    message: Pubkey,
+) -> solitaire::Result<Instruction> {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new_readonly(extension, false),
+            AccountMeta::new(message, false),
+            AccountMeta::new(payer, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::ClosePostedMessage,
+            ClosePostedMessageData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
+pub fn close_guardian_set(
+    program_id: Pubkey,
+    payer: Pubkey,
+    guardian_set_index: u32,
+) -> solitaire::Result<Instruction> {
+    let bridge = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let guardian_set = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData {
+            index: guardian_set_index,
+        },
+        &program_id,
+    );
+
+    Ok(Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new_readonly(bridge, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new(payer, false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::CloseGuardianSet,
+            CloseGuardianSetData {},
+        )
+            .try_to_vec()?,
+    })
+}
+
 // Convert a full VAA structure into the serialization of its unique components, this structure is
 // what is hashed and verified by Guardians.
 pub fn serialize_vaa(vaa: &PostVAAData) -> Vec<u8> {
@@ -4,7 +4,18 @@
 
 use solitaire::*;
 
-pub const MAX_LEN_GUARDIAN_KEYS: usize = 19;
+/// Guardian indices are encoded as a single byte, both in VAAs and in the signer map passed to
+/// `verify_signatures`, which bounds the size of a guardian set.
+pub const MAX_LEN_GUARDIAN_KEYS: usize = 255;
+/// Upper bound on the payload of a message written in chunks. The whole payload is held in memory
+/// while the account is created, so it has to stay well within the 32KiB program heap.
+pub const MAX_LEN_MESSAGE_PAYLOAD: usize = 8 * 1024;
+/// Upper bound on the VAA body held in a buffer, for the same reason.
+pub const MAX_LEN_VAA_BODY: usize = 8 * 1024;
+/// Guardians only observe messages once the slot they were posted in is finalized, which takes
+/// this many confirmations. Message accounts have to outlive that, and then some for guardians
+/// lagging behind, before they can be closed.
+pub const MIN_MESSAGE_RETENTION_SLOTS: u64 = 32 + 128;
 pub const CHAIN_ID_SOLANA: u16 = 1;
 
 #[cfg(feature = "no-entrypoint")]
@@ -22,6 +33,8 @@ pub mod accounts;
 pub use accounts::{
     BridgeConfig,
     BridgeData,
+    BridgeExtension,
+    BridgeExtensionData,
     Claim,
     ClaimData,
     ClaimDerivationData, 
@@ -29,9 +42,13 @@ pub use accounts::{
     GuardianSet,
     GuardianSetData,
     GuardianSetDerivationData,
+    PendingMessage,
+    PendingMessageData,
     PostedMessage,
     PostedMessageData,
     MessageData,
+    MessageDerivationData,
+    MessageHeader,
     PostedVAA,
     PostedVAAData,
     Sequence,
@@ -39,26 +56,61 @@ pub use accounts::{
     SequenceDerivationData,
     SignatureSet,
     SignatureSetData,
+    SignatureSetDerivationData,
+    VAABuffer,
+    VAABufferData,
 };
 
 pub mod api;
 pub use api::{
+    close_guardian_set,
+    close_posted_message,
+    close_signature_set,
+    finalize_message,
     initialize,
+    initialize_message,
     post_message,
+    post_message_derived,
     post_vaa,
+    post_vaa_from_buffer,
     set_fees,
+    set_governance_emitter,
+    set_guardian_set_expiration,
+    set_message_retention,
     transfer_fees,
     upgrade_contract,
     upgrade_guardian_set,
     verify_signatures,
+    verify_signatures_derived,
+    write_message,
+    write_vaa_buffer,
+    CloseGuardianSet,
+    CloseGuardianSetData,
+    ClosePostedMessage,
+    ClosePostedMessageData,
+    CloseSignatureSet,
+    CloseSignatureSetData,
+    FinalizeMessage,
+    FinalizeMessageData,
     Initialize,
     InitializeData,
+    InitializeMessage,
+    InitializeMessageData,
     PostMessage,
     PostMessageData,
+    PostMessageDerived,
     PostVAA,
     PostVAAData,
+    PostVAAFromBuffer,
+    PostVAAFromBufferData,
     SetFees,
     SetFeesData,
+    SetGovernanceEmitter,
+    SetGovernanceEmitterData,
+    SetGuardianSetExpiration,
+    SetGuardianSetExpirationData,
+    SetMessageRetention,
+    SetMessageRetentionData,
     Signature,
     TransferFees,
     TransferFeesData,
@@ -69,9 +121,15 @@ pub use api::{
     UpgradeGuardianSetData,
     VerifySignatures,
     VerifySignaturesData,
+    VerifySignaturesDerived,
+    WriteMessage,
+    WriteMessageData,
+    WriteVAABuffer,
+    WriteVAABufferData,
 };
 
 pub mod error;
+pub mod events;
 pub mod types;
 pub mod vaa;
 
@@ -84,12 +142,25 @@ pub use vaa::{
 };
 
 solitaire! {
-    Initialize(InitializeData)                  => initialize,
-    PostMessage(PostMessageData)                => post_message,
-    PostVAA(PostVAAData)                        => post_vaa,
-    SetFees(SetFeesData)                        => set_fees,
-    TransferFees(TransferFeesData)              => transfer_fees,
-    UpgradeContract(UpgradeContractData)        => upgrade_contract,
-    UpgradeGuardianSet(UpgradeGuardianSetData)  => upgrade_guardian_set,
-    VerifySignatures(VerifySignaturesData)      => verify_signatures,
+    Initialize(InitializeData)                             => initialize,
+    PostMessage(PostMessageData)                           => post_message,
+    PostVAA(PostVAAData)                                   => post_vaa,
+    SetFees(SetFeesData)                                   => set_fees,
+    TransferFees(TransferFeesData)                         => transfer_fees,
+    UpgradeContract(UpgradeContractData)                   => upgrade_contract,
+    UpgradeGuardianSet(UpgradeGuardianSetData)             => upgrade_guardian_set,
+    VerifySignatures(VerifySignaturesData)                 => verify_signatures,
+    SetGovernanceEmitter(SetGovernanceEmitterData)         => set_governance_emitter,
+    WriteVAABuffer(WriteVAABufferData)                     => write_vaa_buffer,
+    PostVAAFromBuffer(PostVAAFromBufferData)               => post_vaa_from_buffer,
+    InitializeMessage(InitializeMessageData)               => initialize_message,
+    WriteMessage(WriteMessageData)                         => write_message,
+    FinalizeMessage(FinalizeMessageData)                   => finalize_message,
+    VerifySignaturesDerived(VerifySignaturesData)          => verify_signatures_derived,
+    CloseSignatureSet(CloseSignatureSetData)               => close_signature_set,
+    ClosePostedMessage(ClosePostedMessageData)             => close_posted_message,
+    CloseGuardianSet(CloseGuardianSetData)                 => close_guardian_set,
+    PostMessageDerived(PostMessageData)                    => post_message_derived,
+    SetGuardianSetExpiration(SetGuardianSetExpirationData) => set_guardian_set_expiration,
+    SetMessageRetention(SetMessageRetentionData)           => set_message_retention,
 }
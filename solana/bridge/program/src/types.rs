@@ -24,7 +24,15 @@ use solana_program::{
     program_error::ProgramError::InvalidAccountData,
     pubkey::Pubkey,
 };
-use solitaire::SolitaireError;
+use solitaire::{
+    idl::{
+        self,
+        IdlType,
+        IdlTypeDef,
+    },
+    IdlSchema,
+    SolitaireError,
+};
 use std::{
     self,
     io::{
@@ -38,12 +46,48 @@ use std::{
 pub type GuardianPublicKey = [u8; 20];
 
 #[repr(u8)]
-#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Clone, Serialize, Deserialize, IdlSchema)]
 pub enum ConsistencyLevel {
     Confirmed,
     Finalized,
 }
 
+/// A field appended to an account layout or instruction data after accounts or clients using it
+/// existed. Data that predates the field ends where it would start and reads as `None`, which in
+/// turn is written as nothing.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Trailing<T>(pub Option<T>);
+
+impl<T: BorshSerialize> BorshSerialize for Trailing<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        match &self.0 {
+            Some(v) => v.serialize(writer),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<T: BorshDeserialize> BorshDeserialize for Trailing<T> {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        match buf.is_empty() {
+            true => Ok(Trailing(None)),
+            _ => T::deserialize(buf).map(|v| Trailing(Some(v))),
+        }
+    }
+}
+
+/// Described as the field itself, which clients generated from the IDL always send.
+impl<T: idl::IdlSchema> idl::IdlSchema for Trailing<T> {
+    fn idl_type() -> IdlType {
+        T::idl_type()
+    }
+
+    fn idl_definitions(definitions: &mut Vec<IdlTypeDef>) {
+        T::idl_definitions(definitions)
+    }
+}
+
 pub struct GovernancePayloadUpgrade {
     // Address of the new Implementation
     pub new_contract: Pubkey,
@@ -185,6 +229,86 @@ impl SerializeGovernancePayload for GovernancePayloadSetMessageFee {
 impl DeserializeGovernancePayload for GovernancePayloadSetMessageFee {
 }
 
+pub struct GovernancePayloadSetGuardianSetExpiration {
+    // Seconds a replaced guardian set remains valid for
+    pub expiration_time: u32,
+}
+
+impl SerializePayload for GovernancePayloadSetGuardianSetExpiration {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u32::<BigEndian>(self.expiration_time)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGuardianSetExpiration
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let expiration_time = c.read_u32::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGuardianSetExpiration { expiration_time })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 6;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGuardianSetExpiration {
+}
+
+pub struct GovernancePayloadSetMessageRetention {
+    // Slots a posted message must exist before its account can be closed
+    pub message_retention_slots: u64,
+}
+
+impl SerializePayload for GovernancePayloadSetMessageRetention {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u64::<BigEndian>(self.message_retention_slots)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetMessageRetention
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let message_retention_slots = c.read_u64::<BigEndian>()?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetMessageRetention {
+            message_retention_slots,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetMessageRetention {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 7;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetMessageRetention {
+}
+
 pub struct GovernancePayloadTransferFees {
     // Amount to be transferred
     pub amount: U256,
@@ -233,3 +357,99 @@ impl SerializeGovernancePayload for GovernancePayloadTransferFees {
 
 impl DeserializeGovernancePayload for GovernancePayloadTransferFees {
 }
+
+pub struct GovernancePayloadSetGovernanceEmitter {
+    // Chain the new governance emitter lives on
+    pub governance_chain: u16,
+
+    // Address of the new governance emitter
+    pub governance_emitter: ForeignAddress,
+}
+
+impl SerializePayload for GovernancePayloadSetGovernanceEmitter {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        use byteorder::WriteBytesExt;
+        v.write_u16::<BigEndian>(self.governance_chain)?;
+        v.write(&self.governance_emitter)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGovernanceEmitter
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let governance_chain = c.read_u16::<BigEndian>()?;
+
+        let mut governance_emitter = ForeignAddress::default();
+        c.read_exact(&mut governance_emitter)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGovernanceEmitter {
+            governance_chain,
+            governance_emitter,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGovernanceEmitter {
+    const MODULE: &'static str = "Core";
+    const ACTION: u8 = 5;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGovernanceEmitter {
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::{
+        GuardianSetData,
+        PostedMessageData,
+    };
+    use solitaire::from_account_data;
+
+    #[test]
+    fn test_trailing_fields() {
+        // Accounts created before a field was appended end where it would start.
+        let mut legacy = GuardianSetData {
+            index: 1,
+            keys: vec![[7u8; 20]],
+            creation_time: 2,
+            expiration_time: 3,
+            payer: Trailing(None),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(legacy.len(), 4 + 4 + 20 + 4 + 4);
+        let set: GuardianSetData = from_account_data(&legacy).unwrap();
+        assert_eq!(set.keys, vec![[7u8; 20]]);
+        assert_eq!(set.payer, Trailing(None));
+
+        let payer = Pubkey::new_unique();
+        legacy.extend(payer.to_bytes());
+        let set: GuardianSetData = from_account_data(&legacy).unwrap();
+        assert_eq!(set.payer, Trailing(Some(payer)));
+
+        // Partial fields are still rejected.
+        legacy.pop();
+        assert!(from_account_data::<GuardianSetData>(&legacy).is_err());
+
+        // Messages posted before refunds have neither a payer nor a slot.
+        let mut message = b"msg".to_vec();
+        message.extend(vec![0u8; 88]);
+        message.extend(&3u32.to_le_bytes());
+        message.extend(&[1, 2, 3]);
+        let posted: PostedMessageData = from_account_data(&message).unwrap();
+        assert_eq!(posted.payload, vec![1, 2, 3]);
+        assert_eq!(posted.payer, Trailing(None));
+        assert_eq!(posted.posted_slot, Trailing(None));
+    }
+}
@@ -9,8 +9,10 @@ use crate::{
         InvalidGovernanceModule,
         VAAAlreadyExecuted,
     },
+    types::Trailing,
     Claim,
     ClaimDerivationData,
+    MessageHeader,
     PostedVAAData,
     Result,
     CHAIN_ID_SOLANA,
@@ -25,13 +27,11 @@ use serde::{
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
-    processors::seeded::Seeded,
-    trace,
     Context,
     CreationLamports::Exempt,
-    Data,
     ExecutionContext,
     Peel,
+    Persistent,
     SolitaireError,
     *,
 };
@@ -107,31 +107,45 @@ pub trait DeserializeGovernancePayload: DeserializePayload + SerializeGovernance
     }
 }
 
-pub struct PayloadMessage<'b, T: DeserializePayload>(
-    Data<'b, PostedVAAData, { AccountState::Initialized }>,
-    T,
-);
+/// A posted VAA along with its parsed payload. The payload is parsed straight from the account
+/// data, only the header of the VAA is copied.
+pub struct PayloadMessage<'b, T: DeserializePayload>(View<'b, PostedVAAData>, T);
 
 impl<'a, 'b: 'a, 'c, T: DeserializePayload> Peel<'a, 'b, 'c> for PayloadMessage<'b, T> {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self>
     where
         Self: Sized,
     {
-        // Deserialize wrapped payload
-        let data: Data<'b, PostedVAAData, { AccountState::Initialized }> = Data::peel(ctx)?;
-        let payload = DeserializePayload::deserialize(&mut &data.payload[..])?;
-        Ok(PayloadMessage(data, payload))
+        let vaa: View<'b, PostedVAAData> = View::peel(ctx)?;
+        let payload = DeserializePayload::deserialize(&mut &vaa.payload()[..])?;
+        Ok(PayloadMessage(vaa, payload))
     }
 
     fn deps() -> Vec<Pubkey> {
-        Data::<'b, PostedVAAData, { AccountState::Initialized }>::deps()
+        View::<'b, PostedVAAData>::deps()
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
-        Data::persist(&self.0, program_id)
+        View::persist(&self.0, program_id)
+    }
+
+    fn idl_accounts(name: &str) -> Vec<solitaire::idl::IdlAccount> {
+        View::<'b, PostedVAAData>::idl_accounts(name)
+    }
+}
+
+#[cfg(feature = "client")]
+impl<'b, T: DeserializePayload> solitaire_client::Wrap for PayloadMessage<'b, T> {
+    fn wrap(
+        a: &solitaire_client::AccEntry,
+    ) -> std::result::Result<Vec<solitaire_client::AccountMeta>, solitaire::ErrBox> {
+        View::<'b, PostedVAAData>::wrap(a)
     }
 }
 
+/// The payload is parsed data, not an account that could be closed.
+impl<'b, T: DeserializePayload> Persistent for PayloadMessage<'b, T> {}
+
 impl<'b, T: DeserializePayload> Deref for PayloadMessage<'b, T> {
     type Target = T;
     fn deref(&self) -> &Self::Target {
@@ -140,7 +154,7 @@ impl<'b, T: DeserializePayload> Deref for PayloadMessage<'b, T> {
 }
 
 impl<'b, T: DeserializePayload> PayloadMessage<'b, T> {
-    pub fn meta(&self) -> &PostedVAAData {
+    pub fn meta(&self) -> &MessageHeader {
         &self.0
     }
 }
@@ -151,9 +165,37 @@ pub struct ClaimableVAA<'b, T: DeserializePayload> {
     pub message: PayloadMessage<'b, T>,
 
     // Claim account to prevent double spending
+    #[seeds(&ClaimDerivationData {
+        emitter_address: message.meta().emitter_address,
+        emitter_chain: message.meta().emitter_chain,
+        sequence: message.meta().sequence,
+    })]
     pub claim: Mut<Claim<'b, { AccountState::Uninitialized }>>,
 }
 
+/// Client side, a ClaimableVAA is passed as `AccEntry::Nested` holding the message and the claim.
+#[cfg(feature = "client")]
+impl<'b, T: DeserializePayload> solitaire_client::Wrap for ClaimableVAA<'b, T> {
+    fn wrap(
+        a: &solitaire_client::AccEntry,
+    ) -> std::result::Result<Vec<solitaire_client::AccountMeta>, solitaire::ErrBox> {
+        match a {
+            solitaire_client::AccEntry::Nested(accounts) if accounts.len() == 2 => {
+                let mut metas = PayloadMessage::<'b, T>::wrap(&accounts[0])?;
+                metas.append(&mut Mut::<Claim<'b, { AccountState::Uninitialized }>>::wrap(
+                    &accounts[1],
+                )?);
+                Ok(metas)
+            }
+            _other => Err(format!(
+                "{} must be passed as Nested with the message and claim accounts",
+                std::any::type_name::<Self>()
+            )
+            .into()),
+        }
+    }
+}
+
 impl<'b, T: DeserializePayload> Deref for ClaimableVAA<'b, T> {
     type Target = PayloadMessage<'b, T>;
     fn deref(&self) -> &Self::Target {
@@ -161,24 +203,6 @@ impl<'b, T: DeserializePayload> Deref for ClaimableVAA<'b, T> {
     }
 }
 
-impl<'b, T: DeserializePayload> ClaimableVAA<'b, T> {
-    pub fn verify(&self, program_id: &Pubkey) -> Result<()> {
-        trace!("Seq: {}", self.message.meta().sequence);
-
-        // Verify that the claim account is derived correctly
-        self.claim.verify_derivation(
-            program_id,
-            &ClaimDerivationData {
-                emitter_address: self.message.meta().emitter_address,
-                emitter_chain: self.message.meta().emitter_chain,
-                sequence: self.message.meta().sequence,
-            },
-        )?;
-
-        Ok(())
-    }
-}
-
 impl<'b, T: DeserializePayload> ClaimableVAA<'b, T> {
     pub fn is_claimed(&self) -> bool {
         self.claim.claimed
@@ -288,6 +312,7 @@ impl From<VAA> for PostVAAData {
             sequence: vaa.sequence,
             consistency_level: vaa.consistency_level,
             payload: vaa.payload,
+            guardian_set_bump: Trailing(None),
         }
     }
 }
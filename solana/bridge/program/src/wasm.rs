@@ -13,8 +13,10 @@ use borsh::BorshDeserialize;
 use byteorder::WriteBytesExt;
 use sha3::Digest;
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
 };
 use std::io::Write;
 
@@ -26,25 +28,42 @@ use crate::{
         GuardianSet,
         GuardianSetData,
         GuardianSetDerivationData,
+        PostedMessageData,
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
     },
     instructions::{
+        close_guardian_set,
+        close_posted_message,
+        close_signature_set,
+        finalize_message,
         hash_vaa,
+        initialize_message,
+        message_address,
         post_message,
+        post_message_derived,
         post_vaa,
+        post_vaa_from_buffer,
         set_fees,
+        set_governance_emitter,
+        set_guardian_set_expiration,
+        set_message_retention,
         transfer_fees,
         upgrade_contract,
         upgrade_guardian_set,
+        signature_set_address,
         verify_signatures,
+        verify_signatures_derived,
+        write_message,
+        write_vaa_buffer,
     },
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
+        Trailing,
     },
     Claim,
     ClaimDerivationData,
@@ -82,6 +101,109 @@ pub fn post_message_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn post_message_derived_ix(
+    program_id: String,
+    payer: String,
+    emitter: String,
+    sequence: u64,
+    nonce: u32,
+    msg: Vec<u8>,
+    consistency: String,
+) -> JsValue {
+    let consistency_level = match consistency.as_str() {
+        "CONFIRMED" => ConsistencyLevel::Confirmed,
+        "FINALIZED" => ConsistencyLevel::Finalized,
+        _ => panic!("invalid consistency level"),
+    };
+    let ix = post_message_derived(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(emitter.as_str()).unwrap(),
+        sequence,
+        nonce,
+        msg,
+        consistency_level,
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn message_address_for_emitter(program_id: String, emitter: String, sequence: u64) -> Vec<u8> {
+    message_address(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(emitter.as_str()).unwrap(),
+        sequence,
+    )
+    .to_bytes()
+    .to_vec()
+}
+
+#[wasm_bindgen]
+pub fn initialize_message_ix(
+    program_id: String,
+    payer: String,
+    emitter: String,
+    message: String,
+    payload_len: u32,
+) -> JsValue {
+    let ix = initialize_message(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(emitter.as_str()).unwrap(),
+        Pubkey::from_str(message.as_str()).unwrap(),
+        payload_len,
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn write_message_ix(
+    program_id: String,
+    emitter: String,
+    message: String,
+    offset: u32,
+    data: Vec<u8>,
+) -> JsValue {
+    let ix = write_message(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(emitter.as_str()).unwrap(),
+        Pubkey::from_str(message.as_str()).unwrap(),
+        offset,
+        data,
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn finalize_message_ix(
+    program_id: String,
+    payer: String,
+    emitter: String,
+    message: String,
+    nonce: u32,
+    consistency: String,
+) -> JsValue {
+    let consistency_level = match consistency.as_str() {
+        "CONFIRMED" => ConsistencyLevel::Confirmed,
+        "FINALIZED" => ConsistencyLevel::Finalized,
+        _ => panic!("invalid consistency level"),
+    };
+    let ix = finalize_message(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(emitter.as_str()).unwrap(),
+        Pubkey::from_str(message.as_str()).unwrap(),
+        nonce,
+        consistency_level,
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn post_vaa_ix(
     program_id: String,
@@ -100,6 +222,7 @@ pub fn post_vaa_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
     let ix = post_vaa(
         Pubkey::from_str(program_id.as_str()).unwrap(),
@@ -110,6 +233,112 @@ pub fn post_vaa_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+/// Maximum number of body bytes written per `write_vaa_buffer` instruction, leaving enough room in
+/// the transaction for signatures and account keys.
+const VAA_BUFFER_CHUNK_LEN: usize = 900;
+
+#[wasm_bindgen]
+pub fn write_vaa_buffer_ixs(
+    program_id: String,
+    payer: String,
+    buffer: String,
+    vaa_data: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let payer = Pubkey::from_str(payer.as_str()).unwrap();
+    let buffer = Pubkey::from_str(buffer.as_str()).unwrap();
+
+    let vaa = VAA::deserialize(vaa_data.as_slice()).unwrap();
+    let vaa_body = &vaa_data[VAA::HEADER_LEN + VAA::SIGNATURE_LEN * vaa.signatures.len()..];
+
+    let ixs: Vec<Instruction> = vaa_body
+        .chunks(VAA_BUFFER_CHUNK_LEN)
+        .enumerate()
+        .map(|(i, chunk)| {
+            write_vaa_buffer(
+                program_id,
+                payer,
+                buffer,
+                vaa_body.len() as u32,
+                (i * VAA_BUFFER_CHUNK_LEN) as u32,
+                chunk.to_vec(),
+            )
+        })
+        .collect();
+    return JsValue::from_serde(&ixs).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn post_vaa_from_buffer_ix(
+    program_id: String,
+    payer: String,
+    signature_set: String,
+    buffer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let vaa = PostVAAData {
+        version: vaa.version,
+        guardian_set_index: vaa.guardian_set_index,
+        timestamp: vaa.timestamp,
+        nonce: vaa.nonce,
+        emitter_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+        consistency_level: vaa.consistency_level,
+        payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
+    };
+    let ix = post_vaa_from_buffer(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(signature_set.as_str()).unwrap(),
+        Pubkey::from_str(buffer.as_str()).unwrap(),
+        &vaa,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn close_signature_set_ix(
+    program_id: String,
+    payer: String,
+    signature_set: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let ix = close_signature_set(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(signature_set.as_str()).unwrap(),
+        hash_vaa(&vaa.into()),
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn close_posted_message_ix(program_id: String, payer: String, message: String) -> JsValue {
+    let ix = close_posted_message(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        Pubkey::from_str(message.as_str()).unwrap(),
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn close_guardian_set_ix(program_id: String, payer: String, index: u32) -> JsValue {
+    let ix = close_guardian_set(
+        Pubkey::from_str(program_id.as_str()).unwrap(),
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        index,
+    )
+    .unwrap();
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn update_guardian_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
@@ -127,6 +356,7 @@ pub fn update_guardian_set_ix(program_id: String, payer: String, vaa: Vec<u8>) -
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         payload.new_guardian_set_index - 1,
         payload.new_guardian_set_index,
         vaa.sequence,
@@ -149,6 +379,70 @@ pub fn set_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_guardian_set_expiration_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_guardian_set_expiration(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_governance_emitter_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_governance_emitter(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
+#[wasm_bindgen]
+pub fn set_message_retention_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &program_id,
+    );
+    let ix = set_message_retention(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         vaa.sequence,
     );
     return JsValue::from_serde(&ix).unwrap();
@@ -170,6 +464,7 @@ pub fn transfer_fees_ix(program_id: String, payer: String, vaa: Vec<u8>) -> JsVa
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         vaa.sequence,
         Pubkey::new(&payload.to[..]),
     );
@@ -198,6 +493,7 @@ pub fn upgrade_contract_ix(
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         payload.new_contract,
         spill,
         vaa.sequence,
@@ -213,10 +509,48 @@ pub fn verify_signatures_ix(
     guardian_set: JsValue,
     signature_set: String,
     vaa_data: Vec<u8>,
+) -> JsValue {
+    let signature_set = Pubkey::from_str(signature_set.as_str()).unwrap();
+    verify_signatures_txs(
+        program_id,
+        payer,
+        guardian_set_index,
+        guardian_set,
+        Some(signature_set),
+        vaa_data,
+    )
+}
+
+/// Like `verify_signatures_ix`, but verifies into the signature set derived from the VAA body hash
+/// which any relayer can contribute to.
+#[wasm_bindgen]
+pub fn verify_signatures_derived_ix(
+    program_id: String,
+    payer: String,
+    guardian_set_index: u32,
+    guardian_set: JsValue,
+    vaa_data: Vec<u8>,
+) -> JsValue {
+    verify_signatures_txs(
+        program_id,
+        payer,
+        guardian_set_index,
+        guardian_set,
+        None,
+        vaa_data,
+    )
+}
+
+fn verify_signatures_txs(
+    program_id: String,
+    payer: String,
+    guardian_set_index: u32,
+    guardian_set: JsValue,
+    signature_set: Option<Pubkey>,
+    vaa_data: Vec<u8>,
 ) -> JsValue {
     let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
     let payer = Pubkey::from_str(payer.as_str()).unwrap();
-    let signature_set = Pubkey::from_str(signature_set.as_str()).unwrap();
 
     let guardian_set: GuardianSetData = guardian_set.into_serde().unwrap();
     let vaa = VAA::deserialize(vaa_data.as_slice()).unwrap();
@@ -241,10 +575,17 @@ pub fn verify_signatures_ix(
         h.finalize().into()
     };
 
+    // Larger guardian sets need a larger signer map, leaving less room for signatures per
+    // transaction.
+    let signatures_per_tx = if guardian_set.keys.len() > 19 { 5 } else { 7 };
+
     let mut verify_txs: Vec<Vec<Instruction>> = Vec::new();
-    for (_tx_index, chunk) in signature_items.chunks(7).enumerate() {
+    for (_tx_index, chunk) in signature_items.chunks(signatures_per_tx).enumerate() {
         let mut secp_payload = Vec::new();
-        let mut signature_status = [-1i8; 19];
+
+        // The signer map only needs to reach the highest guardian index in this chunk.
+        let map_len = chunk.iter().map(|s| s.index as usize + 1).max().unwrap_or(0);
+        let mut signature_status = vec![-1i8; map_len];
 
         let data_offset = 1 + chunk.len() * 11;
         let message_offset = data_offset + chunk.len() * 85;
@@ -288,16 +629,27 @@ pub fn verify_signatures_ix(
         };
 
         let payload = VerifySignaturesData {
+            secp_instructions: vec![0],
             signers: signature_status,
         };
 
-        let verify_ix = match verify_signatures(
-            program_id,
-            payer,
-            guardian_set_index,
-            signature_set,
-            payload,
-        ) {
+        let verify_ix = match signature_set {
+            Some(signature_set) => verify_signatures(
+                program_id,
+                payer,
+                guardian_set_index,
+                signature_set,
+                payload,
+            ),
+            None => verify_signatures_derived(
+                program_id,
+                payer,
+                guardian_set_index,
+                body_hash,
+                payload,
+            ),
+        };
+        let verify_ix = match verify_ix {
             Ok(v) => v,
             Err(e) => panic!("{:?}", e),
         };
@@ -308,6 +660,18 @@ pub fn verify_signatures_ix(
     JsValue::from_serde(&verify_txs).unwrap()
 }
 
+#[wasm_bindgen]
+pub fn signature_set_address_for_vaa(bridge: String, vaa: Vec<u8>) -> Vec<u8> {
+    let program_id = Pubkey::from_str(bridge.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let vaa: PostVAAData = vaa.into();
+    let body_hash = hash_vaa(&vaa);
+
+    signature_set_address(program_id, vaa.guardian_set_index, body_hash)
+        .to_bytes()
+        .to_vec()
+}
+
 #[wasm_bindgen]
 pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
     let program_id = Pubkey::from_str(bridge.as_str()).unwrap();
@@ -321,7 +685,7 @@ pub fn guardian_set_address(bridge: String, index: u32) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_guardian_set(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&GuardianSetData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&from_account_data::<GuardianSetData>(data.as_slice()).unwrap()).unwrap()
 }
 
 #[wasm_bindgen]
@@ -334,7 +698,7 @@ pub fn state_address(bridge: String) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_state(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&BridgeData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&from_account_data::<Versioned<BridgeData>>(data.as_slice()).unwrap().0).unwrap()
 }
 
 #[wasm_bindgen]
@@ -363,7 +727,12 @@ pub fn claim_address(program_id: String, vaa: Vec<u8>) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_posted_message(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&PostedVAAData::try_from_slice(data.as_slice()).unwrap().0).unwrap()
+    // Posted messages and VAAs share their layout.
+    let message = from_account_data::<PostedMessageData>(data.as_slice())
+        .map(|message| message.0)
+        .or_else(|_| from_account_data::<PostedVAAData>(data.as_slice()).map(|vaa| vaa.0))
+        .unwrap();
+    JsValue::from_serde(&message).unwrap()
 }
 
 #[wasm_bindgen]
@@ -75,7 +75,10 @@ use bridge::{
     },
     instruction,
     instructions,
-    types::ConsistencyLevel,
+    types::{
+        ConsistencyLevel,
+        Trailing,
+    },
     Initialize,
     InitializeData,
     PostMessageData,
@@ -85,8 +88,10 @@ use bridge::{
 };
 
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Discriminated,
 };
 
 pub use helpers::*;
@@ -148,9 +153,12 @@ mod helpers {
     }
 
     /// Fetch account data, the loop is there to re-attempt until data is available.
-    pub fn get_account_data<T: BorshDeserialize>(client: &RpcClient, account: &Pubkey) -> T {
+    pub fn get_account_data<T: BorshDeserialize + BorshSerialize + Discriminated>(
+        client: &RpcClient,
+        account: &Pubkey,
+    ) -> T {
         let account = client.get_account(account).unwrap();
-        T::try_from_slice(&account.data).unwrap()
+        from_account_data(&account.data).unwrap()
     }
 
     /// Generate `count` secp256k1 private keys, along with their ethereum-styled public key
@@ -200,6 +208,7 @@ mod helpers {
             emitter_address: emitter.pubkey().to_bytes(),
             sequence: 0,
             payload: data,
+            guardian_set_bump: Trailing(None),
             timestamp: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
@@ -259,6 +268,9 @@ mod helpers {
         payer: &Keypair,
         initial_guardians: &[[u8; 20]],
         fee: u64,
+        governance_chain: u16,
+        governance_emitter: [u8; 32],
+        message_retention_slots: u64,
     ) -> Result<Signature, ClientError> {
         execute(
             client,
@@ -270,6 +282,9 @@ mod helpers {
                 fee,
                 2_000_000_000,
                 initial_guardians,
+                governance_chain,
+                governance_emitter,
+                message_retention_slots,
             )
             .unwrap()],
             CommitmentConfig::processed(),
@@ -316,6 +331,116 @@ mod helpers {
         Ok(message.pubkey())
     }
 
+    pub fn post_message_derived(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        sequence: u64,
+        nonce: u32,
+        data: Vec<u8>,
+        fee: u64,
+    ) -> Result<Pubkey, ClientError> {
+        // Transfer money into the fee collector as it needs a balance/must exist.
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+
+        let instruction = instructions::post_message_derived(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            sequence,
+            nonce,
+            data,
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap();
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instruction,
+            ],
+            CommitmentConfig::processed(),
+        )?;
+
+        Ok(instructions::message_address(
+            *program,
+            emitter.pubkey(),
+            sequence,
+        ))
+    }
+
+    pub fn post_message_chunked(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        emitter: &Keypair,
+        nonce: u32,
+        data: Vec<u8>,
+        fee: u64,
+    ) -> Result<Pubkey, ClientError> {
+        let fee_collector = FeeCollector::<'_>::key(None, program);
+        let message = Keypair::new();
+
+        // Allocate the message for the full payload.
+        execute(
+            client,
+            payer,
+            &[payer, emitter, &message],
+            &[instructions::initialize_message(
+                *program,
+                payer.pubkey(),
+                emitter.pubkey(),
+                message.pubkey(),
+                data.len() as u32,
+            )
+            .unwrap()],
+            CommitmentConfig::processed(),
+        )?;
+
+        // Write the payload in chunks small enough to fit into a single transaction each.
+        for (i, chunk) in data.chunks(800).enumerate() {
+            execute(
+                client,
+                payer,
+                &[payer, emitter],
+                &[instructions::write_message(
+                    *program,
+                    emitter.pubkey(),
+                    message.pubkey(),
+                    (i * 800) as u32,
+                    chunk.to_vec(),
+                )
+                .unwrap()],
+                CommitmentConfig::processed(),
+            )?;
+        }
+
+        execute(
+            client,
+            payer,
+            &[payer, emitter],
+            &[
+                system_instruction::transfer(&payer.pubkey(), &fee_collector, fee),
+                instructions::finalize_message(
+                    *program,
+                    payer.pubkey(),
+                    emitter.pubkey(),
+                    message.pubkey(),
+                    nonce,
+                    ConsistencyLevel::Confirmed,
+                )
+                .unwrap(),
+            ],
+            CommitmentConfig::processed(),
+        )?;
+
+        Ok(message.pubkey())
+    }
+
     pub fn verify_signatures(
         client: &RpcClient,
         program: &Pubkey,
@@ -329,7 +454,7 @@ mod helpers {
         // Push Secp256k1 instructions for each signature we want to verify.
         for (i, key) in secret_keys.iter().enumerate() {
             // Set this signers signature position as present at 0.
-            let mut signers = [-1; 19];
+            let mut signers = vec![-1; i + 1];
             signers[i] = 0;
 
             execute(
@@ -343,7 +468,10 @@ mod helpers {
                         payer.pubkey(),
                         guardian_set_version,
                         signature_set.pubkey(),
-                        VerifySignaturesData { signers },
+                        VerifySignaturesData {
+                            secp_instructions: vec![],
+                            signers,
+                        },
                     )
                     .unwrap(),
                 ],
@@ -353,6 +481,94 @@ mod helpers {
         Ok(signature_set.pubkey())
     }
 
+    /// Verify signatures into the signature set derived from the body hash, returning its address.
+    /// `offset` is the position of the first key in the guardian set.
+    pub fn verify_signatures_derived(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        secret_keys: &[SecretKey],
+        offset: usize,
+        guardian_set_version: u32,
+    ) -> Result<Pubkey, ClientError> {
+        for (i, key) in secret_keys.iter().enumerate() {
+            let mut signers = vec![-1; offset + i + 1];
+            signers[offset + i] = 0;
+
+            execute(
+                client,
+                payer,
+                &[payer],
+                &vec![
+                    new_secp256k1_instruction(&key, &body),
+                    instructions::verify_signatures_derived(
+                        *program,
+                        payer.pubkey(),
+                        guardian_set_version,
+                        body,
+                        VerifySignaturesData {
+                            secp_instructions: vec![],
+                            signers,
+                        },
+                    )
+                    .unwrap(),
+                ],
+                CommitmentConfig::processed(),
+            )?;
+        }
+        Ok(instructions::signature_set_address(
+            *program,
+            guardian_set_version,
+            body,
+        ))
+    }
+
+    /// Verify all signatures in a single transaction, with one secp256k1 instruction per signer
+    /// ahead of the verify instruction.
+    pub fn verify_signatures_batched(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        body: [u8; 32],
+        secret_keys: &[SecretKey],
+        guardian_set_version: u32,
+    ) -> Result<Pubkey, ClientError> {
+        let signature_set = Keypair::new();
+        let tx_signers = &[payer, &signature_set];
+
+        // Signatures are numbered in the order their secp instructions are listed.
+        let mut instructions: Vec<Instruction> = secret_keys
+            .iter()
+            .map(|key| new_secp256k1_instruction(&key, &body))
+            .collect();
+        let secp_instructions = (0..secret_keys.len() as u8).collect();
+        let signers = (0..secret_keys.len() as i8).collect();
+
+        instructions.push(
+            instructions::verify_signatures(
+                *program,
+                payer.pubkey(),
+                guardian_set_version,
+                signature_set.pubkey(),
+                VerifySignaturesData {
+                    secp_instructions,
+                    signers,
+                },
+            )
+            .unwrap(),
+        );
+
+        execute(
+            client,
+            payer,
+            tx_signers,
+            &instructions,
+            CommitmentConfig::processed(),
+        )?;
+        Ok(signature_set.pubkey())
+    }
+
     pub fn post_vaa(
         client: &RpcClient,
         program: &Pubkey,
@@ -374,12 +590,57 @@ mod helpers {
         )
     }
 
+    pub fn post_vaa_from_buffer(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        signature_set: Pubkey,
+        vaa: PostVAAData,
+    ) -> Result<Pubkey, ClientError> {
+        let buffer = Keypair::new();
+        let body = instructions::serialize_vaa(&vaa);
+
+        // Write the body in chunks small enough to fit into a single transaction each.
+        for (i, chunk) in body.chunks(800).enumerate() {
+            execute(
+                client,
+                payer,
+                &[payer, &buffer],
+                &[instructions::write_vaa_buffer(
+                    *program,
+                    payer.pubkey(),
+                    buffer.pubkey(),
+                    body.len() as u32,
+                    (i * 800) as u32,
+                    chunk.to_vec(),
+                )],
+                CommitmentConfig::processed(),
+            )?;
+        }
+
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::post_vaa_from_buffer(
+                *program,
+                payer.pubkey(),
+                signature_set,
+                buffer.pubkey(),
+                &vaa,
+            )],
+            CommitmentConfig::processed(),
+        )?;
+        Ok(buffer.pubkey())
+    }
+
     pub fn upgrade_guardian_set(
         client: &RpcClient,
         program: &Pubkey,
         payer: &Keypair,
         payload_message: Pubkey,
         emitter: Pubkey,
+        emitter_chain: u16,
         old_index: u32,
         new_index: u32,
         sequence: u64,
@@ -393,6 +654,7 @@ mod helpers {
                 payer.pubkey(),
                 payload_message,
                 emitter,
+                emitter_chain,
                 old_index,
                 new_index,
                 sequence,
@@ -407,6 +669,7 @@ mod helpers {
         payer: &Keypair,
         payload_message: Pubkey,
         emitter: Pubkey,
+        emitter_chain: u16,
         new_contract: Pubkey,
         spill: Pubkey,
         sequence: u64,
@@ -420,6 +683,7 @@ mod helpers {
                 payer.pubkey(),
                 payload_message,
                 emitter,
+                emitter_chain,
                 new_contract,
                 spill,
                 sequence,
@@ -434,6 +698,7 @@ mod helpers {
         payer: &Keypair,
         message: Pubkey,
         emitter: Pubkey,
+        emitter_chain: u16,
         sequence: u64,
     ) -> Result<Signature, ClientError> {
         execute(
@@ -445,6 +710,7 @@ mod helpers {
                 payer.pubkey(),
                 message,
                 emitter,
+                emitter_chain,
                 sequence,
             )],
             CommitmentConfig::processed(),
@@ -457,6 +723,7 @@ mod helpers {
         payer: &Keypair,
         message: Pubkey,
         emitter: Pubkey,
+        emitter_chain: u16,
         recipient: Pubkey,
         sequence: u64,
     ) -> Result<Signature, ClientError> {
@@ -469,10 +736,138 @@ mod helpers {
                 payer.pubkey(),
                 message,
                 emitter,
+                emitter_chain,
                 sequence,
                 recipient,
             )],
             CommitmentConfig::processed(),
         )
     }
+
+    pub fn set_guardian_set_expiration(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        emitter_chain: u16,
+        sequence: u64,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_guardian_set_expiration(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                emitter_chain,
+                sequence,
+            )],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn set_message_retention(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        emitter_chain: u16,
+        sequence: u64,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_message_retention(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                emitter_chain,
+                sequence,
+            )],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn set_governance_emitter(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        message: Pubkey,
+        emitter: Pubkey,
+        emitter_chain: u16,
+        sequence: u64,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::set_governance_emitter(
+                *program,
+                payer.pubkey(),
+                message,
+                emitter,
+                emitter_chain,
+                sequence,
+            )],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn close_signature_set(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        recipient: Pubkey,
+        signature_set: Pubkey,
+        body_hash: [u8; 32],
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[
+                instructions::close_signature_set(*program, recipient, signature_set, body_hash)
+                    .unwrap(),
+            ],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn close_posted_message(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        recipient: Pubkey,
+        message: Pubkey,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::close_posted_message(*program, recipient, message).unwrap()],
+            CommitmentConfig::processed(),
+        )
+    }
+
+    pub fn close_guardian_set(
+        client: &RpcClient,
+        program: &Pubkey,
+        payer: &Keypair,
+        recipient: Pubkey,
+        guardian_set_index: u32,
+    ) -> Result<Signature, ClientError> {
+        execute(
+            client,
+            payer,
+            &[payer],
+            &[instructions::close_guardian_set(*program, recipient, guardian_set_index).unwrap()],
+            CommitmentConfig::processed(),
+        )
+    }
 }
@@ -13,13 +13,17 @@ use secp256k1::{
     SecretKey,
 };
 use sha3::Digest;
-use solana_client::rpc_client::RpcClient;
+use solana_client::{
+    client_error::ClientErrorKind,
+    rpc_client::RpcClient,
+};
 use solana_program::{
     borsh::try_from_slice_unchecked,
     hash,
     instruction::{
         AccountMeta,
         Instruction,
+        InstructionError,
     },
     program_pack::Pack,
     pubkey::Pubkey,
@@ -28,7 +32,6 @@ use solana_program::{
         create_account,
     },
     system_program,
-    sysvar,
 };
 use solana_sdk::{
     signature::{
@@ -36,11 +39,21 @@ use solana_sdk::{
         Keypair,
         Signer,
     },
-    transaction::Transaction,
+    transaction::{
+        Transaction,
+        TransactionError,
+    },
 };
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
+    SolitaireErrorCode,
+    Versioned,
+};
+use solitaire_client::{
+    compute_units,
+    decode_events,
+    simulate,
 };
 use std::{
     convert::TryInto,
@@ -59,23 +72,33 @@ use bridge::{
         Bridge,
         BridgeConfig,
         BridgeData,
+        BridgeExtension,
         FeeCollector,
         GuardianSet,
         GuardianSetData,
         GuardianSetDerivationData,
         MessageData,
+        PostedMessageData,
         PostedVAA,
         PostedVAAData,
         PostedVAADerivationData,
         SequenceTracker,
         SignatureSet,
         SignatureSetData,
+        VAABufferData,
+    },
+    events::{
+        Event,
+        EVENT_LOG_PREFIX,
     },
     instruction,
     instructions::hash_vaa,
     types::{
         ConsistencyLevel,
         GovernancePayloadGuardianSetChange,
+        GovernancePayloadSetGovernanceEmitter,
+        GovernancePayloadSetGuardianSetExpiration,
+        GovernancePayloadSetMessageRetention,
         GovernancePayloadSetMessageFee,
         GovernancePayloadTransferFees,
         GovernancePayloadUpgrade,
@@ -85,6 +108,9 @@ use bridge::{
     PostVAAData,
     SerializeGovernancePayload,
     Signature,
+    VerifySignaturesData,
+    CHAIN_ID_SOLANA,
+    MIN_MESSAGE_RETENTION_SLOTS,
 };
 use primitive_types::U256;
 use solana_sdk::hash::hashv;
@@ -98,6 +124,14 @@ const GOVERNANCE_KEY: [u8; 64] = [
     88, 97, 199,
 ];
 
+/// Compute units `post_vaa` is expected to stay under. The message derivation is searched once
+/// while parsing, creating the account reuses its bump.
+const POST_VAA_COMPUTE_BUDGET: u64 = 50_000;
+
+/// Compute units the runtime charges for deriving an address, which a search does for each bump it
+/// tries.
+const CREATE_PROGRAM_ADDRESS_UNITS: u64 = 1500;
+
 struct Context {
     public: Vec<[u8; 20]>,
     secret: Vec<SecretKey>,
@@ -139,11 +173,23 @@ fn run_integration_tests() {
     // Tests are currently unhygienic as It's difficult to wrap `solana-test-validator` within the
     // integration tests so for now we work around it by simply chain-calling our tests.
     test_bridge_messages(&mut context);
+    test_large_bridge_messages(&mut context);
+    test_derived_bridge_messages(&mut context);
+    test_prefunded_derived_message(&mut context);
+    test_bridge_events(&mut context);
+    test_post_vaa_compute_units(&mut context);
     test_foreign_bridge_messages(&mut context);
+    test_batched_signature_verification(&mut context);
+    test_derived_signature_set(&mut context);
+    test_forged_instructions_sysvar(&mut context);
+    test_large_foreign_bridge_messages(&mut context);
     test_invalid_emitter(&mut context);
     test_duplicate_messages_fail(&mut context);
     test_guardian_set_change(&mut context);
     test_guardian_set_change_fails(&mut context);
+    test_close_accounts(&mut context);
+    test_account_discriminators(&mut context);
+    test_malformed_instructions(&mut context);
     test_set_fees(&mut context);
     test_set_fees_fails(&mut context);
     test_free_fees(&mut context);
@@ -151,6 +197,154 @@ fn run_integration_tests() {
     test_transfer_fees_fails(&mut context);
     test_transfer_too_much(&mut context);
     test_transfer_total_fails(&mut context);
+    test_large_guardian_set_quorum(&mut context);
+    test_set_guardian_set_expiration(&mut context);
+    test_set_message_retention(&mut context);
+    test_set_governance_emitter(&mut context);
+}
+
+#[test]
+fn test_idl() {
+    let idl = instruction::idl::<bridge::error::Error>();
+
+    // Instructions are listed in dispatch order, with their accounts in the order they are parsed.
+    let post_message = &idl.instructions[instruction::Instruction::PostMessage as usize];
+    assert_eq!(post_message.name, "PostMessage");
+    assert_eq!(
+        post_message.args,
+        solitaire::idl::IdlType::Defined("PostMessageData".to_string())
+    );
+
+    let names: Vec<&str> = post_message
+        .accounts
+        .iter()
+        .map(|a| a.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "bridge",
+            "message",
+            "emitter",
+            "sequence",
+            "payer",
+            "fee_collector"
+        ]
+    );
+
+    let bridge = &post_message.accounts[0];
+    assert!(bridge.writable && !bridge.signer);
+    assert_eq!(bridge.seed.as_deref(), Some("Bridge"));
+    assert_eq!(bridge.data.as_deref(), Some("BridgeData"));
+
+    let message = &post_message.accounts[1];
+    assert!(message.writable && message.signer);
+    assert_eq!(message.state.as_deref(), Some("Uninitialized"));
+
+    // The clock is read through its syscall rather than passed as an account.
+    assert_eq!(post_message.accounts.len(), 6);
+
+    // Argument types are resolvable by name.
+    assert!(idl.types.iter().any(|t| t.name() == "PostMessageData"));
+    assert!(idl.types.iter().any(|t| t.name() == "ConsistencyLevel"));
+
+    // Program errors follow the framework errors.
+    let error = idl
+        .errors
+        .iter()
+        .find(|e| e.name == "GuardianSetMismatch")
+        .unwrap();
+    assert_eq!(
+        error.code,
+        solitaire::CUSTOM_ERROR_OFFSET + bridge::error::Error::GuardianSetMismatch as u32
+    );
+
+    assert!(idl.to_json().starts_with("{\"name\":\"bridge\""));
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_generated_builders() {
+    use bridge::api::{
+        PostMessageAccounts,
+        SetFeesAccounts,
+    };
+    use solitaire_client::AccEntry;
+
+    let program_id = Pubkey::new_unique();
+    let payer = Keypair::new();
+    let emitter = Keypair::new();
+    let message = Keypair::new();
+    let copy = |k: &Keypair| Keypair::from_bytes(&k.to_bytes()).unwrap();
+
+    // The generated builder must produce exactly what the hand-written one does.
+    let sequence = bridge::accounts::Sequence::key(
+        &bridge::accounts::SequenceDerivationData {
+            emitter_key: &emitter.pubkey(),
+        },
+        &program_id,
+    );
+    let (generated, signers) = instruction::PostMessage::build(
+        program_id,
+        PostMessageAccounts {
+            bridge: AccEntry::Derived(program_id),
+            message: AccEntry::Signer(copy(&message)),
+            emitter: AccEntry::SignerRO(copy(&emitter)),
+            sequence: AccEntry::Unprivileged(sequence),
+            payer: AccEntry::Signer(copy(&payer)),
+            fee_collector: AccEntry::Derived(program_id),
+        },
+        bridge::PostMessageData {
+            nonce: 1,
+            payload: vec![1, 2, 3],
+            consistency_level: ConsistencyLevel::Confirmed,
+        },
+    )
+    .unwrap();
+    let expected = bridge::instructions::post_message(
+        program_id,
+        payer.pubkey(),
+        emitter.pubkey(),
+        message.pubkey(),
+        1,
+        vec![1, 2, 3],
+        ConsistencyLevel::Confirmed,
+    )
+    .unwrap();
+    assert_eq!(generated, expected);
+    assert_eq!(signers.len(), 3);
+
+    // Nested account structs are passed as a list of their accounts.
+    let vaa = Pubkey::new_unique();
+    let claim = Pubkey::new_unique();
+    let (generated, _) = instruction::SetFees::build(
+        program_id,
+        SetFeesAccounts {
+            payer: AccEntry::Signer(copy(&payer)),
+            bridge: AccEntry::Derived(program_id),
+            extension: AccEntry::DerivedRO(program_id),
+            vaa: AccEntry::Nested(vec![
+                AccEntry::UnprivilegedRO(vaa),
+                AccEntry::Unprivileged(claim),
+            ]),
+        },
+        bridge::SetFeesData {},
+    )
+    .unwrap();
+    let bridge = Bridge::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let extension =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    assert_eq!(
+        &generated.accounts[..5],
+        &[
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(bridge, false),
+            AccountMeta::new_readonly(extension, false),
+            AccountMeta::new_readonly(vaa, false),
+            AccountMeta::new(claim, false),
+        ]
+    );
+    assert_eq!(generated.data[0], instruction::Instruction::SetFees as u8);
 }
 
 fn test_initialize(context: &mut Context) {
@@ -163,7 +357,17 @@ fn test_initialize(context: &mut Context) {
         .as_secs()
         - 10;
 
-    common::initialize(client, program, payer, &*context.public.clone(), 500);
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    common::initialize(
+        client,
+        program,
+        payer,
+        &*context.public.clone(),
+        500,
+        CHAIN_ID_SOLANA,
+        governance.pubkey().to_bytes(),
+        MIN_MESSAGE_RETENTION_SLOTS,
+    );
     common::sync(client, payer);
 
     // Verify the initial bridge state is as expected.
@@ -174,13 +378,22 @@ fn test_initialize(context: &mut Context) {
     );
 
     // Fetch account states.
-    let bridge: BridgeData = common::get_account_data(client, &bridge_key);
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
     let guardian_set: GuardianSetData = common::get_account_data(client, &guardian_set_key);
 
     // Bridge Config should be as expected.
     assert_eq!(bridge.guardian_set_index, 0);
     assert_eq!(bridge.config.guardian_set_expiration_time, 2_000_000_000);
     assert_eq!(bridge.config.fee, 500);
+    assert_eq!(bridge.config.governance_chain, CHAIN_ID_SOLANA);
+    assert_eq!(
+        bridge.config.message_retention_slots,
+        MIN_MESSAGE_RETENTION_SLOTS
+    );
+    assert_eq!(
+        bridge.config.governance_emitter,
+        governance.pubkey().to_bytes()
+    );
 
     // Guardian set account must also be as expected.
     assert_eq!(guardian_set.index, 0);
@@ -201,7 +414,7 @@ fn test_bridge_messages(context: &mut Context) {
         let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
         // Post the message, publishing the data for guardian consumption.
-        let message_key = common::post_message(
+        common::post_message(
             client,
             program,
             payer,
@@ -214,6 +427,12 @@ fn test_bridge_messages(context: &mut Context) {
 
         // Emulate Guardian behaviour, verifying the data and publishing signatures/VAA.
         let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 1);
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: hash_vaa(&vaa).to_vec(),
+            },
+            &program,
+        );
         let signature_set =
             common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
         common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -250,7 +469,7 @@ fn test_bridge_messages(context: &mut Context) {
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
     // Post the message, publishing the data for guardian consumption.
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -263,6 +482,12 @@ fn test_bridge_messages(context: &mut Context) {
 
     // Emulate Guardian behaviour, verifying the data and publishing signatures/VAA.
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -394,7 +619,7 @@ fn test_guardian_set_change(context: &mut Context) {
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -406,6 +631,12 @@ fn test_guardian_set_change(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -415,6 +646,7 @@ fn test_guardian_set_change(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         0,
         1,
         sequence,
@@ -430,7 +662,7 @@ fn test_guardian_set_change(context: &mut Context) {
     );
 
     // Fetch account states.
-    let bridge: BridgeData = common::get_account_data(client, &bridge_key);
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
     let guardian_set: GuardianSetData = common::get_account_data(client, &guardian_set_key);
 
     // Confirm the bridge now has a new guardian set, and no other fields have shifted.
@@ -445,7 +677,7 @@ fn test_guardian_set_change(context: &mut Context) {
 
     // Submit the message a second time with a new nonce.
     let nonce = rand::thread_rng().gen();
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -462,6 +694,12 @@ fn test_guardian_set_change(context: &mut Context) {
     // Emulate Guardian behaviour, verifying the data and publishing signatures/VAA.
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -510,7 +748,7 @@ fn test_guardian_set_change_fails(context: &mut Context) {
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -521,6 +759,12 @@ fn test_guardian_set_change_fails(context: &mut Context) {
     )
     .unwrap();
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
 
     assert!(common::upgrade_guardian_set(
         client,
@@ -528,6 +772,7 @@ fn test_guardian_set_change_fails(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         1,
         2,
         sequence,
@@ -535,6 +780,176 @@ fn test_guardian_set_change_fails(context: &mut Context) {
     .is_err());
 }
 
+fn test_close_accounts(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let posted_message: PostedMessageData = common::get_account_data(client, &message_key);
+    assert_eq!(posted_message.0.payer.0, Some(payer.pubkey()));
+
+    // Messages must be retained for the configured number of slots.
+    assert!(common::close_posted_message(client, program, payer, payer.pubkey(), message_key)
+        .is_err());
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
+    assert_eq!(signatures.payer.0, Some(payer.pubkey()));
+
+    // Signature sets can't be closed before the VAA is posted.
+    assert!(common::close_signature_set(
+        client,
+        program,
+        payer,
+        payer.pubkey(),
+        signature_set,
+        body
+    )
+    .is_err());
+
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::sync(client, payer);
+
+    // Refunds only go to the original payer.
+    let other = Keypair::new();
+    assert!(common::close_signature_set(
+        client,
+        program,
+        payer,
+        other.pubkey(),
+        signature_set,
+        body
+    )
+    .is_err());
+    assert!(
+        common::close_posted_message(client, program, payer, other.pubkey(), message_key).is_err()
+    );
+
+    common::close_signature_set(client, program, payer, payer.pubkey(), signature_set, body)
+        .unwrap();
+
+    // Wait out the retention period.
+    let posted_slot = posted_message.0.posted_slot.0.unwrap();
+    while client.get_slot().unwrap() < posted_slot + MIN_MESSAGE_RETENTION_SLOTS {
+        std::thread::sleep(std::time::Duration::from_millis(400));
+    }
+    common::close_posted_message(client, program, payer, payer.pubkey(), message_key).unwrap();
+    common::sync(client, payer);
+
+    assert!(client.get_account(&signature_set).is_err());
+    assert!(client.get_account(&message_key).is_err());
+
+    // Neither the active nor an unexpired previous guardian set can be closed.
+    assert!(common::close_guardian_set(client, program, payer, payer.pubkey(), 1).is_err());
+    assert!(common::close_guardian_set(client, program, payer, payer.pubkey(), 0).is_err());
+}
+
+fn test_account_discriminators(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // A message from the governance emitter shares its layout with a VAA, but is not one.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100),
+    }
+    .try_to_vec()
+    .unwrap();
+    let message_key = common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    let err = common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        sequence,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ClientErrorKind::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) if *code == SolitaireErrorCode::InvalidDiscriminator as u32
+    ));
+}
+
+fn test_malformed_instructions(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let data = [3u8; 32].to_vec();
+
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, data, nonce, 0, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    let post_vaa = bridge::instructions::post_vaa(*program, payer.pubkey(), signature_set, vaa);
+
+    let fails_with = |instruction: Instruction, expected: SolitaireErrorCode| {
+        let err = common::execute(
+            client,
+            payer,
+            &[payer],
+            &[instruction],
+            solana_sdk::commitment_config::CommitmentConfig::processed(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ClientErrorKind::TransactionError(TransactionError::InstructionError(
+                _,
+                InstructionError::Custom(code),
+            )) if *code == expected as u32
+        ));
+    };
+
+    // Instruction data without an instruction to dispatch.
+    let mut empty = post_vaa.clone();
+    empty.data.clear();
+    fails_with(empty, SolitaireErrorCode::EmptyInstruction);
+
+    // Accounts that stop short of the accounts struct.
+    let mut truncated = post_vaa.clone();
+    truncated.accounts.truncate(3);
+    fails_with(truncated, SolitaireErrorCode::NotEnoughAccountKeys);
+
+    // PostVAA is strict, so accounts beyond its own are rejected.
+    let mut trailing = post_vaa;
+    trailing
+        .accounts
+        .push(AccountMeta::new_readonly(Pubkey::new_unique(), false));
+    fails_with(trailing, SolitaireErrorCode::UnexpectedAccount);
+}
+
 fn test_set_fees(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
@@ -548,7 +963,7 @@ fn test_set_fees(context: &mut Context) {
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -560,6 +975,12 @@ fn test_set_fees(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -569,6 +990,7 @@ fn test_set_fees(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         sequence,
     )
     .unwrap();
@@ -577,7 +999,7 @@ fn test_set_fees(context: &mut Context) {
     // Fetch Bridge to check on-state value.
     let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
     let fee_collector = FeeCollector::key(None, &program);
-    let bridge: BridgeData = common::get_account_data(client, &bridge_key);
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
     assert_eq!(bridge.config.fee, 100);
 
     // Check that posting a new message fails with too small a fee.
@@ -600,7 +1022,7 @@ fn test_set_fees(context: &mut Context) {
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let nonce = rand::thread_rng().gen();
     let message = [0u8; 32].to_vec();
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -612,6 +1034,12 @@ fn test_set_fees(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -663,7 +1091,7 @@ fn test_set_fees_fails(context: &mut Context) {
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -675,6 +1103,12 @@ fn test_set_fees_fails(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -684,6 +1118,7 @@ fn test_set_fees_fails(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         sequence,
     )
     .is_err());
@@ -702,7 +1137,7 @@ fn test_free_fees(context: &mut Context) {
         .try_to_vec()
         .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -714,6 +1149,12 @@ fn test_free_fees(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -723,6 +1164,7 @@ fn test_free_fees(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         sequence,
     )
     .unwrap();
@@ -731,7 +1173,7 @@ fn test_free_fees(context: &mut Context) {
     // Fetch Bridge to check on-state value.
     let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
     let fee_collector = FeeCollector::key(None, &program);
-    let bridge: BridgeData = common::get_account_data(client, &bridge_key);
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
     assert_eq!(bridge.config.fee, 0);
 
     // Check that posting a new message is free.
@@ -740,10 +1182,15 @@ fn test_free_fees(context: &mut Context) {
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let nonce = rand::thread_rng().gen();
     let message = [0u8; 32].to_vec();
-    let message_key =
-        common::post_message(client, program, payer, &emitter, nonce, message.clone(), 0).unwrap();
+    common::post_message(client, program, payer, &emitter, nonce, message.clone(), 0).unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -799,7 +1246,7 @@ fn test_transfer_fees(context: &mut Context) {
     let fee_collector = FeeCollector::key(None, &program);
     let account_balance = client.get_account(&fee_collector).unwrap().lamports;
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -811,6 +1258,12 @@ fn test_transfer_fees(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -820,6 +1273,7 @@ fn test_transfer_fees(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         payer.pubkey(),
         sequence,
     )
@@ -848,7 +1302,7 @@ fn test_transfer_fees_fails(context: &mut Context) {
     let fee_collector = FeeCollector::key(None, &program);
     let account_balance = client.get_account(&fee_collector).unwrap().lamports;
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -860,6 +1314,12 @@ fn test_transfer_fees_fails(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -870,6 +1330,7 @@ fn test_transfer_fees_fails(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         payer.pubkey(),
         sequence,
     )
@@ -895,7 +1356,7 @@ fn test_transfer_too_much(context: &mut Context) {
     let fee_collector = FeeCollector::key(None, &program);
     let account_balance = client.get_account(&fee_collector).unwrap().lamports;
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -907,6 +1368,12 @@ fn test_transfer_too_much(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
@@ -918,83 +1385,884 @@ fn test_transfer_too_much(context: &mut Context) {
         payer,
         message_key,
         emitter.pubkey(),
+        CHAIN_ID_SOLANA,
         payer.pubkey(),
         sequence,
     )
     .is_err());
 }
 
-fn test_foreign_bridge_messages(context: &mut Context) {
-    // Initialize a wormhole bridge on Solana to test with.
+fn test_large_bridge_messages(context: &mut Context) {
     let (ref payer, ref client, ref program) = common::setup();
-    let nonce = rand::thread_rng().gen();
-    let message = [0u8; 32].to_vec();
+
+    // A payload far larger than what fits into a single post_message instruction.
+    let mut message = vec![0u8; 3000];
+    rand::thread_rng().fill(&mut message[..]);
     let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
 
-    // Verify the VAA generated on a foreign chain.
-    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+    // Post the message in chunks, publishing the data for guardian consumption.
+    let message_key = common::post_message_chunked(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
 
-    // Derive where we expect created accounts to be.
+    // Finalized messages must look exactly like messages published through post_message.
+    let account = client.get_account(&message_key).unwrap();
+    assert_eq!(&account.data[..3], b"msg");
+
+    let posted_message: PostedMessageData = common::get_account_data(client, &message_key);
+    assert_eq!(posted_message.0.nonce, nonce);
+    assert_eq!(posted_message.0.sequence, sequence);
+    assert_eq!(posted_message.0.emitter_chain, 1);
+    assert_eq!(posted_message.0.consistency_level, 1);
+    assert_eq!(posted_message.0.payload, message);
+    assert_eq!(
+        posted_message.0.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // A message can only be finalized once.
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &emitter],
+        &[bridge::instructions::finalize_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            message_key,
+            nonce,
+            ConsistencyLevel::Confirmed,
+        )
+        .unwrap()],
+        solana_sdk::commitment_config::CommitmentConfig::processed(),
+    )
+    .is_err());
+
+    // Only the emitter that initialized a message may write into it.
+    let other = Keypair::new();
+    let pending = Keypair::new();
+    common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &pending],
+        &[bridge::instructions::initialize_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            pending.pubkey(),
+            32,
+        )
+        .unwrap()],
+        solana_sdk::commitment_config::CommitmentConfig::processed(),
+    )
+    .unwrap();
+    assert!(common::execute(
+        client,
+        payer,
+        &[payer, &other],
+        &[bridge::instructions::write_message(
+            *program,
+            other.pubkey(),
+            pending.pubkey(),
+            0,
+            vec![1u8; 32],
+        )
+        .unwrap()],
+        solana_sdk::commitment_config::CommitmentConfig::processed(),
+    )
+    .is_err());
+
+    // Payloads beyond the maximum are rejected before anything is allocated.
+    let oversized = Keypair::new();
+    let err = common::execute(
+        client,
+        payer,
+        &[payer, &emitter, &oversized],
+        &[bridge::instructions::initialize_message(
+            *program,
+            payer.pubkey(),
+            emitter.pubkey(),
+            oversized.pubkey(),
+            u32::MAX,
+        )
+        .unwrap()],
+        solana_sdk::commitment_config::CommitmentConfig::processed(),
+    )
+    .unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ClientErrorKind::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) if *code == solitaire::CUSTOM_ERROR_OFFSET + bridge::error::Error::MessageOverflow as u32
+    ));
+}
+
+fn test_derived_bridge_messages(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+
+    for _ in 0..2 {
+        let nonce = rand::thread_rng().gen();
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+        // Post the message into the account derived from the emitter and sequence.
+        let message_key = common::post_message_derived(
+            client,
+            program,
+            payer,
+            &emitter,
+            sequence,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .unwrap();
+        common::sync(client, payer);
+
+        let posted_message: PostedMessageData = common::get_account_data(client, &message_key);
+        assert_eq!(posted_message.0.nonce, nonce);
+        assert_eq!(posted_message.0.sequence, sequence);
+        assert_eq!(posted_message.0.emitter_chain, 1);
+        assert_eq!(posted_message.0.payload, message);
+        assert_eq!(
+            posted_message.0.emitter_address,
+            emitter.pubkey().to_bytes()
+        );
+    }
+
+    // Using a sequence other than the emitter's next one is rejected.
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.peek(emitter.pubkey().to_bytes());
+    assert!(common::post_message_derived(
+        client,
+        program,
+        payer,
+        &emitter,
+        sequence + 1,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .is_err());
+}
+
+fn test_prefunded_derived_message(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Funding the next message address up front must not lock the emitter out.
+    let message_key =
+        bridge::instructions::message_address(*program, emitter.pubkey(), sequence);
+    common::transfer(client, payer, &message_key, 1).unwrap();
+    common::sync(client, payer);
+
+    common::post_message_derived(
+        client,
+        program,
+        payer,
+        &emitter,
+        sequence,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    let account = client.get_account(&message_key).unwrap();
+    assert_eq!(account.owner, *program);
+    assert_eq!(
+        account.lamports,
+        client
+            .get_minimum_balance_for_rent_exemption(account.data.len())
+            .unwrap()
+    );
+
+    let posted_message: PostedMessageData = common::get_account_data(client, &message_key);
+    assert_eq!(posted_message.0.nonce, nonce);
+    assert_eq!(posted_message.0.sequence, sequence);
+    assert_eq!(posted_message.0.payload, message);
+}
+
+fn test_bridge_events(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let fee_collector = FeeCollector::<'_>::key(None, program);
+    let emitter = Keypair::new();
+    let message = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let data = [1u8; 32].to_vec();
+
+    // Only simulated, so the emitter sequence is not consumed.
+    let logs = simulate(
+        client,
+        payer,
+        &[payer, &emitter, &message],
+        &[
+            system_instruction::transfer(&payer.pubkey(), &fee_collector, 10_000),
+            bridge::instructions::post_message(
+                *program,
+                payer.pubkey(),
+                emitter.pubkey(),
+                message.pubkey(),
+                nonce,
+                data.clone(),
+                ConsistencyLevel::Confirmed,
+            )
+            .unwrap(),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(
+        decode_events::<Event, _>(program, EVENT_LOG_PREFIX, &logs),
+        vec![Event::MessagePosted {
+            emitter: emitter.pubkey(),
+            sequence: 0,
+            message: message.pubkey(),
+        }]
+    );
+
+    // Events logged by any other program must be ignored.
+    assert!(
+        decode_events::<Event, _>(&Pubkey::new_unique(), EVENT_LOG_PREFIX, &logs).is_empty()
+    );
+
+    // Posting a VAA emits the hash and the account it is stored in.
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, data, nonce, 0, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    let posted_vaa = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: body.to_vec(),
+        },
+        program,
+    );
+
+    let logs = simulate(
+        client,
+        payer,
+        &[payer],
+        &[bridge::instructions::post_vaa(
+            *program,
+            payer.pubkey(),
+            signature_set,
+            vaa.clone(),
+        )],
+    )
+    .unwrap();
+
+    assert_eq!(
+        decode_events::<Event, _>(program, EVENT_LOG_PREFIX, &logs),
+        vec![Event::VAAPosted {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            hash: body,
+            message: posted_vaa,
+        }]
+    );
+}
+
+fn test_post_vaa_compute_units(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let data = [2u8; 32].to_vec();
+
+    let (vaa, body, _body_hash) = common::generate_vaa(&emitter, data, nonce, 0, 1);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+
+    let (_, guardian_set_bump) = GuardianSet::<'_, { AccountState::Initialized }>::key_and_bump(
+        &GuardianSetDerivationData {
+            index: vaa.guardian_set_index,
+        },
+        program,
+    );
+    let post_vaa = bridge::instructions::post_vaa(*program, payer.pubkey(), signature_set, vaa);
+
+    // Clients predating the guardian set bump leave it out, the program then searches for it.
+    let mut searching = post_vaa.clone();
+    assert_eq!(searching.data.pop(), Some(guardian_set_bump));
+
+    let units = |ix: Instruction| {
+        let logs = simulate(client, payer, &[payer], &[ix]).unwrap();
+        compute_units(program, &logs)
+    };
+    let (searched, bumped) = (units(searching), units(post_vaa));
+    assert!(bumped > 0 && bumped <= POST_VAA_COMPUTE_BUDGET);
+
+    // The search tries every bump above the guardian set's before finding it, the known bump is
+    // checked with a single derivation.
+    let skipped = u64::from(u8::MAX - guardian_set_bump);
+    assert!(
+        searched + CREATE_PROGRAM_ADDRESS_UNITS > bumped + skipped * CREATE_PROGRAM_ADDRESS_UNITS
+    );
+}
+
+fn test_foreign_bridge_messages(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Verify the VAA generated on a foreign chain.
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+
+    // Derive where we expect created accounts to be.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::sync(client, payer);
+
+    // Fetch chain accounts to verify state.
+    let posted_message: PostedVAAData = common::get_account_data(client, &message_key);
+    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
+
+    assert_eq!(posted_message.0.vaa_version, 0);
+    assert_eq!(posted_message.0.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.0.nonce, nonce);
+    assert_eq!(posted_message.0.sequence, sequence);
+    assert_eq!(posted_message.0.emitter_chain, 2);
+    assert_eq!(posted_message.0.payload, message);
+    assert_eq!(
+        posted_message.0.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // Verify on chain Signatures
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+
+    for (signature, secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
+        assert_eq!(*signature, true);
+    }
+}
+
+fn test_batched_signature_verification(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+
+    // Verify all guardian signatures of a VAA in a single transaction.
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+    let signature_set =
+        common::verify_signatures_batched(client, program, payer, body, &context.secret, 0)
+            .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::sync(client, payer);
+
+    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+    assert!(signatures.signatures.iter().all(|s| *s));
+}
+
+fn test_derived_signature_set(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+
+    // Two relayers each submit half of the signatures into the same derived signature set.
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+    let (first, second) = context.secret.split_at(context.secret.len() / 2);
+    let signature_set =
+        common::verify_signatures_derived(client, program, payer, body, first, 0, 0).unwrap();
+    let other = Keypair::new();
+    common::transfer(client, payer, &other.pubkey(), 1_000_000_000).unwrap();
+    let signature_set_other = common::verify_signatures_derived(
+        client,
+        program,
+        &other,
+        body,
+        second,
+        first.len(),
+        0,
+    )
+    .unwrap();
+    assert_eq!(signature_set, signature_set_other);
+
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::sync(client, payer);
+
+    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
+    assert_eq!(signatures.hash, body);
+    assert_eq!(signatures.guardian_set_index, 0);
+    assert!(signatures.signatures.iter().all(|s| *s));
+}
+
+fn test_forged_instructions_sysvar(context: &mut Context) {
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (_vaa, body, _body_hash) = common::generate_vaa(&emitter, vec![0u8; 32], nonce, 0, 1);
+
+    let fails_with_forged = |mut instruction: Instruction, signers: &[&Keypair]| {
+        // Stand in for the instructions sysvar with an account the caller controls.
+        instruction.accounts[3] = AccountMeta::new_readonly(Pubkey::new_unique(), false);
+        let secp = solana_sdk::secp256k1_instruction::new_secp256k1_instruction(
+            &context.secret[0],
+            &body,
+        );
+        let err = common::execute(
+            client,
+            payer,
+            signers,
+            &[secp, instruction],
+            solana_sdk::commitment_config::CommitmentConfig::processed(),
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ClientErrorKind::TransactionError(TransactionError::InstructionError(
+                _,
+                InstructionError::Custom(code),
+            )) if *code == SolitaireErrorCode::ConstraintViolated as u32
+        ));
+    };
+
+    let signature_set = Keypair::new();
+    let verify = bridge::instructions::verify_signatures(
+        *program,
+        payer.pubkey(),
+        0,
+        signature_set.pubkey(),
+        VerifySignaturesData {
+            secp_instructions: vec![],
+            signers: vec![0],
+        },
+    )
+    .unwrap();
+    fails_with_forged(verify, &[payer, &signature_set]);
+
+    let verify = bridge::instructions::verify_signatures_derived(
+        *program,
+        payer.pubkey(),
+        0,
+        body,
+        VerifySignaturesData {
+            secp_instructions: vec![],
+            signers: vec![0],
+        },
+    )
+    .unwrap();
+    fails_with_forged(verify, &[payer]);
+}
+
+fn test_large_foreign_bridge_messages(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::new();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // A payload far larger than what fits into a single post_vaa instruction.
+    let mut message = vec![0u8; 4000];
+    rand::thread_rng().fill(&mut message[..]);
+
+    // Verify the VAA generated on a foreign chain.
+    let (vaa, body, _) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 2);
+
+    // Derive where we expect created accounts to be.
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    let buffer =
+        common::post_vaa_from_buffer(client, program, payer, signature_set, vaa.clone()).unwrap();
+    common::sync(client, payer);
+
+    // Fetch chain accounts to verify state.
+    let posted_message: PostedVAAData = common::get_account_data(client, &message_key);
+    let buffer_data: VAABufferData = common::get_account_data(client, &buffer);
+
+    assert_eq!(buffer_data.body, bridge::instructions::serialize_vaa(&vaa));
+    assert_eq!(posted_message.0.vaa_version, 0);
+    assert_eq!(posted_message.0.vaa_signature_account, signature_set);
+    assert_eq!(posted_message.0.nonce, nonce);
+    assert_eq!(posted_message.0.sequence, sequence);
+    assert_eq!(posted_message.0.emitter_chain, 2);
+    assert_eq!(posted_message.0.payload, message);
+    assert_eq!(
+        posted_message.0.emitter_address,
+        emitter.pubkey().to_bytes()
+    );
+
+    // A buffer whose contents do not match the signed hash must be rejected.
+    let (vaa, body, _) =
+        common::generate_vaa(&emitter, message.clone(), nonce.wrapping_add(1), 0, 2);
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    let mut tampered = vaa.clone();
+    tampered.payload[0] ^= 0xff;
+    assert!(
+        common::post_vaa_from_buffer(client, program, payer, signature_set, tampered).is_err()
+    );
+}
+
+fn test_transfer_total_fails(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+
+    // Be sure any previous tests have fully committed.
+    common::sync(client, payer);
+
+    let fee_collector = FeeCollector::key(None, &program);
+    let account_balance = client.get_account(&fee_collector).unwrap().lamports;
+
+    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
+    let recipient = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadTransferFees {
+        amount: (account_balance + 10_000).into(),
+        to: payer.pubkey().to_bytes(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+
+    // Transferring total fees should fail, to prevent the account being de-allocated.
+    assert!(common::transfer_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        payer.pubkey(),
+        sequence,
+    )
+    .is_err());
+    common::sync(client, payer);
+
+    // The fee should have been paid, but other than that the balance should be exactly the same,
+    // I.E non-zero.
+    assert_eq!(
+        client.get_account(&fee_collector).unwrap().lamports,
+        account_balance + 10_000
+    );
+}
+
+fn test_upgrade_contract(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+
+    // Upgrade the guardian set with a new set of guardians.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(1);
+
+    // New Contract Address
+    let new_contract = Pubkey::new_unique();
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadUpgrade {
+        new_contract: new_contract.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::upgrade_contract(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        new_contract,
+        Pubkey::new_unique(),
+        sequence,
+    )
+    .unwrap();
+    common::sync(client, payer);
+}
+
+fn test_large_guardian_set_quorum(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
+
+    // Upgrade to a guardian set larger than what fits into a single verification transaction.
+    let (new_public_keys, new_secret_keys) = common::generate_keys(25);
+
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 2,
+        new_guardian_set: new_public_keys.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        1,
+        2,
+        sequence,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    let guardian_set_key = GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+        &GuardianSetDerivationData { index: 2 },
+        &program,
+    );
+    let guardian_set: GuardianSetData = common::get_account_data(client, &guardian_set_key);
+    assert_eq!(guardian_set.keys, new_public_keys);
+
+    // Quorum for 25 guardians is 17, exactly that many signatures must be accepted.
+    let quorum = 17;
+    let message = [0u8; 32].to_vec();
+    let emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(&emitter, message.clone(), nonce, 2, 1);
+    let signature_set = common::verify_signatures(
+        client,
+        program,
+        payer,
+        body,
+        &new_secret_keys[..quorum],
+        2,
+    )
+    .unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::sync(client, payer);
+
+    // One signature short of quorum must be rejected.
+    let nonce = rand::thread_rng().gen();
+    let (vaa, body, _) = common::generate_vaa(&emitter, message.clone(), nonce, 2, 1);
+    let signature_set = common::verify_signatures(
+        client,
+        program,
+        payer,
+        body,
+        &new_secret_keys[..quorum - 1],
+        2,
+    )
+    .unwrap();
+    assert!(common::post_vaa(client, program, payer, signature_set, vaa).is_err());
+    common::sync(client, payer);
+
+    // Guardian sets containing duplicate keys are refused.
+    let nonce = rand::thread_rng().gen();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 3,
+        new_guardian_set: vec![new_public_keys[0]; 2],
+    }
+    .try_to_vec()
+    .unwrap();
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 2, 1);
     let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
         &PostedVAADerivationData {
             payload_hash: hash_vaa(&vaa).to_vec(),
         },
         &program,
     );
-
     let signature_set =
-        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+        common::verify_signatures(client, program, payer, body, &new_secret_keys, 2).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    assert!(common::upgrade_guardian_set(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        2,
+        3,
+        sequence,
+    )
+    .is_err());
     common::sync(client, payer);
-
-    // Fetch chain accounts to verify state.
-    let posted_message: PostedVAAData = common::get_account_data(client, &message_key);
-    let signatures: SignatureSetData = common::get_account_data(client, &signature_set);
-
-    assert_eq!(posted_message.0.vaa_version, 0);
-    assert_eq!(posted_message.0.vaa_signature_account, signature_set);
-    assert_eq!(posted_message.0.nonce, nonce);
-    assert_eq!(posted_message.0.sequence, sequence);
-    assert_eq!(posted_message.0.emitter_chain, 2);
-    assert_eq!(posted_message.0.payload, message);
-    assert_eq!(
-        posted_message.0.emitter_address,
-        emitter.pubkey().to_bytes()
-    );
-
-    // Verify on chain Signatures
-    assert_eq!(signatures.hash, body);
-    assert_eq!(signatures.guardian_set_index, 0);
-
-    for (signature, secret_key) in signatures.signatures.iter().zip(context.secret.iter()) {
-        assert_eq!(*signature, true);
-    }
 }
 
-fn test_transfer_total_fails(context: &mut Context) {
+fn test_set_guardian_set_expiration(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
+    let guardian_set_key = |index| {
+        GuardianSet::<'_, { AccountState::Uninitialized }>::key(
+            &GuardianSetDerivationData { index },
+            &program,
+        )
+    };
+
+    // Shorten the window, while keeping it long enough for later tests relying on old sets.
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGuardianSetExpiration {
+        expiration_time: 1_000_000_000,
+    }
+    .try_to_vec()
+    .unwrap();
 
-    // Be sure any previous tests have fully committed.
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        10_000,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    common::set_guardian_set_expiration(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        sequence,
+    )
+    .unwrap();
     common::sync(client, payer);
 
-    let fee_collector = FeeCollector::key(None, &program);
-    let account_balance = client.get_account(&fee_collector).unwrap().lamports;
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
+    assert_eq!(bridge.config.guardian_set_expiration_time, 1_000_000_000);
 
-    // Prepare to remove total balance, adding 10_000 to include the fee we're about to pay.
-    let recipient = Keypair::new();
+    // Sets that were already replaced keep their expiration.
+    let replaced: GuardianSetData = common::get_account_data(client, &guardian_set_key(1));
+
+    // The new window applies to the next rotation.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
     let nonce = rand::thread_rng().gen();
-    let message = GovernancePayloadTransferFees {
-        amount: (account_balance + 10_000).into(),
-        to: payer.pubkey().to_bytes(),
+    let message = GovernancePayloadGuardianSetChange {
+        new_guardian_set_index: 3,
+        new_guardian_set: context.public.clone(),
     }
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -1006,51 +2274,117 @@ fn test_transfer_total_fails(context: &mut Context) {
     .unwrap();
 
     let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
         common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
-
-    // Transferring total fees should fail, to prevent the account being de-allocated.
-    assert!(common::transfer_fees(
+    common::upgrade_guardian_set(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        payer.pubkey(),
+        CHAIN_ID_SOLANA,
+        2,
+        3,
         sequence,
     )
-    .is_err());
+    .unwrap();
     common::sync(client, payer);
 
-    // The fee should have been paid, but other than that the balance should be exactly the same,
-    // I.E non-zero.
+    let posted_message: PostedVAAData = common::get_account_data(client, &message_key);
+    let rotated: GuardianSetData = common::get_account_data(client, &guardian_set_key(2));
     assert_eq!(
-        client.get_account(&fee_collector).unwrap().lamports,
-        account_balance + 10_000
+        rotated.expiration_time,
+        posted_message.0.vaa_time + 1_000_000_000
     );
+
+    let unchanged: GuardianSetData = common::get_account_data(client, &guardian_set_key(1));
+    assert_eq!(unchanged.expiration_time, replaced.expiration_time);
 }
 
-fn test_upgrade_contract(context: &mut Context) {
+fn test_set_message_retention(context: &mut Context) {
     // Initialize a wormhole bridge on Solana to test with.
     let (ref payer, ref client, ref program) = common::setup();
+    let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
 
-    // Upgrade the guardian set with a new set of guardians.
-    let (new_public_keys, new_secret_keys) = common::generate_keys(1);
+    // Retention shorter than the minimum is rejected, longer is stored.
+    for (message_retention_slots, accepted) in [
+        (MIN_MESSAGE_RETENTION_SLOTS - 1, false),
+        (MIN_MESSAGE_RETENTION_SLOTS + 100, true),
+    ] {
+        let sequence = context.seq.next(emitter.pubkey().to_bytes());
+        let nonce = rand::thread_rng().gen();
+        let message = GovernancePayloadSetMessageRetention {
+            message_retention_slots,
+        }
+        .try_to_vec()
+        .unwrap();
 
-    // New Contract Address
-    let new_contract = Pubkey::new_unique();
+        common::post_message(
+            client,
+            program,
+            payer,
+            &emitter,
+            nonce,
+            message.clone(),
+            10_000,
+        )
+        .unwrap();
 
-    let nonce = rand::thread_rng().gen();
+        let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+        let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+            &PostedVAADerivationData {
+                payload_hash: hash_vaa(&vaa).to_vec(),
+            },
+            &program,
+        );
+        let signature_set =
+            common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+        common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+        let result = common::set_message_retention(
+            client,
+            program,
+            payer,
+            message_key,
+            emitter.pubkey(),
+            CHAIN_ID_SOLANA,
+            sequence,
+        );
+        assert_eq!(result.is_ok(), accepted);
+        common::sync(client, payer);
+    }
+
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
+    assert_eq!(
+        bridge.config.message_retention_slots,
+        MIN_MESSAGE_RETENTION_SLOTS + 100
+    );
+}
+
+fn test_set_governance_emitter(context: &mut Context) {
+    // Initialize a wormhole bridge on Solana to test with.
+    let (ref payer, ref client, ref program) = common::setup();
     let emitter = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
     let sequence = context.seq.next(emitter.pubkey().to_bytes());
-    let message = GovernancePayloadUpgrade {
-        new_contract: new_contract.clone(),
+
+    // Hand governance over to an emitter living on a foreign chain.
+    let foreign_emitter = Keypair::new();
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetGovernanceEmitter {
+        governance_chain: 2,
+        governance_emitter: foreign_emitter.pubkey().to_bytes(),
     }
     .try_to_vec()
     .unwrap();
 
-    let message_key = common::post_message(
+    common::post_message(
         client,
         program,
         payer,
@@ -1061,20 +2395,110 @@ fn test_upgrade_contract(context: &mut Context) {
     )
     .unwrap();
 
-    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 0, 1);
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
     let signature_set =
-        common::verify_signatures(client, program, payer, body, &context.secret, 0).unwrap();
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
     common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
-    common::upgrade_contract(
+    common::set_governance_emitter(
         client,
         program,
         payer,
         message_key,
         emitter.pubkey(),
-        new_contract,
-        Pubkey::new_unique(),
+        CHAIN_ID_SOLANA,
         sequence,
     )
     .unwrap();
     common::sync(client, payer);
+
+    // The bridge should now only trust the foreign emitter.
+    let bridge_key = Bridge::<'_, { AccountState::Uninitialized }>::key(None, &program);
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
+    assert_eq!(bridge.config.governance_chain, 2);
+    assert_eq!(
+        bridge.config.governance_emitter,
+        foreign_emitter.pubkey().to_bytes()
+    );
+
+    // Governance from the foreign chain is accepted.
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(200),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    let (vaa, body, body_hash) =
+        common::generate_vaa(&foreign_emitter, message.clone(), nonce, 1, 2);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa.clone()).unwrap();
+    common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        foreign_emitter.pubkey(),
+        2,
+        vaa.sequence,
+    )
+    .unwrap();
+    common::sync(client, payer);
+
+    let bridge: Versioned<BridgeData> = common::get_account_data(client, &bridge_key);
+    assert_eq!(bridge.config.fee, 200);
+
+    // The previous governance key is no longer respected.
+    let sequence = context.seq.next(emitter.pubkey().to_bytes());
+    let nonce = rand::thread_rng().gen();
+    let message = GovernancePayloadSetMessageFee {
+        fee: U256::from(100),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    common::post_message(
+        client,
+        program,
+        payer,
+        &emitter,
+        nonce,
+        message.clone(),
+        200,
+    )
+    .unwrap();
+
+    let (vaa, body, body_hash) = common::generate_vaa(&emitter, message.clone(), nonce, 1, 1);
+    let message_key = PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa).to_vec(),
+        },
+        &program,
+    );
+    let signature_set =
+        common::verify_signatures(client, program, payer, body, &context.secret, 1).unwrap();
+    common::post_vaa(client, program, payer, signature_set, vaa).unwrap();
+    assert!(common::set_fees(
+        client,
+        program,
+        payer,
+        message_key,
+        emitter.pubkey(),
+        CHAIN_ID_SOLANA,
+        sequence,
+    )
+    .is_err());
+    common::sync(client, payer);
 }
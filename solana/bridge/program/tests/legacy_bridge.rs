@@ -0,0 +1,348 @@
+//! Governance of a bridge deployed before its settings were stored on-chain, whose account is
+//! sized for the legacy layout. The program runs in-process, with the system program emulated
+//! through syscall stubs.
+
+use borsh::BorshSerialize;
+use bridge::{
+    accounts::{
+        BridgeData,
+        BridgeExtension,
+        BridgeExtensionData,
+        MessageData,
+        PostedVAAData,
+    },
+    error::Error,
+    instruction::solitaire,
+    instructions,
+    types::{
+        GovernancePayloadSetGovernanceEmitter,
+        GovernancePayloadSetMessageFee,
+        GovernancePayloadSetMessageRetention,
+        Trailing,
+    },
+    vaa::SerializeGovernancePayload,
+    CHAIN_ID_SOLANA,
+    MIN_MESSAGE_RETENTION_SLOTS,
+};
+use primitive_types::U256;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{
+        ProgramResult,
+        SUCCESS,
+    },
+    instruction::Instruction,
+    program_stubs::{
+        set_syscall_stubs,
+        SyscallStubs,
+    },
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction::SystemInstruction,
+    system_program,
+};
+use solana_sdk::program_utils::limited_deserialize;
+use solitaire::{
+    from_account_data,
+    processors::seeded::Seeded,
+    AccountState,
+    Discriminated,
+    SolitaireError,
+    Versioned,
+};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+};
+
+/// The parts of the system program the bridge invokes, applied to the accounts passed along.
+struct SystemProgram;
+
+impl SyscallStubs for SystemProgram {
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Rent) = Rent::default() };
+        SUCCESS
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        ix: &Instruction,
+        infos: &[AccountInfo],
+        _seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        assert_eq!(ix.program_id, system_program::id());
+        let account = |i: usize| {
+            infos
+                .iter()
+                .find(|info| *info.key == ix.accounts[i].pubkey)
+                .unwrap()
+        };
+
+        match limited_deserialize(&ix.data).unwrap() {
+            SystemInstruction::CreateAccount {
+                lamports,
+                space,
+                owner,
+            } => {
+                transfer(account(0), account(1), lamports);
+                allocate(account(1), space);
+                assign(account(1), owner);
+            }
+            SystemInstruction::Transfer { lamports } => transfer(account(0), account(1), lamports),
+            SystemInstruction::Allocate { space } => allocate(account(0), space),
+            SystemInstruction::Assign { owner } => assign(account(0), owner),
+            other => panic!("unexpected system instruction {:?}", other),
+        }
+        Ok(())
+    }
+}
+
+fn transfer(from: &AccountInfo, to: &AccountInfo, lamports: u64) {
+    **from.lamports.borrow_mut() -= lamports;
+    **to.lamports.borrow_mut() += lamports;
+}
+
+fn allocate(account: &AccountInfo, space: u64) {
+    assert!(account.data_is_empty(), "the runtime can't resize accounts");
+    *account.data.borrow_mut() = Box::leak(vec![0; space as usize].into_boxed_slice());
+}
+
+/// Like the runtime, update the owner every copy of the account info points at.
+fn assign(account: &AccountInfo, owner: Pubkey) {
+    unsafe { *(account.owner as *const Pubkey as *mut Pubkey) = owner };
+}
+
+/// Accounts of a cluster, handed to instructions by key.
+struct Cluster {
+    program: Pubkey,
+    accounts: HashMap<Pubkey, AccountInfo<'static>>,
+}
+
+impl Cluster {
+    fn new() -> Self {
+        set_syscall_stubs(Box::new(SystemProgram));
+        Cluster {
+            program: Pubkey::new_unique(),
+            accounts: HashMap::new(),
+        }
+    }
+
+    fn add(&mut self, key: Pubkey, lamports: u64, data: Vec<u8>, owner: Pubkey) {
+        let info = AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            false,
+            Box::leak(Box::new(lamports)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            owner == solana_program::bpf_loader::id(),
+            0,
+        );
+        self.accounts.insert(key, info);
+    }
+
+    fn data(&self, key: &Pubkey) -> Vec<u8> {
+        self.accounts[key].data.borrow().to_vec()
+    }
+
+    /// Post a governance VAA carrying `payload`, returning the account it was posted to.
+    fn post_vaa(
+        &mut self,
+        emitter_chain: u16,
+        emitter: [u8; 32],
+        sequence: u64,
+        payload: Vec<u8>,
+    ) -> Pubkey {
+        let message = MessageData {
+            vaa_version: 1,
+            emitter_chain,
+            emitter_address: emitter,
+            sequence,
+            payload,
+            payer: Trailing(None),
+            posted_slot: Trailing(None),
+            ..Default::default()
+        };
+        let data = [
+            PostedVAAData::DISCRIMINATOR,
+            &PostedVAAData(message).try_to_vec().unwrap(),
+        ]
+        .concat();
+
+        let key = Pubkey::new_unique();
+        self.add(key, 1, data, self.program);
+        key
+    }
+
+    fn process(&mut self, ix: Instruction) -> ProgramResult {
+        for meta in &ix.accounts {
+            if !self.accounts.contains_key(&meta.pubkey) {
+                self.add(meta.pubkey, 0, vec![], system_program::id());
+            }
+        }
+        let infos: Vec<AccountInfo> = ix
+            .accounts
+            .iter()
+            .map(|meta| {
+                let mut info = self.accounts[&meta.pubkey].clone();
+                info.is_signer = meta.is_signer;
+                info.is_writable = meta.is_writable;
+                info
+            })
+            .collect();
+        solitaire(&ix.program_id, &infos, &ix.data)
+    }
+}
+
+/// Guardian set index, fee collector balance, guardian set expiration and fee, serialized without
+/// a tag as bridges deployed before versioning stored them.
+fn legacy_bridge(fee: u64) -> Vec<u8> {
+    (0u32, 0u64, 3600u32, fee).try_to_vec().unwrap()
+}
+
+const PAYER: Pubkey = Pubkey::new_from_array([1; 32]);
+
+/// A cluster with a legacy bridge, returning the address of the bridge account.
+fn legacy_cluster() -> (Cluster, Pubkey) {
+    let mut cluster = Cluster::new();
+    let bridge = Pubkey::find_program_address(&[b"Bridge"], &cluster.program).0;
+    cluster.add(bridge, 1_000_000, legacy_bridge(100), cluster.program);
+    cluster.add(PAYER, 1_000_000_000, vec![], system_program::id());
+    cluster.add(
+        system_program::id(),
+        1,
+        vec![],
+        solana_program::bpf_loader::id(),
+    );
+    (cluster, bridge)
+}
+
+fn legacy_emitter() -> [u8; 32] {
+    Pubkey::from_str(env!("EMITTER_ADDRESS"))
+        .unwrap()
+        .to_bytes()
+}
+
+/// Post a governance VAA and process the instruction `build` makes of it.
+fn govern(
+    cluster: &mut Cluster,
+    (emitter_chain, emitter): (u16, [u8; 32]),
+    sequence: u64,
+    payload: Vec<u8>,
+    build: fn(Pubkey, Pubkey, Pubkey, Pubkey, u16, u64) -> Instruction,
+) -> ProgramResult {
+    let message = cluster.post_vaa(emitter_chain, emitter, sequence, payload);
+    let ix = build(
+        cluster.program,
+        PAYER,
+        message,
+        Pubkey::new(&emitter),
+        emitter_chain,
+        sequence,
+    );
+    cluster.process(ix)
+}
+
+fn set_fee(
+    cluster: &mut Cluster,
+    governance: (u16, [u8; 32]),
+    sequence: u64,
+    fee: u64,
+) -> ProgramResult {
+    let payload = GovernancePayloadSetMessageFee {
+        fee: U256::from(fee),
+    };
+    let payload = SerializeGovernancePayload::try_to_vec(&payload).unwrap();
+    govern(
+        cluster,
+        governance,
+        sequence,
+        payload,
+        instructions::set_fees,
+    )
+}
+
+fn extension(cluster: &Cluster) -> BridgeExtensionData {
+    let key =
+        BridgeExtension::<'_, { AccountState::MaybeInitialized }>::key(None, &cluster.program);
+    from_account_data(&cluster.data(&key)).unwrap()
+}
+
+fn error(error: Error) -> ProgramResult {
+    Err(SolitaireError::from(error).into())
+}
+
+#[test]
+fn rotate_emitter_of_legacy_bridge() {
+    let (mut cluster, bridge) = legacy_cluster();
+    let legacy = (CHAIN_ID_SOLANA, legacy_emitter());
+    let rotated = (2, [7u8; 32]);
+
+    // Rotate the emitter with a VAA from the one the bridge was built with.
+    let payload = GovernancePayloadSetGovernanceEmitter {
+        governance_chain: rotated.0,
+        governance_emitter: rotated.1,
+    };
+    let payload = SerializeGovernancePayload::try_to_vec(&payload).unwrap();
+    govern(
+        &mut cluster,
+        legacy,
+        1,
+        payload,
+        instructions::set_governance_emitter,
+    )
+    .unwrap();
+
+    // The bridge account keeps its legacy layout, the new emitter lives in the extension.
+    assert_eq!(cluster.data(&bridge), legacy_bridge(100));
+    let settings = extension(&cluster);
+    assert_eq!(settings.governance_chain, rotated.0);
+    assert_eq!(settings.governance_emitter, rotated.1);
+    assert_eq!(settings.message_retention_slots, u64::MAX);
+
+    // Governance is only accepted from the new emitter from now on.
+    assert_eq!(
+        set_fee(&mut cluster, legacy, 2, 200),
+        error(Error::InvalidGovernanceKey)
+    );
+    set_fee(&mut cluster, rotated, 3, 300).unwrap();
+
+    assert_eq!(cluster.data(&bridge), legacy_bridge(300));
+    let bridge_data: Versioned<BridgeData> = from_account_data(&cluster.data(&bridge)).unwrap();
+    assert_eq!(bridge_data.config.fee, 300);
+}
+
+#[test]
+fn set_message_retention_of_legacy_bridge() {
+    let (mut cluster, bridge) = legacy_cluster();
+    let legacy = (CHAIN_ID_SOLANA, legacy_emitter());
+    let set_retention = |cluster: &mut Cluster, sequence, message_retention_slots| {
+        let payload = GovernancePayloadSetMessageRetention {
+            message_retention_slots,
+        };
+        let payload = SerializeGovernancePayload::try_to_vec(&payload).unwrap();
+        govern(
+            cluster,
+            legacy,
+            sequence,
+            payload,
+            instructions::set_message_retention,
+        )
+    };
+
+    // Messages have to be kept until guardians had the chance to observe them.
+    assert_eq!(
+        set_retention(&mut cluster, 1, MIN_MESSAGE_RETENTION_SLOTS - 1),
+        error(Error::MessageRetentionTooShort)
+    );
+    set_retention(&mut cluster, 2, MIN_MESSAGE_RETENTION_SLOTS).unwrap();
+
+    assert_eq!(cluster.data(&bridge), legacy_bridge(100));
+    let settings = extension(&cluster);
+    assert_eq!(settings.governance_chain, legacy.0);
+    assert_eq!(settings.governance_emitter, legacy.1);
+    assert_eq!(
+        settings.message_retention_slots,
+        MIN_MESSAGE_RETENTION_SLOTS
+    );
+}
@@ -4,10 +4,6 @@ use borsh::{
     BorshDeserialize,
     BorshSerialize,
 };
-use solana_program::{
-    self,
-    sysvar::clock::Clock,
-};
 
 use bridge::{
     accounts::{
@@ -25,7 +21,7 @@ use solitaire::{
     CreationLamports::Exempt,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct PostVAA<'b> {
     /// Information about the current guardian set.
     pub guardian_set: Info<'b>,
@@ -41,9 +37,6 @@ pub struct PostVAA<'b> {
 
     /// Account used to pay for auxillary instructions.
     pub payer: Mut<Signer<Info<'b>>>,
-
-    /// Clock used for timestamping.
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'b> InstructionContext<'b> for PostVAA<'b> {
@@ -33,6 +33,9 @@ token_bridge_address=B6RHG3mfcckmrYN1UhmJzyS1XX3fZKbkeUcpJe9Sy3FE
 initial_guardian=befa429d57cd18b7f8a4d91a2da9ab4af05d0fbe
 recipient_address=90F8bf6A479f320ead074411a4B0e7944Ea8c9C1
 chain_id_ethereum=2
+governance_chain=1
+governance_emitter=0000000000000000000000000000000000000000000000000000000000000004
+message_retention_slots=1000
 
 retry () {
   while ! $@; do
@@ -83,12 +86,12 @@ token-bridge-client create-meta "$nft" "Not a PUNK 2🎸" "PUNK2🎸" "https://w
 
 # Create the bridge contract at a known address
 # OK to fail on subsequent attempts (already created).
-retry client create-bridge "$bridge_address" "$initial_guardian" 86400 100
+retry client create-bridge "$bridge_address" "$initial_guardian" 86400 100 "$governance_chain" "$governance_emitter" "$message_retention_slots"
 
 # Initialize the token bridge
-retry token-bridge-client create-bridge "$token_bridge_address" "$bridge_address"
+retry token-bridge-client create-bridge "$token_bridge_address" "$bridge_address" "$governance_chain" "$governance_emitter"
 # Initialize the NFT bridge
-retry token-bridge-client create-bridge "$nft_bridge_address" "$bridge_address"
+retry token-bridge-client create-bridge "$nft_bridge_address" "$bridge_address" "$governance_chain" "$governance_emitter"
 
 pushd /usr/src/clients/token_bridge
 # Register the Token Bridge Endpoint on ETH
@@ -10,6 +10,7 @@ use solitaire::{
     Data,
     Derive,
     Info,
+    Versioned,
 };
 
 pub type ShareMint<'a, const STATE: AccountState> = Data<'a, SplMint, { STATE }>;
@@ -61,7 +62,7 @@ impl<'b, const STATE: AccountState> Seeded<&ToCustodyTokenAccountDerivationData>
     }
 }
 
-pub type MigrationPool<'a, const STATE: AccountState> = Data<'a, PoolData, { STATE }>;
+pub type MigrationPool<'a, const STATE: AccountState> = Data<'a, Versioned<PoolData>, { STATE }>;
 
 pub struct MigrationPoolDerivationData {
     pub from: Pubkey,
@@ -22,7 +22,7 @@ use borsh::{
 use crate::accounts::MigrationPoolDerivationData;
 use solitaire::*;
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct AddLiquidity<'b> {
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
@@ -36,7 +36,7 @@ pub struct AddLiquidity<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct AddLiquidityData {
     pub amount: u64,
 }
@@ -25,7 +25,7 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct ClaimShares<'b> {
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
@@ -37,7 +37,7 @@ pub struct ClaimShares<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct ClaimSharesData {
     pub amount: u64,
 }
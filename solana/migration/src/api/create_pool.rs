@@ -22,7 +22,7 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CreatePool<'b> {
     pub payer: Mut<Signer<Info<'b>>>,
 
@@ -36,7 +36,7 @@ pub struct CreatePool<'b> {
     pub custody_signer: CustodySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CreatePoolData {}
 
 pub fn create_pool(
@@ -12,7 +12,6 @@ use crate::{
         SplAccount,
         SplMint,
     },
-    MigrationError::WrongMint,
 };
 use borsh::{
     BorshDeserialize,
@@ -28,21 +27,24 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct MigrateTokens<'b> {
+    #[has_one(from = from_mint, to = to_mint)]
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_mint: Data<'b, SplMint, { AccountState::Initialized }>,
     pub to_token_custody: Mut<ToCustodyTokenAccount<'b, { AccountState::Initialized }>>,
     pub from_token_custody: Mut<FromCustodyTokenAccount<'b, { AccountState::Initialized }>>,
 
+    #[has_one(mint = from_mint)]
     pub user_from_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[has_one(mint = to_mint)]
     pub user_to_acc: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub custody_signer: CustodySigner<'b>,
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct MigrateTokensData {
     pub amount: u64,
 }
@@ -52,18 +54,6 @@ pub fn migrate_tokens(
     accs: &mut MigrateTokens,
     data: MigrateTokensData,
 ) -> Result<()> {
-    if *accs.from_mint.info().key != accs.pool.from {
-        return Err(WrongMint.into());
-    }
-    if *accs.to_mint.info().key != accs.pool.to {
-        return Err(WrongMint.into());
-    }
-    if accs.user_from_acc.mint != accs.pool.from {
-        return Err(WrongMint.into());
-    }
-    if accs.user_to_acc.mint != accs.pool.to {
-        return Err(WrongMint.into());
-    }
     accs.to_token_custody.verify_derivation(
         ctx.program_id,
         &ToCustodyTokenAccountDerivationData {
@@ -27,7 +27,7 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct RemoveLiquidity<'b> {
     pub pool: Mut<MigrationPool<'b, { AccountState::Initialized }>>,
     pub from_mint: Data<'b, SplMint, { AccountState::Initialized }>,
@@ -41,7 +41,7 @@ pub struct RemoveLiquidity<'b> {
     pub authority_signer: AuthoritySigner<'b>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct RemoveLiquidityData {
     pub amount: u64,
 }
@@ -9,8 +9,8 @@ use api::{
     remove_liquidity::*,
 };
 use solitaire::{
+    custom_error,
     solitaire,
-    SolitaireError,
 };
 
 pub mod accounts;
@@ -28,13 +28,9 @@ extern crate wasm_bindgen;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 pub mod wasm;
 
-pub enum MigrationError {
-    WrongMint,
-}
-
-impl From<MigrationError> for SolitaireError {
-    fn from(t: MigrationError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+custom_error! {
+    pub enum MigrationError {
+        WrongMint,
     }
 }
 
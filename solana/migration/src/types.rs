@@ -13,13 +13,25 @@ use solitaire::{
         AccountOwner,
         Owned,
     },
+    Discriminated,
+    Migrate,
 };
 use spl_token::state::{
     Account,
     Mint,
 };
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+#[discriminator(legacy)]
 pub struct PoolData {
     pub from: Pubkey,
     pub to: Pubkey,
@@ -31,5 +43,10 @@ impl Owned for PoolData {
     }
 }
 
+/// Still the layout of the pools deployed before it was versioned.
+impl Migrate for PoolData {
+    const VERSION: u8 = 0;
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
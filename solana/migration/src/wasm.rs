@@ -16,8 +16,10 @@ use crate::{
 use borsh::BorshDeserialize;
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
 };
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
@@ -218,5 +220,5 @@ pub fn to_custody_address(program_id: String, pool: String) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_pool(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&PoolData::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&from_account_data::<Versioned<PoolData>>(data.as_slice()).unwrap().0).unwrap()
 }
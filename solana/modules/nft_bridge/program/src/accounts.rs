@@ -14,12 +14,16 @@ pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
 pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
 pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
-pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, BridgeData, { STATE }>;
+pub type CoreBridge<'a, const STATE: AccountState> = Data<'a, Versioned<BridgeData>, { STATE }>;
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
 pub type ConfigAccount<'b, const STATE: AccountState> =
-    Derive<Data<'b, Config, { STATE }>, "config">;
+    Derive<Data<'b, Versioned<Config>, { STATE }>, "config">;
+
+/// Governance of legacy configs, see `ConfigExtension`.
+pub type ConfigExtensionAccount<'b, const STATE: AccountState> =
+    Derive<Data<'b, ConfigExtension, { STATE }>, "config_extension">;
 
 pub type CustodyAccount<'b, const STATE: AccountState> = Data<'b, SplAccount, { STATE }>;
 
@@ -56,7 +60,7 @@ impl<'b, const STATE: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
     }
 }
 
-pub type WrappedTokenMeta<'b, const STATE: AccountState> = Data<'b, WrappedMeta, { STATE }>;
+pub type WrappedTokenMeta<'b, const STATE: AccountState> = Data<'b, Versioned<WrappedMeta>, { STATE }>;
 
 pub struct WrappedMetaDerivationData {
     pub mint_key: Pubkey,
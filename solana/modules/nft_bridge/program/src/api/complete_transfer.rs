@@ -39,43 +39,31 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CompleteNative<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.meta().emitter_chain,
+        emitter_address: vaa.meta().emitter_address,
+    })]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub to_authority: MaybeMut<Info<'b>>,
+    #[seeds(&CustodyAccountDerivationData { mint: *mint.info().key })]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
 }
 
-impl<'a> From<&CompleteNative<'a>> for EndpointDerivationData {
-    fn from(accs: &CompleteNative<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
-impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
-    fn from(accs: &CompleteNative<'a>) -> Self {
-        CustodyAccountDerivationData {
-            mint: *accs.mint.info().key,
-        }
-    }
-}
-
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CompleteNativeData {}
 
 pub fn complete_native(
@@ -83,16 +71,6 @@ pub fn complete_native(
     accs: &mut CompleteNative,
     _data: CompleteNativeData,
 ) -> Result<()> {
-    // Verify the chain registration
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Verify mints
     if *accs.mint.info().key != accs.custody.mint {
         return Err(InvalidMint.into());
@@ -119,7 +97,6 @@ pub fn complete_native(
     }
 
     // Prevent vaa double signing
-    accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     if !accs.to.is_initialized() {
@@ -155,7 +132,7 @@ pub fn complete_native(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CompleteWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
@@ -163,25 +140,25 @@ pub struct CompleteWrapped<'b> {
     // Signed message for the transfer
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
 
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.meta().emitter_chain,
+        emitter_address: vaa.meta().emitter_address,
+    })]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::MaybeInitialized }>>,
     pub to_authority: MaybeMut<Info<'b>>,
+    #[seeds(&WrappedDerivationData {
+        token_chain: vaa.token_chain,
+        token_address: vaa.token_address,
+        token_id: vaa.token_id,
+    })]
     pub mint: Mut<WrappedMint<'b, { AccountState::MaybeInitialized }>>,
     pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
 
     pub mint_authority: MintSigner<'b>,
 }
 
-impl<'a> From<&CompleteWrapped<'a>> for EndpointDerivationData {
-    fn from(accs: &CompleteWrapped<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
 impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
     fn from(accs: &CompleteWrapped<'a>) -> Self {
         WrappedDerivationData {
@@ -203,7 +180,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -213,22 +190,11 @@ pub fn complete_wrapped(
 ) -> Result<()> {
     use bstr::ByteSlice;
 
-    // Verify the chain registration
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mint
-    let derivation_data: WrappedDerivationData = (&*accs).into();
-    accs.mint
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Verify VAA
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
     }
 
-    accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     // Initialize the NFT if it doesn't already exist
@@ -290,7 +256,7 @@ pub fn complete_wrapped(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CompleteWrappedMeta<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
@@ -298,8 +264,17 @@ pub struct CompleteWrappedMeta<'b> {
     // VAA for the transfer; this does not need to get claimed
     pub vaa: PayloadMessage<'b, PayloadTransfer>,
 
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.meta().emitter_chain,
+        emitter_address: vaa.meta().emitter_address,
+    })]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
+    #[seeds(&WrappedDerivationData {
+        token_chain: vaa.token_chain,
+        token_address: vaa.token_address,
+        token_id: vaa.token_id,
+    })]
     pub mint: WrappedMint<'b, { AccountState::Initialized }>,
     pub meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
@@ -309,25 +284,6 @@ pub struct CompleteWrappedMeta<'b> {
     pub mint_authority: MintSigner<'b>,
 }
 
-impl<'a> From<&CompleteWrappedMeta<'a>> for EndpointDerivationData {
-    fn from(accs: &CompleteWrappedMeta<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
-impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedDerivationData {
-    fn from(accs: &CompleteWrappedMeta<'a>) -> Self {
-        WrappedDerivationData {
-            token_chain: accs.vaa.token_chain,
-            token_address: accs.vaa.token_address,
-            token_id: accs.vaa.token_id,
-        }
-    }
-}
-
 impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedMetaDerivationData {
     fn from(accs: &CompleteWrappedMeta<'a>) -> Self {
         WrappedMetaDerivationData {
@@ -339,7 +295,7 @@ impl<'a> From<&CompleteWrappedMeta<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrappedMeta<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CompleteWrappedMetaData {}
 
 pub fn complete_wrapped_meta(
@@ -349,16 +305,6 @@ pub fn complete_wrapped_meta(
 ) -> Result<()> {
     use bstr::ByteSlice;
 
-    // Verify the chain registration
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mint
-    let derivation_data: WrappedDerivationData = (&*accs).into();
-    accs.mint
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Verify VAA
     if accs.vaa.to_chain != CHAIN_ID_SOLANA {
         return Err(InvalidChain.into());
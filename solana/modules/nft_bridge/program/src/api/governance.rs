@@ -1,13 +1,16 @@
 use crate::{
     accounts::{
         ConfigAccount,
+        ConfigExtensionAccount,
         Endpoint,
         EndpointDerivationData,
     },
     messages::{
+        GovernancePayloadSetGovernanceEmitter,
         GovernancePayloadUpgrade,
         PayloadGovernanceRegisterChain,
     },
+    types::Config,
     TokenBridgeError::{
         InvalidChain,
         InvalidGovernanceKey,
@@ -23,37 +26,37 @@ use bridge::{
 use solana_program::{
     account_info::AccountInfo,
     program::invoke_signed,
-    pubkey::Pubkey,
     sysvar::{
         clock::Clock,
         rent::Rent,
     },
 };
 use solitaire::{
-    processors::seeded::Seeded,
+    processors::seeded::{
+        AccountSize,
+        Seeded,
+    },
     CreationLamports::Exempt,
     *,
 };
 
 // Confirm that a ClaimableVAA came from the correct chain, signed by the right emitter.
-fn verify_governance<'a, T>(vaa: &ClaimableVAA<'a, T>) -> Result<()>
+fn verify_governance<'a, T>(config: &Config, vaa: &ClaimableVAA<'a, T>) -> Result<()>
 where
     T: DeserializePayload,
 {
-    let expected_emitter = std::env!("EMITTER_ADDRESS");
-    let current_emitter = format!(
-        "{}",
-        Pubkey::new_from_array(vaa.message.meta().emitter_address)
-    );
-    // Fail if the emitter is not the known governance key, or the emitting chain is not Solana.
-    if expected_emitter != current_emitter || vaa.message.meta().emitter_chain != CHAIN_ID_SOLANA {
+    // Fail if the emitter is not the known governance key, or the emitting chain is not the
+    // governance chain recorded in the config.
+    if vaa.message.meta().emitter_address != config.governance_emitter
+        || vaa.message.meta().emitter_chain != config.governance_chain
+    {
         Err(InvalidGovernanceKey.into())
     } else {
         Ok(())
     }
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct UpgradeContract<'b> {
     /// Payer for account creation (vaa-claim)
     pub payer: Mut<Signer<Info<'b>>>,
@@ -81,12 +84,18 @@ pub struct UpgradeContract<'b> {
     pub clock: Sysvar<'b, Clock>,
     pub bpf_loader: Info<'b>,
     pub system: Info<'b>,
+
+    /// NFT bridge config, holding the governance emitter
+    pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+
+    /// Governance of legacy configs that the config account has no room for
+    pub config_extension: ConfigExtensionAccount<'b, { AccountState::MaybeInitialized }>,
 }
 
 impl<'b> InstructionContext<'b> for UpgradeContract<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct UpgradeContractData {}
 
 pub fn upgrade_contract(
@@ -94,8 +103,7 @@ pub fn upgrade_contract(
     accs: &mut UpgradeContract,
     _data: UpgradeContractData,
 ) -> Result<()> {
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(&ctx.program_id)?;
+    verify_governance(&accs.config.current(&accs.config_extension), &accs.vaa)?;
 
     accs.vaa.claim(ctx, accs.payer.key)?;
 
@@ -116,11 +124,16 @@ pub fn upgrade_contract(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct RegisterChain<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
+    pub config_extension: ConfigExtensionAccount<'b, { AccountState::MaybeInitialized }>,
 
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.chain,
+        emitter_address: vaa.endpoint_address,
+    })]
     pub endpoint: Mut<Endpoint<'b, { AccountState::Uninitialized }>>,
 
     pub vaa: ClaimableVAA<'b, PayloadGovernanceRegisterChain>,
@@ -138,7 +151,7 @@ impl<'a> From<&RegisterChain<'a>> for EndpointDerivationData {
 impl<'b> InstructionContext<'b> for RegisterChain<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct RegisterChainData {}
 
 pub fn register_chain(
@@ -146,13 +159,8 @@ pub fn register_chain(
     accs: &mut RegisterChain,
     _data: RegisterChainData,
 ) -> Result<()> {
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.endpoint
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Claim VAA
-    verify_governance(&accs.vaa)?;
-    accs.vaa.verify(&ctx.program_id)?;
+    verify_governance(&accs.config.current(&accs.config_extension), &accs.vaa)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     if accs.vaa.chain == CHAIN_ID_SOLANA {
@@ -168,3 +176,42 @@ pub fn register_chain(
 
     Ok(())
 }
+
+#[derive(FromAccounts, ToInstruction)]
+pub struct SetGovernanceEmitter<'b> {
+    pub payer: Mut<Signer<AccountInfo<'b>>>,
+    pub config: Mut<ConfigAccount<'b, { AccountState::Initialized }>>,
+    pub config_extension: Mut<ConfigExtensionAccount<'b, { AccountState::MaybeInitialized }>>,
+
+    pub vaa: ClaimableVAA<'b, GovernancePayloadSetGovernanceEmitter>,
+}
+
+impl<'b> InstructionContext<'b> for SetGovernanceEmitter<'b> {
+}
+
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct SetGovernanceEmitterData {}
+
+pub fn set_governance_emitter(
+    ctx: &ExecutionContext,
+    accs: &mut SetGovernanceEmitter,
+    _data: SetGovernanceEmitterData,
+) -> Result<()> {
+    verify_governance(&accs.config.current(&accs.config_extension), &accs.vaa)?;
+    accs.vaa.claim(ctx, accs.payer.key)?;
+
+    // Legacy config accounts have no room for the governance, it goes to the extension instead.
+    if accs.config.info().data_len() >= accs.config.size() {
+        accs.config.governance_chain = accs.vaa.governance_chain;
+        accs.config.governance_emitter = accs.vaa.governance_emitter;
+        return Ok(());
+    }
+
+    accs.config_extension.governance_chain = accs.vaa.governance_chain;
+    accs.config_extension.governance_emitter = accs.vaa.governance_emitter;
+    if !accs.config_extension.is_initialized() {
+        accs.config_extension.create(ctx, accs.payer.key, Exempt)?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::accounts::ConfigAccount;
+use crate::{
+    accounts::ConfigAccount,
+    types::*,
+};
 use solana_program::{
     account_info::AccountInfo,
     pubkey::Pubkey,
@@ -8,16 +11,18 @@ use solitaire::{
     *,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct Initialize<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
 
     pub config: Mut<ConfigAccount<'b, { AccountState::Uninitialized }>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct InitializeData {
     pub bridge: Pubkey,
+    pub governance_chain: ChainID,
+    pub governance_emitter: Address,
 }
 
 impl<'b> InstructionContext<'b> for Initialize<'b> {
@@ -31,5 +36,7 @@ pub fn initialize(
     // Create the config account
     accs.config.create(ctx, accs.payer.key, Exempt)?;
     accs.config.wormhole_bridge = data.bridge;
+    accs.config.governance_chain = data.governance_chain;
+    accs.config.governance_emitter = data.governance_emitter;
     Ok(())
 }
@@ -40,7 +40,6 @@ use solana_program::{
         invoke_signed,
     },
     program_option::COption,
-    sysvar::clock::Clock,
 };
 use solitaire::{
     processors::seeded::{
@@ -52,7 +51,7 @@ use solitaire::{
 };
 use spl_token_metadata::state::Metadata;
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct TransferNative<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
 
@@ -64,6 +63,7 @@ pub struct TransferNative<'b> {
     /// SPL Metadata for the associated Mint
     pub spl_metadata: SplTokenMeta<'b>,
 
+    #[seeds(&CustodyAccountDerivationData { mint: *mint.info().key })]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -86,8 +86,6 @@ pub struct TransferNative<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
@@ -109,7 +107,7 @@ impl<'a> From<&TransferNative<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub target_address: Address,
@@ -121,11 +119,6 @@ pub fn transfer_native(
     accs: &mut TransferNative,
     data: TransferNativeData,
 ) -> Result<()> {
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     let derivation_data: SplTokenMetaDerivationData = (&*accs).into();
     accs.spl_metadata
         .verify_derivation(&spl_token_metadata::id(), &derivation_data)?;
@@ -219,7 +212,6 @@ pub fn transfer_native(
             AccountMeta::new(*accs.sequence.key, false),
             AccountMeta::new(*accs.payer.key, true),
             AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
@@ -229,7 +221,7 @@ pub fn transfer_native(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
@@ -237,6 +229,7 @@ pub struct TransferWrapped<'b> {
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(&WrappedMetaDerivationData { mint_key: *mint.info().key })]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
     /// SPL Metadata for the associated Mint
     pub spl_metadata: SplTokenMeta<'b>,
@@ -257,16 +250,6 @@ pub struct TransferWrapped<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
-}
-
-impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
-    fn from(accs: &TransferWrapped<'a>) -> Self {
-        WrappedMetaDerivationData {
-            mint_key: *accs.mint.info().key,
-        }
-    }
 }
 
 impl<'a> From<&TransferWrapped<'a>> for SplTokenMetaDerivationData {
@@ -280,7 +263,7 @@ impl<'a> From<&TransferWrapped<'a>> for SplTokenMetaDerivationData {
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub target_address: Address,
@@ -302,11 +285,6 @@ pub fn transfer_wrapped(
         return Err(TokenBridgeError::InvalidMint.into());
     }
 
-    // Verify that meta is correct
-    let derivation_data: WrappedMetaDerivationData = (&*accs).into();
-    accs.wrapped_meta
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Burn tokens
     let burn_ix = spl_token::instruction::burn(
         &spl_token::id(),
@@ -327,11 +305,6 @@ pub fn transfer_wrapped(
 
     invoke(&transfer_ix, ctx.accounts)?;
 
-    // Enfoce wrapped meta to be uninitialized.
-    let derivation_data: WrappedMetaDerivationData = (&*accs).into();
-    accs.wrapped_meta
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Token must have metadata
     if accs.spl_metadata.data_is_empty() {
         return Err(TokenNotNFT.into());
@@ -378,7 +351,6 @@ pub fn transfer_wrapped(
             AccountMeta::new(*accs.sequence.key, false),
             AccountMeta::new(*accs.payer.key, true),
             AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
@@ -2,6 +2,7 @@ use crate::{
     accounts::{
         AuthoritySigner,
         ConfigAccount,
+        ConfigExtensionAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
         CustodySigner,
@@ -21,7 +22,9 @@ use crate::{
             CompleteNativeData,
             CompleteWrappedData,
         },
+        InitializeData,
         RegisterChainData,
+        SetGovernanceEmitterData,
         TransferNativeData,
         TransferWrappedData,
         UpgradeContractData,
@@ -55,7 +58,6 @@ use bridge::{
     },
     PostVAA,
     PostVAAData,
-    CHAIN_ID_SOLANA,
 };
 use primitive_types::U256;
 use solana_program::{
@@ -76,6 +78,8 @@ pub fn initialize(
     program_id: Pubkey,
     payer: Pubkey,
     bridge: Pubkey,
+    governance_chain: u16,
+    governance_emitter: ForeignAddress,
 ) -> solitaire::Result<Instruction> {
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     Ok(Instruction {
@@ -87,7 +91,15 @@ pub fn initialize(
             AccountMeta::new(solana_program::sysvar::rent::id(), false),
             AccountMeta::new(solana_program::system_program::id(), false),
         ],
-        data: (crate::instruction::Instruction::Initialize, bridge).try_to_vec()?,
+        data: (
+            crate::instruction::Instruction::Initialize,
+            InitializeData {
+                bridge,
+                governance_chain,
+                governance_emitter,
+            },
+        )
+            .try_to_vec()?,
     })
 }
 
@@ -277,6 +289,8 @@ pub fn register_chain(
     data: RegisterChainData,
 ) -> solitaire::Result<Instruction> {
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
     let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
         &EndpointDerivationData {
@@ -291,6 +305,7 @@ pub fn register_chain(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(config_extension, false),
             AccountMeta::new(endpoint, false),
             message_acc,
             claim_acc,
@@ -375,7 +390,6 @@ pub fn transfer_native(
             AccountMeta::new_readonly(emitter_key, false),
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -453,7 +467,6 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(emitter_key, false),
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -470,14 +483,18 @@ pub fn upgrade_contract(
     payer: Pubkey,
     payload_message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     new_contract: Pubkey,
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
+    let config_key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
+            emitter_chain,
             sequence,
         },
         &program_id,
@@ -506,6 +523,8 @@ pub fn upgrade_contract(
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(config_extension, false),
         ],
 
         data: (
@@ -516,3 +535,45 @@ pub fn upgrade_contract(
             .unwrap(),
     }
 }
+
+pub fn set_governance_emitter(
+    program_id: Pubkey,
+    payer: Pubkey,
+    payload_message: Pubkey,
+    emitter: Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let config_key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key, false),
+            AccountMeta::new(config_extension, false),
+            AccountMeta::new_readonly(payload_message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGovernanceEmitter,
+            SetGovernanceEmitterData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
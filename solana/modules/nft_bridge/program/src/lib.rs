@@ -25,6 +25,7 @@ pub use api::{
     complete_wrapped_meta,
     initialize,
     register_chain,
+    set_governance_emitter,
     transfer_native,
     transfer_wrapped,
     upgrade_contract,
@@ -38,6 +39,8 @@ pub use api::{
     InitializeData,
     RegisterChain,
     RegisterChainData,
+    SetGovernanceEmitter,
+    SetGovernanceEmitterData,
     TransferNative,
     TransferNativeData,
     TransferWrapped,
@@ -48,24 +51,20 @@ pub use api::{
 
 use solitaire::*;
 
-pub enum TokenBridgeError {
-    AlreadyExecuted,
-    InvalidChain,
-    InvalidGovernanceKey,
-    InvalidMetadata,
-    InvalidMint,
-    InvalidPayload,
-    InvalidUTF8String,
-    TokenNotNative,
-    UninitializedMint,
-    WrongAccountOwner,
-    TokenNotNFT,
-    InvalidAssociatedAccount,
-}
-
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+custom_error! {
+    pub enum TokenBridgeError {
+        AlreadyExecuted,
+        InvalidChain,
+        InvalidGovernanceKey,
+        InvalidMetadata,
+        InvalidMint,
+        InvalidPayload,
+        InvalidUTF8String,
+        TokenNotNative,
+        UninitializedMint,
+        WrongAccountOwner,
+        TokenNotNFT,
+        InvalidAssociatedAccount,
     }
 }
 
@@ -78,4 +77,5 @@ solitaire! {
     TransferNative(TransferNativeData) => transfer_native,
     RegisterChain(RegisterChainData) => register_chain,
     UpgradeContract(UpgradeContractData) => upgrade_contract,
+    SetGovernanceEmitter(SetGovernanceEmitterData) => set_governance_emitter,
 }
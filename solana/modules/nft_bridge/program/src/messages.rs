@@ -1,6 +1,9 @@
-use crate::types::{
-    Address,
-    ChainID,
+use crate::{
+    types::{
+        Address,
+        ChainID,
+    },
+    TokenBridgeError,
 };
 use bridge::{
     vaa::{
@@ -55,7 +58,7 @@ impl DeserializePayload for PayloadTransfer {
         let mut v = Cursor::new(buf);
 
         if v.read_u8()? != 1 {
-            return Err(SolitaireError::Custom(0));
+            return Err(TokenBridgeError::InvalidPayload.into());
         };
 
         let mut token_address = Address::default();
@@ -242,9 +245,58 @@ impl SerializeGovernancePayload for GovernancePayloadUpgrade {
 impl DeserializeGovernancePayload for GovernancePayloadUpgrade {
 }
 
+#[derive(PartialEq, Debug)]
+pub struct GovernancePayloadSetGovernanceEmitter {
+    // Chain ID of the new governance emitter
+    pub governance_chain: ChainID,
+    // Address of the new governance emitter
+    pub governance_emitter: Address,
+}
+
+impl SerializePayload for GovernancePayloadSetGovernanceEmitter {
+    fn serialize<W: Write>(&self, v: &mut W) -> std::result::Result<(), SolitaireError> {
+        self.write_governance_header(v)?;
+        v.write_u16::<BigEndian>(self.governance_chain)?;
+        v.write(&self.governance_emitter)?;
+        Ok(())
+    }
+}
+
+impl DeserializePayload for GovernancePayloadSetGovernanceEmitter
+where
+    Self: DeserializeGovernancePayload,
+{
+    fn deserialize(buf: &mut &[u8]) -> Result<Self, SolitaireError> {
+        let mut c = Cursor::new(buf);
+        Self::check_governance_header(&mut c)?;
+
+        let governance_chain = c.read_u16::<BigEndian>()?;
+        let mut governance_emitter = Address::default();
+        c.read_exact(&mut governance_emitter)?;
+
+        if c.position() != c.into_inner().len() as u64 {
+            return Err(InvalidAccountData.into());
+        }
+
+        Ok(GovernancePayloadSetGovernanceEmitter {
+            governance_chain,
+            governance_emitter,
+        })
+    }
+}
+
+impl SerializeGovernancePayload for GovernancePayloadSetGovernanceEmitter {
+    const MODULE: &'static str = MODULE;
+    const ACTION: u8 = 3;
+}
+
+impl DeserializeGovernancePayload for GovernancePayloadSetGovernanceEmitter {
+}
+
 #[cfg(feature = "no-entrypoint")]
 mod tests {
     use crate::messages::{
+        GovernancePayloadSetGovernanceEmitter,
         GovernancePayloadUpgrade,
         PayloadGovernanceRegisterChain,
         PayloadTransfer,
@@ -308,4 +360,21 @@ mod tests {
 
         assert_eq!(original, deser);
     }
+
+    #[test]
+    pub fn test_serde_gov_set_governance_emitter() {
+        let mut governance_emitter = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut governance_emitter);
+
+        let original = GovernancePayloadSetGovernanceEmitter {
+            governance_chain: 2,
+            governance_emitter,
+        };
+
+        let mut data = original.try_to_vec().unwrap();
+        let deser =
+            GovernancePayloadSetGovernanceEmitter::deserialize(&mut data.as_slice()).unwrap();
+
+        assert_eq!(original, deser);
+    }
 }
@@ -1,3 +1,4 @@
+use bridge::CHAIN_ID_SOLANA;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -13,6 +14,17 @@ use solitaire::{
         AccountOwner,
         Owned,
     },
+    AccountState,
+    Data,
+    Discriminated,
+    Migrate,
+};
+use std::{
+    io::{
+        Error,
+        ErrorKind,
+    },
+    str::FromStr,
 };
 use spl_token::state::{
     Account,
@@ -22,9 +34,34 @@ use spl_token::state::{
 pub type Address = [u8; 32];
 pub type ChainID = u16;
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+/// Tags of the account types of the bridge. Data starting with one of them is never read as a
+/// legacy account of another type.
+pub const ACCOUNT_TAGS: &[&[u8]] = &[
+    Config::DISCRIMINATOR,
+    ConfigExtension::DISCRIMINATOR,
+    EndpointRegistration::DISCRIMINATOR,
+    WrappedMeta::DISCRIMINATOR,
+];
+
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+#[discriminator(legacy(ACCOUNT_TAGS))]
 pub struct Config {
     pub wormhole_bridge: Pubkey,
+
+    /// Chain the governance emitter lives on.
+    pub governance_chain: ChainID,
+
+    /// Address of the emitter that is allowed to issue governance VAAs.
+    pub governance_emitter: Address,
 }
 
 impl Owned for Config {
@@ -33,7 +70,109 @@ impl Owned for Config {
     }
 }
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+impl Config {
+    /// The config, with the governance of a legacy config that its account has no room for read
+    /// from its extension.
+    pub fn current(
+        &self,
+        extension: &Data<'_, ConfigExtension, { AccountState::MaybeInitialized }>,
+    ) -> Config {
+        let mut config = *self;
+        if extension.is_initialized() {
+            config.governance_chain = extension.governance_chain;
+            config.governance_emitter = extension.governance_emitter;
+        }
+        config
+    }
+}
+
+/// Governance of a legacy config that its account has no room for. The account is created the
+/// first time governance rotates the emitter, until then the one the program was built with
+/// applies.
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+pub struct ConfigExtension {
+    /// Chain the governance emitter lives on.
+    pub governance_chain: ChainID,
+
+    /// Address of the emitter that is allowed to issue governance VAAs.
+    pub governance_emitter: Address,
+}
+
+impl Owned for ConfigExtension {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+/// Layout of configs deployed before governance was stored on-chain. Their accounts are sized for
+/// it and can't grow.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct ConfigV0 {
+    wormhole_bridge: Pubkey,
+}
+
+/// Governance of configs deployed before it was stored on-chain, which the program was built with.
+/// Upgrading such a config requires building with `EMITTER_ADDRESS` set to its emitter.
+fn legacy_governance_emitter() -> std::io::Result<Address> {
+    option_env!("EMITTER_ADDRESS")
+        .and_then(|emitter| Pubkey::from_str(emitter).ok())
+        .map(|emitter| emitter.to_bytes())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "no legacy governance emitter"))
+}
+
+impl Migrate for Config {
+    const VERSION: u8 = 1;
+
+    /// Legacy configs were governed from Solana by the emitter they were built with.
+    fn migrate(version: u8, data: &mut &[u8]) -> std::io::Result<Self> {
+        match version {
+            0 => {
+                let v0 = ConfigV0::deserialize(data)?;
+                Ok(Config {
+                    wormhole_bridge: v0.wormhole_bridge,
+                    governance_chain: CHAIN_ID_SOLANA,
+                    governance_emitter: legacy_governance_emitter()?,
+                })
+            }
+            _ => Err(ErrorKind::InvalidData.into()),
+        }
+    }
+
+    fn downgrade(&self) -> Option<Vec<u8>> {
+        let migrated = self.governance_chain == CHAIN_ID_SOLANA
+            && legacy_governance_emitter().ok() == Some(self.governance_emitter);
+        if !migrated {
+            return None;
+        }
+
+        ConfigV0 {
+            wormhole_bridge: self.wormhole_bridge,
+        }
+        .try_to_vec()
+        .ok()
+    }
+}
+
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+#[discriminator(legacy(ACCOUNT_TAGS))]
 pub struct EndpointRegistration {
     pub chain: ChainID,
     pub contract: Address,
@@ -45,7 +184,17 @@ impl Owned for EndpointRegistration {
     }
 }
 
-#[derive(Default, Clone, Copy, BorshDeserialize, BorshSerialize, Serialize, Deserialize)]
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    Serialize,
+    Deserialize,
+    Discriminated,
+)]
+#[discriminator(legacy(ACCOUNT_TAGS))]
 pub struct WrappedMeta {
     pub chain: ChainID,
     pub token_address: Address,
@@ -58,5 +207,10 @@ impl Owned for WrappedMeta {
     }
 }
 
+/// Still the layout of the wrapped metas deployed before it was versioned.
+impl Migrate for WrappedMeta {
+    const VERSION: u8 = 0;
+}
+
 pack_type!(SplMint, Mint, AccountOwner::Other(spl_token::id()));
 pack_type!(SplAccount, Account, AccountOwner::Other(spl_token::id()));
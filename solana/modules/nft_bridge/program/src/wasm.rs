@@ -14,6 +14,7 @@ use crate::{
         complete_wrapped,
         complete_wrapped_meta,
         register_chain,
+        set_governance_emitter,
         transfer_native,
         transfer_wrapped,
         upgrade_contract,
@@ -38,6 +39,7 @@ use borsh::BorshDeserialize;
 use bridge::{
     accounts::PostedVAADerivationData,
     instructions::hash_vaa,
+    types::Trailing,
     vaa::VAA,
     DeserializePayload,
     PostVAAData,
@@ -45,8 +47,10 @@ use bridge::{
 use primitive_types::U256;
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
 };
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
@@ -170,6 +174,7 @@ pub fn complete_transfer_native_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let mut mint_bytes = [0u8; 32];
@@ -219,6 +224,7 @@ pub fn complete_transfer_wrapped_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let ix = complete_wrapped(
@@ -264,6 +270,7 @@ pub fn complete_transfer_wrapped_meta_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let ix = complete_wrapped_meta(
@@ -304,6 +311,7 @@ pub fn upgrade_contract_ix(
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         payload.new_contract,
         spill,
         vaa.sequence,
@@ -311,6 +319,33 @@ pub fn upgrade_contract_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_governance_emitter_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let ix = set_governance_emitter(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn register_chain_ix(
     program_id: String,
@@ -339,6 +374,7 @@ pub fn register_chain_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
     let ix = register_chain(
         program_id,
@@ -420,10 +456,11 @@ pub fn spl_meta_address(mint_address: Vec<u8>) -> Vec<u8> {
 
 #[wasm_bindgen]
 pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&WrappedMeta::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&from_account_data::<Versioned<WrappedMeta>>(data.as_slice()).unwrap().0).unwrap()
 }
 
 #[wasm_bindgen]
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    let registration: EndpointRegistration = from_account_data(data.as_slice()).unwrap();
+    JsValue::from_serde(&registration).unwrap()
 }
@@ -71,8 +71,10 @@ use token_bridge::{
 };
 
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Discriminated,
 };
 
 pub use helpers::*;
@@ -103,6 +105,7 @@ mod helpers {
     use bridge::types::{
         ConsistencyLevel,
         PostedVAAData,
+        Trailing,
     };
     use token_bridge::{
         CompleteNativeData,
@@ -168,14 +171,14 @@ mod helpers {
     }
 
     /// Fetch account data, the loop is there to re-attempt until data is available.
-    pub fn get_account_data<T: BorshDeserialize>(
+    pub fn get_account_data<T: BorshDeserialize + BorshSerialize + Discriminated>(
         client: &RpcClient,
         account: &Pubkey,
     ) -> Option<T> {
         let account = client
             .get_account_with_commitment(account, CommitmentConfig::processed())
             .unwrap();
-        T::try_from_slice(&account.value.unwrap().data).ok()
+        from_account_data(&account.value.unwrap().data).ok()
     }
 
     pub fn initialize_bridge(
@@ -194,6 +197,9 @@ mod helpers {
                 50,
                 2_000_000_000,
                 initial_guardians,
+                1,
+                [0u8; 32],
+                bridge::MIN_MESSAGE_RETENTION_SLOTS,
             )
             .unwrap()],
             CommitmentConfig::processed(),
@@ -220,9 +226,17 @@ mod helpers {
         program: &Pubkey,
         payer: &Keypair,
         bridge: &Pubkey,
+        governance_chain: u16,
+        governance_emitter: [u8; 32],
     ) -> Result<Signature, ClientError> {
-        let instruction = instructions::initialize(*program, payer.pubkey(), *bridge)
-            .expect("Could not create Initialize instruction");
+        let instruction = instructions::initialize(
+            *program,
+            payer.pubkey(),
+            *bridge,
+            governance_chain,
+            governance_emitter,
+        )
+        .expect("Could not create Initialize instruction");
 
         for account in instruction.accounts.iter().enumerate() {
             println!("{}: {}", account.0, account.1.pubkey);
@@ -660,6 +674,7 @@ mod helpers {
             emitter_address: emitter,
             sequence,
             payload: data,
+            guardian_set_bump: Trailing(None),
             timestamp: SystemTime::now()
                 .duration_since(SystemTime::UNIX_EPOCH)
                 .unwrap()
@@ -42,6 +42,7 @@ use solana_sdk::{
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
 };
 use spl_token::state::Mint;
 use std::{
@@ -622,10 +623,21 @@ fn test_initialize(context: &mut Context) {
         ..
     } = context;
 
-    common::initialize(client, token_bridge, payer, &bridge).unwrap();
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    common::initialize(
+        client,
+        token_bridge,
+        payer,
+        &bridge,
+        1,
+        governance.pubkey().to_bytes(),
+    )
+    .unwrap();
 
     // Verify Token Bridge State
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &token_bridge);
-    let config: Config = common::get_account_data(client, &config_key).unwrap();
+    let config: Versioned<Config> = common::get_account_data(client, &config_key).unwrap();
     assert_eq!(config.wormhole_bridge, *bridge);
+    assert_eq!(config.governance_chain, 1);
+    assert_eq!(config.governance_emitter, governance.pubkey().to_bytes());
 }
@@ -69,15 +69,27 @@ struct Config {
 type Error = Box<dyn std::error::Error>;
 type CommmandResult = Result<Option<Transaction>, Error>;
 
-fn command_init_bridge(config: &Config, bridge: &Pubkey, core_bridge: &Pubkey) -> CommmandResult {
+fn command_init_bridge(
+    config: &Config,
+    bridge: &Pubkey,
+    core_bridge: &Pubkey,
+    governance_chain: u16,
+    governance_emitter: [u8; 32],
+) -> CommmandResult {
     println!("Initializing Token bridge {}", bridge);
 
     let minimum_balance_for_rent_exemption = config
         .rpc_client
         .get_minimum_balance_for_rent_exemption(size_of::<token_bridge::types::Config>())?;
 
-    let ix = token_bridge::instructions::initialize(*bridge, config.owner.pubkey(), *core_bridge)
-        .unwrap();
+    let ix = token_bridge::instructions::initialize(
+        *bridge,
+        config.owner.pubkey(),
+        *core_bridge,
+        governance_chain,
+        governance_emitter,
+    )
+    .unwrap();
     println!("config account: {}, ", ix.accounts[1].pubkey.to_string());
     let mut transaction = Transaction::new_with_payer(&[ix], Some(&config.fee_payer.pubkey()));
 
@@ -203,6 +215,24 @@ fn main() {
                         .index(2)
                         .required(true)
                         .help("Address of the Wormhole core bridge program"),
+                )
+                .arg(
+                    Arg::with_name("governance_chain")
+                        .validator(is_u16)
+                        .value_name("GOVERNANCE_CHAIN")
+                        .takes_value(true)
+                        .index(3)
+                        .required(true)
+                        .help("Chain ID of the governance emitter"),
+                )
+                .arg(
+                    Arg::with_name("governance_emitter")
+                        .validator(is_hex)
+                        .value_name("GOVERNANCE_EMITTER")
+                        .takes_value(true)
+                        .index(4)
+                        .required(true)
+                        .help("Address of the governance emitter, 32 bytes hex encoded"),
                 ),
         )
         .subcommand(
@@ -307,8 +337,19 @@ fn main() {
         ("create-bridge", Some(arg_matches)) => {
             let bridge = pubkey_of(arg_matches, "bridge").unwrap();
             let core_bridge = pubkey_of(arg_matches, "core-bridge").unwrap();
+            let governance_chain: u16 = value_of(arg_matches, "governance_chain").unwrap();
+            let governance_str: String = value_of(arg_matches, "governance_emitter").unwrap();
+            let governance_data = hex::decode(governance_str).unwrap();
 
-            command_init_bridge(&config, &bridge, &core_bridge)
+            let mut governance_emitter = [0u8; 32];
+            governance_emitter.copy_from_slice(&governance_data);
+            command_init_bridge(
+                &config,
+                &bridge,
+                &core_bridge,
+                governance_chain,
+                governance_emitter,
+            )
         }
         ("create-meta", Some(arg_matches)) => {
             let mint = pubkey_of(arg_matches, "mint").unwrap();
@@ -389,6 +430,20 @@ where
     }
 }
 
+pub fn is_u16<T>(amount: T) -> Result<(), String>
+where
+    T: AsRef<str> + Display,
+{
+    if amount.as_ref().parse::<u16>().is_ok() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Unable to parse input amount as integer, provided: {}",
+            amount
+        ))
+    }
+}
+
 pub fn is_u32<T>(amount: T) -> Result<(), String>
 where
     T: AsRef<str> + Display,
@@ -17,12 +17,16 @@ pub type AuthoritySigner<'b> = Derive<Info<'b>, "authority_signer">;
 pub type CustodySigner<'b> = Derive<Info<'b>, "custody_signer">;
 pub type MintSigner<'b> = Derive<Info<'b>, "mint_signer">;
 
-pub type CoreBridge<'a, const State: AccountState> = Data<'a, BridgeData, { State }>;
+pub type CoreBridge<'a, const State: AccountState> = Data<'a, Versioned<BridgeData>, { State }>;
 
 pub type EmitterAccount<'b> = Derive<Info<'b>, "emitter">;
 
 pub type ConfigAccount<'b, const State: AccountState> =
-    Derive<Data<'b, Config, { State }>, "config">;
+    Derive<Data<'b, Versioned<Config>, { State }>, "config">;
+
+/// Governance of legacy configs, see `ConfigExtension`.
+pub type ConfigExtensionAccount<'b, const State: AccountState> =
+    Derive<Data<'b, ConfigExtension, { State }>, "config_extension">;
 
 pub type CustodyAccount<'b, const State: AccountState> = Data<'b, SplAccount, { State }>;
 
@@ -55,7 +59,7 @@ impl<'b, const State: AccountState> Seeded<&WrappedDerivationData> for WrappedMi
     }
 }
 
-pub type WrappedTokenMeta<'b, const State: AccountState> = Data<'b, WrappedMeta, { State }>;
+pub type WrappedTokenMeta<'b, const State: AccountState> = Data<'b, Versioned<WrappedMeta>, { State }>;
 
 pub struct WrappedMetaDerivationData {
     pub mint_key: Pubkey,
@@ -41,7 +41,6 @@ use solana_program::{
     },
     program_error::ProgramError,
     pubkey::Pubkey,
-    sysvar::clock::Clock,
 };
 use solitaire::{
     processors::seeded::{
@@ -65,7 +64,7 @@ use std::ops::{
     DerefMut,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct AttestToken<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
 
@@ -73,10 +72,11 @@ pub struct AttestToken<'b> {
 
     /// Mint to attest
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
+    #[seeds(&WrappedMetaDerivationData { mint_key: *mint.info().key })]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Uninitialized }>,
 
-    /// SPL Metadata for the associated Mint
-    pub spl_metadata: SplTokenMeta<'b>,
+    /// SPL Metadata for the associated Mint, if there is any
+    pub spl_metadata: Optional<SplTokenMeta<'b>>,
 
     /// CPI Context
     pub bridge: Mut<CoreBridge<'b, { AccountState::Initialized }>>,
@@ -92,21 +92,11 @@ pub struct AttestToken<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'b> InstructionContext<'b> for AttestToken<'b> {
 }
 
-impl<'a> From<&AttestToken<'a>> for WrappedMetaDerivationData {
-    fn from(accs: &AttestToken<'a>) -> Self {
-        WrappedMetaDerivationData {
-            mint_key: *accs.mint.info().key,
-        }
-    }
-}
-
 impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     fn from(accs: &AttestToken<'a>) -> Self {
         SplTokenMetaDerivationData {
@@ -115,7 +105,7 @@ impl<'a> From<&AttestToken<'a>> for SplTokenMetaDerivationData {
     }
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct AttestTokenData {
     pub nonce: u32,
 }
@@ -134,11 +124,6 @@ pub fn attest_token(
 
     invoke(&transfer_ix, ctx.accounts)?;
 
-    // Enfoce wrapped meta to be uninitialized.
-    let derivation_data: WrappedMetaDerivationData = (&*accs).into();
-    accs.wrapped_meta
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Create Asset Metadata
     let mut payload = PayloadAssetMeta {
         token_address: accs.mint.info().key.to_bytes(),
@@ -148,18 +133,19 @@ pub fn attest_token(
         name: "".to_string(),
     };
 
-    // Assign metadata if an SPL Metadata account exists for the SPL token in question.
-    if !accs.spl_metadata.data_is_empty() {
+    // Assign metadata if an SPL Metadata account exists for the SPL token in question. Clients
+    // that don't know whether it exists may pass its address anyway.
+    let spl_metadata = accs.spl_metadata.as_ref().filter(|m| !m.data_is_empty());
+    if let Some(spl_metadata) = spl_metadata {
         let derivation_data: SplTokenMetaDerivationData = (&*accs).into();
-        accs.spl_metadata
-            .verify_derivation(&spl_token_metadata::id(), &derivation_data)?;
+        spl_metadata.verify_derivation(&spl_token_metadata::id(), &derivation_data)?;
 
-        if *accs.spl_metadata.owner != spl_token_metadata::id() {
+        if *spl_metadata.owner != spl_token_metadata::id() {
             return Err(WrongAccountOwner.into());
         }
 
         let metadata: Metadata =
-            Metadata::from_account_info(accs.spl_metadata.info()).ok_or(InvalidMetadata)?;
+            Metadata::from_account_info(spl_metadata.info()).ok_or(InvalidMetadata)?;
         payload.name = metadata.data.name.clone();
         payload.symbol = metadata.data.symbol.clone();
     }
@@ -183,7 +169,6 @@ pub fn attest_token(
             AccountMeta::new(*accs.sequence.key, false),
             AccountMeta::new(*accs.payer.key, true),
             AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
@@ -17,6 +17,7 @@ use crate::{
     TokenBridgeError::*,
 };
 use bridge::{
+    types::Trailing,
     vaa::ClaimableVAA,
     CHAIN_ID_SOLANA,
 };
@@ -27,10 +28,7 @@ use solana_program::{
     pubkey::Pubkey,
 };
 use solitaire::{
-    processors::seeded::{
-        invoke_seeded,
-        Seeded,
-    },
+    processors::seeded::invoke_seeded,
     CreationLamports::Exempt,
     *,
 };
@@ -43,74 +41,53 @@ use std::ops::{
     DerefMut,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
+#[instruction(CompleteNativeData)]
 pub struct CompleteNative<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
+    #[seeds(
+        &EndpointDerivationData {
+            emitter_chain: vaa.meta().emitter_chain,
+            emitter_address: vaa.meta().emitter_address,
+        },
+        bump = data.chain_registration_bump.0
+    )]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
+    #[has_one(mint)]
     pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[has_one(mint)]
     pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
+    #[seeds(
+        &CustodyAccountDerivationData { mint: *mint.info().key },
+        bump = data.custody_bump.0
+    )]
+    #[has_one(mint, owner = custody_signer)]
     pub custody: Mut<CustodyAccount<'b, { AccountState::Initialized }>>,
     pub mint: Data<'b, SplMint, { AccountState::Initialized }>,
 
     pub custody_signer: CustodySigner<'b>,
 }
 
-impl<'a> From<&CompleteNative<'a>> for EndpointDerivationData {
-    fn from(accs: &CompleteNative<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
-impl<'a> From<&CompleteNative<'a>> for CustodyAccountDerivationData {
-    fn from(accs: &CompleteNative<'a>) -> Self {
-        CustodyAccountDerivationData {
-            mint: *accs.mint.info().key,
-        }
-    }
-}
-
 impl<'b> InstructionContext<'b> for CompleteNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
-pub struct CompleteNativeData {}
+/// Bumps of the derivations the program checks, which spare it searching for them. Clients that
+/// predate them send no data.
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
+pub struct CompleteNativeData {
+    pub chain_registration_bump: Trailing<u8>,
+    pub custody_bump: Trailing<u8>,
+}
 
 pub fn complete_native(
     ctx: &ExecutionContext,
     accs: &mut CompleteNative,
     data: CompleteNativeData,
 ) -> Result<()> {
-    // Verify the chain registration
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    // Verify mints
-    if *accs.mint.info().key != accs.to.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.to_fees.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.mint.info().key != accs.custody.mint {
-        return Err(InvalidMint.into());
-    }
-    if *accs.custody_signer.key != accs.custody.owner {
-        return Err(WrongAccountOwner.into());
-    }
-
     // Verify VAA
     if accs.vaa.token_address != accs.mint.info().key.to_bytes() {
         return Err(InvalidMint.into());
@@ -123,7 +100,6 @@ pub fn complete_native(
     }
 
     // Prevent vaa double signing
-    accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     let mut amount = accs.vaa.amount.as_u64();
@@ -160,7 +136,7 @@ pub fn complete_native(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CompleteWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
@@ -168,25 +144,21 @@ pub struct CompleteWrapped<'b> {
     // Signed message for the transfer
     pub vaa: ClaimableVAA<'b, PayloadTransfer>,
 
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.meta().emitter_chain,
+        emitter_address: vaa.meta().emitter_address,
+    })]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
 
     pub to: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub to_fees: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(&WrappedMetaDerivationData { mint_key: *mint.info().key })]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub mint_authority: MintSigner<'b>,
 }
 
-impl<'a> From<&CompleteWrapped<'a>> for EndpointDerivationData {
-    fn from(accs: &CompleteWrapped<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
 impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
     fn from(accs: &CompleteWrapped<'a>) -> Self {
         WrappedDerivationData {
@@ -199,7 +171,7 @@ impl<'a> From<&CompleteWrapped<'a>> for WrappedDerivationData {
 impl<'b> InstructionContext<'b> for CompleteWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CompleteWrappedData {}
 
 pub fn complete_wrapped(
@@ -207,18 +179,7 @@ pub fn complete_wrapped(
     accs: &mut CompleteWrapped,
     data: CompleteWrappedData,
 ) -> Result<()> {
-    // Verify the chain registration
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Verify mint
-    accs.wrapped_meta.verify_derivation(
-        ctx.program_id,
-        &WrappedMetaDerivationData {
-            mint_key: *accs.mint.info().key,
-        },
-    )?;
     if accs.wrapped_meta.token_address != accs.vaa.token_address
         || accs.wrapped_meta.chain != accs.vaa.token_chain
     {
@@ -238,7 +199,6 @@ pub fn complete_wrapped(
         return Err(InvalidChain.into());
     }
 
-    accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     // Mint tokens
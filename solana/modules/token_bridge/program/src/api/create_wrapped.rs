@@ -55,16 +55,25 @@ use std::{
     },
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct CreateWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
 
+    #[seeds(&EndpointDerivationData {
+        emitter_chain: vaa.meta().emitter_chain,
+        emitter_address: vaa.meta().emitter_address,
+    })]
     pub chain_registration: Endpoint<'b, { AccountState::Initialized }>,
     pub vaa: ClaimableVAA<'b, PayloadAssetMeta>,
 
     // New Wrapped
+    #[seeds(&WrappedDerivationData {
+        token_chain: vaa.token_chain,
+        token_address: vaa.token_address,
+    })]
     pub mint: Mut<WrappedMint<'b, { AccountState::MaybeInitialized }>>,
+    #[seeds(&WrappedMetaDerivationData { mint_key: *mint.info().key })]
     pub meta: Mut<WrappedTokenMeta<'b, { AccountState::MaybeInitialized }>>,
 
     /// SPL Metadata for the associated Mint
@@ -73,15 +82,6 @@ pub struct CreateWrapped<'b> {
     pub mint_authority: MintSigner<'b>,
 }
 
-impl<'a> From<&CreateWrapped<'a>> for EndpointDerivationData {
-    fn from(accs: &CreateWrapped<'a>) -> Self {
-        EndpointDerivationData {
-            emitter_chain: accs.vaa.meta().emitter_chain,
-            emitter_address: accs.vaa.meta().emitter_address,
-        }
-    }
-}
-
 impl<'a> From<&CreateWrapped<'a>> for WrappedDerivationData {
     fn from(accs: &CreateWrapped<'a>) -> Self {
         WrappedDerivationData {
@@ -102,7 +102,7 @@ impl<'a> From<&CreateWrapped<'a>> for WrappedMetaDerivationData {
 impl<'b> InstructionContext<'b> for CreateWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct CreateWrappedData {}
 
 pub fn create_wrapped(
@@ -115,19 +115,6 @@ pub fn create_wrapped(
         return Err(InvalidChain.into());
     }
 
-    let derivation_data: WrappedDerivationData = (&*accs).into();
-    accs.mint
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    let meta_derivation_data: WrappedMetaDerivationData = (&*accs).into();
-    accs.meta
-        .verify_derivation(ctx.program_id, &meta_derivation_data)?;
-
-    let derivation_data: EndpointDerivationData = (&*accs).into();
-    accs.chain_registration
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
-    accs.vaa.verify(ctx.program_id)?;
     accs.vaa.claim(ctx, accs.payer.key)?;
 
     if accs.mint.is_initialized() {
@@ -17,16 +17,18 @@ use std::ops::{
     DerefMut,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct Initialize<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
 
     pub config: Mut<ConfigAccount<'b, { AccountState::Uninitialized }>>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct InitializeData {
     pub bridge: Pubkey,
+    pub governance_chain: ChainID,
+    pub governance_emitter: Address,
 }
 
 impl<'b> InstructionContext<'b> for Initialize<'b> {
@@ -40,5 +42,7 @@ pub fn initialize(
     // Create the config account
     accs.config.create(ctx, accs.payer.key, Exempt)?;
     accs.config.wormhole_bridge = data.bridge;
+    accs.config.governance_chain = data.governance_chain;
+    accs.config.governance_emitter = data.governance_emitter;
     Ok(())
 }
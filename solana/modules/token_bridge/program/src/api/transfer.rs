@@ -46,13 +46,9 @@ use solana_program::{
     program_error::ProgramError,
     program_option::COption,
     pubkey::Pubkey,
-    sysvar::clock::Clock,
 };
 use solitaire::{
-    processors::seeded::{
-        invoke_seeded,
-        Seeded,
-    },
+    processors::seeded::invoke_seeded,
     CreationLamports::Exempt,
     *,
 };
@@ -68,7 +64,7 @@ use std::ops::{
     DerefMut,
 };
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct TransferNative<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
 
@@ -78,6 +74,7 @@ pub struct TransferNative<'b> {
 
     pub mint: Mut<Data<'b, SplMint, { AccountState::Initialized }>>,
 
+    #[seeds(&CustodyAccountDerivationData { mint: *mint.info().key })]
     pub custody: Mut<CustodyAccount<'b, { AccountState::MaybeInitialized }>>,
 
     // This could allow someone to race someone else's tx if they do the approval in a separate tx.
@@ -100,8 +97,6 @@ pub struct TransferNative<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
@@ -115,7 +110,7 @@ impl<'a> From<&TransferNative<'a>> for CustodyAccountDerivationData {
 impl<'b> InstructionContext<'b> for TransferNative<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct TransferNativeData {
     pub nonce: u32,
     pub amount: u64,
@@ -134,11 +129,6 @@ pub fn transfer_native(
         return Err(InvalidChain.into());
     }
 
-    // Verify that the custody account is derived correctly
-    let derivation_data: CustodyAccountDerivationData = (&*accs).into();
-    accs.custody
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Verify mints
     if accs.from.mint != *accs.mint.info().key {
         return Err(TokenBridgeError::InvalidMint.into());
@@ -223,7 +213,6 @@ pub fn transfer_native(
             AccountMeta::new(*accs.sequence.key, false),
             AccountMeta::new(*accs.payer.key, true),
             AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
@@ -233,7 +222,7 @@ pub fn transfer_native(
     Ok(())
 }
 
-#[derive(FromAccounts)]
+#[derive(FromAccounts, ToInstruction)]
 pub struct TransferWrapped<'b> {
     pub payer: Mut<Signer<AccountInfo<'b>>>,
     pub config: ConfigAccount<'b, { AccountState::Initialized }>,
@@ -241,6 +230,7 @@ pub struct TransferWrapped<'b> {
     pub from: Mut<Data<'b, SplAccount, { AccountState::Initialized }>>,
     pub from_owner: MaybeMut<Signer<Info<'b>>>,
     pub mint: Mut<WrappedMint<'b, { AccountState::Initialized }>>,
+    #[seeds(&WrappedMetaDerivationData { mint_key: *mint.info().key })]
     pub wrapped_meta: WrappedTokenMeta<'b, { AccountState::Initialized }>,
 
     pub authority_signer: AuthoritySigner<'b>,
@@ -259,8 +249,6 @@ pub struct TransferWrapped<'b> {
 
     /// Account to collect tx fee
     pub fee_collector: Mut<Info<'b>>,
-
-    pub clock: Sysvar<'b, Clock>,
 }
 
 impl<'a> From<&TransferWrapped<'a>> for WrappedDerivationData {
@@ -272,18 +260,10 @@ impl<'a> From<&TransferWrapped<'a>> for WrappedDerivationData {
     }
 }
 
-impl<'a> From<&TransferWrapped<'a>> for WrappedMetaDerivationData {
-    fn from(accs: &TransferWrapped<'a>) -> Self {
-        WrappedMetaDerivationData {
-            mint_key: *accs.mint.info().key,
-        }
-    }
-}
-
 impl<'b> InstructionContext<'b> for TransferWrapped<'b> {
 }
 
-#[derive(BorshDeserialize, BorshSerialize, Default)]
+#[derive(BorshDeserialize, BorshSerialize, Default, IdlSchema)]
 pub struct TransferWrappedData {
     pub nonce: u32,
     pub amount: u64,
@@ -317,11 +297,6 @@ pub fn transfer_wrapped(
         return Err(InvalidFee.into());
     }
 
-    // Verify that meta is correct
-    let derivation_data: WrappedMetaDerivationData = (&*accs).into();
-    accs.wrapped_meta
-        .verify_derivation(ctx.program_id, &derivation_data)?;
-
     // Burn tokens
     let burn_ix = spl_token::instruction::burn(
         &spl_token::id(),
@@ -370,7 +345,6 @@ pub fn transfer_wrapped(
             AccountMeta::new(*accs.sequence.key, false),
             AccountMeta::new(*accs.payer.key, true),
             AccountMeta::new(*accs.fee_collector.key, false),
-            AccountMeta::new_readonly(*accs.clock.info().key, false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
             AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
         ],
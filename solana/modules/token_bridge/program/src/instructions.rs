@@ -2,6 +2,7 @@ use crate::{
     accounts::{
         AuthoritySigner,
         ConfigAccount,
+        ConfigExtensionAccount,
         CustodyAccount,
         CustodyAccountDerivationData,
         CustodySigner,
@@ -23,7 +24,9 @@ use crate::{
         },
         AttestTokenData,
         CreateWrappedData,
+        InitializeData,
         RegisterChainData,
+        SetGovernanceEmitterData,
         TransferNativeData,
         TransferWrappedData,
         UpgradeContractData,
@@ -50,6 +53,7 @@ use bridge::{
     },
     api::ForeignAddress,
     instructions::hash_vaa,
+    types::Trailing,
     vaa::{
         ClaimableVAA,
         PayloadMessage,
@@ -57,7 +61,6 @@ use bridge::{
     },
     PostVAA,
     PostVAAData,
-    CHAIN_ID_SOLANA,
 };
 use primitive_types::U256;
 use solana_program::{
@@ -78,6 +81,8 @@ pub fn initialize(
     program_id: Pubkey,
     payer: Pubkey,
     bridge: Pubkey,
+    governance_chain: u16,
+    governance_emitter: ForeignAddress,
 ) -> solitaire::Result<Instruction> {
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     Ok(Instruction {
@@ -89,7 +94,15 @@ pub fn initialize(
             AccountMeta::new(solana_program::sysvar::rent::id(), false),
             AccountMeta::new(solana_program::system_program::id(), false),
         ],
-        data: (crate::instruction::Instruction::Initialize, bridge).try_to_vec()?,
+        data: (
+            crate::instruction::Instruction::Initialize,
+            InitializeData {
+                bridge,
+                governance_chain,
+                governance_emitter,
+            },
+        )
+            .try_to_vec()?,
     })
 }
 
@@ -106,19 +119,26 @@ pub fn complete_native(
 ) -> solitaire::Result<Instruction> {
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
     let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa.clone());
-    let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
+    let (endpoint, endpoint_bump) = Endpoint::<'_, { AccountState::Initialized }>::key_and_bump(
         &EndpointDerivationData {
             emitter_chain: vaa.emitter_chain,
             emitter_address: vaa.emitter_address,
         },
         &program_id,
     );
-    let custody_key = CustodyAccount::<'_, { AccountState::Initialized }>::key(
-        &CustodyAccountDerivationData { mint },
-        &program_id,
-    );
+    let (custody_key, custody_bump) =
+        CustodyAccount::<'_, { AccountState::Initialized }>::key_and_bump(
+            &CustodyAccountDerivationData { mint },
+            &program_id,
+        );
     let custody_signer_key = CustodySigner::key(None, &program_id);
 
+    // Pass the bumps the caller left out.
+    let data = CompleteNativeData {
+        chain_registration_bump: Trailing(data.chain_registration_bump.0.or(Some(endpoint_bump))),
+        custody_bump: Trailing(data.custody_bump.0.or(Some(custody_bump))),
+    };
+
     Ok(Instruction {
         program_id,
         accounts: vec![
@@ -278,6 +298,8 @@ pub fn register_chain(
     data: RegisterChainData,
 ) -> solitaire::Result<Instruction> {
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let (message_acc, claim_acc) = claimable_vaa(program_id, message_key, vaa);
     let endpoint = Endpoint::<'_, { AccountState::Initialized }>::key(
         &EndpointDerivationData {
@@ -292,6 +314,7 @@ pub fn register_chain(
         accounts: vec![
             AccountMeta::new(payer, true),
             AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(config_extension, false),
             AccountMeta::new(endpoint, false),
             message_acc,
             claim_acc,
@@ -377,7 +400,6 @@ pub fn transfer_native(
             AccountMeta::new_readonly(emitter_key, false),
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -452,7 +474,6 @@ pub fn transfer_wrapped(
             AccountMeta::new_readonly(emitter_key, false),
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -511,7 +532,6 @@ pub fn attest(
             AccountMeta::new_readonly(emitter_key, false),
             AccountMeta::new(sequence_key, false),
             AccountMeta::new(fee_collector_key, false),
-            AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             // Dependencies
             AccountMeta::new(solana_program::sysvar::rent::id(), false),
             AccountMeta::new(solana_program::system_program::id(), false),
@@ -531,14 +551,18 @@ pub fn upgrade_contract(
     payer: Pubkey,
     payload_message: Pubkey,
     emitter: Pubkey,
+    emitter_chain: u16,
     new_contract: Pubkey,
     spill: Pubkey,
     sequence: u64,
 ) -> Instruction {
+    let config_key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
     let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
         &ClaimDerivationData {
             emitter_address: emitter.to_bytes(),
-            emitter_chain: CHAIN_ID_SOLANA,
+            emitter_chain,
             sequence,
         },
         &program_id,
@@ -567,6 +591,8 @@ pub fn upgrade_contract(
             AccountMeta::new_readonly(solana_program::sysvar::clock::id(), false),
             AccountMeta::new_readonly(solana_program::bpf_loader_upgradeable::id(), false),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(config_key, false),
+            AccountMeta::new_readonly(config_extension, false),
         ],
 
         data: (
@@ -577,3 +603,45 @@ pub fn upgrade_contract(
             .unwrap(),
     }
 }
+
+pub fn set_governance_emitter(
+    program_id: Pubkey,
+    payer: Pubkey,
+    payload_message: Pubkey,
+    emitter: Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let config_key = ConfigAccount::<'_, { AccountState::Initialized }>::key(None, &program_id);
+    let config_extension =
+        ConfigExtensionAccount::<'_, { AccountState::MaybeInitialized }>::key(None, &program_id);
+    let claim = Claim::<'_, { AccountState::Uninitialized }>::key(
+        &ClaimDerivationData {
+            emitter_address: emitter.to_bytes(),
+            emitter_chain,
+            sequence,
+        },
+        &program_id,
+    );
+
+    Instruction {
+        program_id,
+
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(config_key, false),
+            AccountMeta::new(config_extension, false),
+            AccountMeta::new_readonly(payload_message, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new_readonly(solana_program::sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+
+        data: (
+            crate::instruction::Instruction::SetGovernanceEmitter,
+            SetGovernanceEmitterData {},
+        )
+            .try_to_vec()
+            .unwrap(),
+    }
+}
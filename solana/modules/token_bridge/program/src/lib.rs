@@ -26,6 +26,7 @@ pub use api::{
     create_wrapped,
     initialize,
     register_chain,
+    set_governance_emitter,
     transfer_native,
     transfer_wrapped,
     upgrade_contract,
@@ -41,6 +42,8 @@ pub use api::{
     InitializeData,
     RegisterChain,
     RegisterChainData,
+    SetGovernanceEmitter,
+    SetGovernanceEmitterData,
     TransferNative,
     TransferNativeData,
     TransferWrapped,
@@ -52,23 +55,19 @@ pub use api::{
 use solitaire::*;
 use std::error::Error;
 
-pub enum TokenBridgeError {
-    AlreadyExecuted,
-    InvalidChain,
-    InvalidGovernanceKey,
-    InvalidMetadata,
-    InvalidMint,
-    InvalidPayload,
-    InvalidUTF8String,
-    TokenNotNative,
-    UninitializedMint,
-    WrongAccountOwner,
-    InvalidFee,
-}
-
-impl From<TokenBridgeError> for SolitaireError {
-    fn from(t: TokenBridgeError) -> SolitaireError {
-        SolitaireError::Custom(t as u64)
+custom_error! {
+    pub enum TokenBridgeError {
+        AlreadyExecuted,
+        InvalidChain,
+        InvalidGovernanceKey,
+        InvalidMetadata,
+        InvalidMint,
+        InvalidPayload,
+        InvalidUTF8String,
+        TokenNotNative,
+        UninitializedMint,
+        WrongAccountOwner,
+        InvalidFee,
     }
 }
 
@@ -82,4 +81,5 @@ solitaire! {
     RegisterChain(RegisterChainData) => register_chain,
     CreateWrapped(CreateWrappedData) => create_wrapped,
     UpgradeContract(UpgradeContractData) => upgrade_contract,
+    SetGovernanceEmitter(SetGovernanceEmitterData) => set_governance_emitter,
 }
@@ -14,6 +14,7 @@ use crate::{
         complete_wrapped,
         create_wrapped,
         register_chain,
+        set_governance_emitter,
         transfer_native,
         transfer_wrapped,
         upgrade_contract,
@@ -39,14 +40,17 @@ use borsh::BorshDeserialize;
 use bridge::{
     accounts::PostedVAADerivationData,
     instructions::hash_vaa,
+    types::Trailing,
     vaa::VAA,
     DeserializePayload,
     PostVAAData,
 };
 use solana_program::pubkey::Pubkey;
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
 };
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
@@ -194,6 +198,7 @@ pub fn complete_transfer_native_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let ix = complete_native(
@@ -209,7 +214,7 @@ pub fn complete_transfer_native_ix(
             None
         },
         Pubkey::new(&payload.token_address),
-        CompleteNativeData {},
+        CompleteNativeData::default(),
     )
     .unwrap();
 
@@ -245,6 +250,7 @@ pub fn complete_transfer_wrapped_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let ix = complete_wrapped(
@@ -295,6 +301,7 @@ pub fn create_wrapped_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
 
     let ix = create_wrapped(
@@ -335,6 +342,7 @@ pub fn upgrade_contract_ix(
         Pubkey::from_str(payer.as_str()).unwrap(),
         message_key,
         Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
         payload.new_contract,
         spill,
         vaa.sequence,
@@ -342,6 +350,33 @@ pub fn upgrade_contract_ix(
     return JsValue::from_serde(&ix).unwrap();
 }
 
+#[wasm_bindgen]
+pub fn set_governance_emitter_ix(
+    program_id: String,
+    bridge_id: String,
+    payer: String,
+    vaa: Vec<u8>,
+) -> JsValue {
+    let program_id = Pubkey::from_str(program_id.as_str()).unwrap();
+    let bridge_id = Pubkey::from_str(bridge_id.as_str()).unwrap();
+    let vaa = VAA::deserialize(vaa.as_slice()).unwrap();
+    let message_key = bridge::accounts::PostedVAA::<'_, { AccountState::Uninitialized }>::key(
+        &PostedVAADerivationData {
+            payload_hash: hash_vaa(&vaa.clone().into()).to_vec(),
+        },
+        &bridge_id,
+    );
+    let ix = set_governance_emitter(
+        program_id,
+        Pubkey::from_str(payer.as_str()).unwrap(),
+        message_key,
+        Pubkey::new(&vaa.emitter_address),
+        vaa.emitter_chain,
+        vaa.sequence,
+    );
+    return JsValue::from_serde(&ix).unwrap();
+}
+
 #[wasm_bindgen]
 pub fn register_chain_ix(
     program_id: String,
@@ -370,6 +405,7 @@ pub fn register_chain_ix(
         sequence: vaa.sequence,
         consistency_level: vaa.consistency_level,
         payload: vaa.payload,
+        guardian_set_bump: Trailing(None),
     };
     let ix = register_chain(
         program_id,
@@ -440,10 +476,11 @@ pub fn wrapped_meta_address(program_id: String, mint_address: Vec<u8>) -> Vec<u8
 
 #[wasm_bindgen]
 pub fn parse_wrapped_meta(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&WrappedMeta::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    JsValue::from_serde(&from_account_data::<Versioned<WrappedMeta>>(data.as_slice()).unwrap().0).unwrap()
 }
 
 #[wasm_bindgen]
 pub fn parse_endpoint_registration(data: Vec<u8>) -> JsValue {
-    JsValue::from_serde(&EndpointRegistration::try_from_slice(data.as_slice()).unwrap()).unwrap()
+    let registration: EndpointRegistration = from_account_data(data.as_slice()).unwrap();
+    JsValue::from_serde(&registration).unwrap()
 }
@@ -42,6 +42,11 @@ use solana_sdk::{
 use solitaire::{
     processors::seeded::Seeded,
     AccountState,
+    Versioned,
+};
+use solitaire_client::{
+    compute_units,
+    simulate,
 };
 use spl_token::state::Mint;
 use std::{
@@ -93,7 +98,11 @@ use std::{
 };
 use token_bridge::{
     accounts::{
+        CustodyAccount,
+        CustodyAccountDerivationData,
         EmitterAccount,
+        Endpoint,
+        EndpointDerivationData,
         WrappedDerivationData,
         WrappedMint,
     },
@@ -114,6 +123,14 @@ const GOVERNANCE_KEY: [u8; 64] = [
     88, 97, 199,
 ];
 
+/// Compute units `complete_native` is expected to stay under. Each derivation it checks finds its
+/// bump once and reuses it for creating and signing for the account.
+const COMPLETE_NATIVE_COMPUTE_BUDGET: u64 = 100_000;
+
+/// Compute units the runtime charges for deriving an address, which a search does for each bump it
+/// tries.
+const CREATE_PROGRAM_ADDRESS_UNITS: u64 = 1500;
+
 struct Context {
     /// Address of the core bridge contract.
     bridge: Pubkey,
@@ -484,6 +501,87 @@ fn test_transfer_native_in(context: &mut Context) -> () {
     let message_key =
         PostedVAA::<'_, { AccountState::MaybeInitialized }>::key(&msg_derivation_data, &bridge);
 
+    // Fees can only be paid into a token account of the transferred mint.
+    let other_mint = Keypair::new();
+    let other_account = Keypair::new();
+    common::create_mint(client, payer, &mint_authority.pubkey(), &other_mint).unwrap();
+    common::create_token_account(
+        client,
+        payer,
+        &other_account,
+        token_authority.pubkey(),
+        other_mint.pubkey(),
+    )
+    .unwrap();
+    let logs = simulate(
+        client,
+        payer,
+        &[payer],
+        &[token_bridge::instructions::complete_native(
+            *token_bridge,
+            *bridge,
+            payer.pubkey(),
+            message_key,
+            vaa.clone(),
+            token_account.pubkey(),
+            Some(other_account.pubkey()),
+            mint.pubkey(),
+            token_bridge::CompleteNativeData::default(),
+        )
+        .unwrap()],
+    )
+    .unwrap();
+    assert!(logs
+        .iter()
+        .any(|log| log.contains("ConstraintViolated(\"CompleteNative.to_fees\")")));
+
+    // Benchmark the transfer before executing it.
+    let (_, endpoint_bump) = Endpoint::<'_, { AccountState::Initialized }>::key_and_bump(
+        &EndpointDerivationData {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+        },
+        token_bridge,
+    );
+    let (_, custody_bump) = CustodyAccount::<'_, { AccountState::Initialized }>::key_and_bump(
+        &CustodyAccountDerivationData { mint: mint.pubkey() },
+        token_bridge,
+    );
+    let complete_native = token_bridge::instructions::complete_native(
+        *token_bridge,
+        *bridge,
+        payer.pubkey(),
+        message_key,
+        vaa.clone(),
+        token_account.pubkey(),
+        None,
+        mint.pubkey(),
+        token_bridge::CompleteNativeData::default(),
+    )
+    .unwrap();
+
+    // Clients predating the bumps leave them out, the program then searches for both.
+    let mut searching = complete_native.clone();
+    assert_eq!(
+        searching.data.split_off(1),
+        vec![1, endpoint_bump, 1, custody_bump]
+    );
+
+    let units = |ix: Instruction| {
+        let logs = simulate(client, payer, &[payer], &[ix]).unwrap();
+        compute_units(token_bridge, &logs)
+    };
+    let (searched, bumped) = (units(searching), units(complete_native));
+    assert!(bumped > 0 && bumped <= COMPLETE_NATIVE_COMPUTE_BUDGET);
+
+    // Each search tries every bump above the account's before finding it, a known bump is checked
+    // with a single derivation.
+    let skipped = u64::from(u8::MAX - endpoint_bump) + u64::from(u8::MAX - custody_bump);
+    assert!(
+        searched + 2 * CREATE_PROGRAM_ADDRESS_UNITS
+            > bumped + skipped * CREATE_PROGRAM_ADDRESS_UNITS
+    );
+
     common::complete_native(
         client,
         token_bridge,
@@ -622,10 +720,21 @@ fn test_initialize(context: &mut Context) {
         ..
     } = context;
 
-    common::initialize(client, token_bridge, payer, &bridge).unwrap();
+    let governance = Keypair::from_bytes(&GOVERNANCE_KEY).unwrap();
+    common::initialize(
+        client,
+        token_bridge,
+        payer,
+        &bridge,
+        1,
+        governance.pubkey().to_bytes(),
+    )
+    .unwrap();
 
     // Verify Token Bridge State
     let config_key = ConfigAccount::<'_, { AccountState::Uninitialized }>::key(None, &token_bridge);
-    let config: Config = common::get_account_data(client, &config_key).unwrap();
+    let config: Versioned<Config> = common::get_account_data(client, &config_key).unwrap();
     assert_eq!(config.wormhole_bridge, *bridge);
+    assert_eq!(config.governance_chain, 1);
+    assert_eq!(config.governance_emitter, governance.pubkey().to_bytes());
 }
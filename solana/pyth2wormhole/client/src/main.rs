@@ -30,16 +30,18 @@ use solana_sdk::{
 };
 use solana_transaction_status::UiTransactionEncoding;
 use solitaire::{
+    from_account_data,
     processors::seeded::Seeded,
     AccountState,
     Derive,
     Info,
+    Versioned,
 };
 use solitaire_client::{
+    decode_events,
     AccEntry,
     Keypair,
     SolSigner,
-    ToInstruction,
 };
 
 use cli::{
@@ -54,6 +56,10 @@ use bridge::{
         Sequence,
         SequenceDerivationData,
     },
+    events::{
+        Event,
+        EVENT_LOG_PREFIX,
+    },
     types::ConsistencyLevel,
     CHAIN_ID_SOLANA,
 };
@@ -70,8 +76,6 @@ use pyth2wormhole::{
 
 pub type ErrBox = Box<dyn std::error::Error>;
 
-pub const SEQNO_PREFIX: &'static str = "Program log: Sequence: ";
-
 fn main() -> Result<(), ErrBox> {
     let cli = Cli::parse();
     init_logging(cli.log_level);
@@ -127,17 +131,20 @@ fn main() -> Result<(), ErrBox> {
 
     let sig = rpc_client.send_and_confirm_transaction_with_spinner(&tx)?;
 
-    // To complete attestation, retrieve sequence number from transaction logs
+    // To complete attestation, retrieve sequence number from the bridge events
     if let Action::Attest { .. } = cli.action {
+        let p2w_config_addr =
+            P2WConfigAccount::<{ AccountState::Initialized }>::key(None, &p2w_addr);
+        let config: Versioned<Pyth2WormholeConfig> =
+            from_account_data(rpc_client.get_account_data(&p2w_config_addr)?.as_slice())?;
         let this_tx = rpc_client.get_transaction(&sig, UiTransactionEncoding::Json)?;
 
         if let Some(logs) = this_tx.transaction.meta.and_then(|meta| meta.log_messages) {
-	    for log in logs {
-		if log.starts_with(SEQNO_PREFIX) {
-		    let seqno = log.replace(SEQNO_PREFIX, "");
-		    println!("Sequence number: {}", seqno);
-		}
-	    }
+            for event in decode_events::<Event, _>(&config.wh_prog, EVENT_LOG_PREFIX, &logs) {
+                if let Event::MessagePosted { sequence, .. } = event {
+                    println!("Sequence number: {}", sequence);
+                }
+            }
         } else {
             warn!("Could not get program logs for attestation");
         }
@@ -168,9 +175,7 @@ fn handle_init(
         wh_prog: wh_prog,
         pyth_owner: pyth_owner_addr,
     };
-    let ix_data = (pyth2wormhole::instruction::Instruction::Initialize, config);
-
-    let (ix, signers) = accs.to_ix(p2w_addr, ix_data.try_to_vec()?.as_slice())?;
+    let (ix, signers) = pyth2wormhole::instruction::Initialize::build(p2w_addr, accs, config)?;
 
     let tx_signed = Transaction::new_signed_with_payer::<Vec<&Keypair>>(
         &[ix],
@@ -207,9 +212,7 @@ fn handle_set_config(
         wh_prog: new_wh_prog,
         pyth_owner: new_pyth_owner_addr,
     };
-    let ix_data = (pyth2wormhole::instruction::Instruction::SetConfig, config);
-
-    let (ix, signers) = accs.to_ix(p2w_addr, ix_data.try_to_vec()?.as_slice())?;
+    let (ix, signers) = pyth2wormhole::instruction::SetConfig::build(p2w_addr, accs, config)?;
 
     let tx_signed = Transaction::new_signed_with_payer::<Vec<&Keypair>>(
         &[ix],
@@ -235,8 +238,8 @@ fn handle_attest(
 
     let p2w_config_addr = P2WConfigAccount::<{ AccountState::Initialized }>::key(None, &p2w_addr);
 
-    let config =
-        Pyth2WormholeConfig::try_from_slice(rpc.get_account_data(&p2w_config_addr)?.as_slice())?;
+    let config: Versioned<Pyth2WormholeConfig> =
+        from_account_data(rpc.get_account_data(&p2w_config_addr)?.as_slice())?;
 
     // Derive dynamic seeded accounts
     let seq_addr = Sequence::key(
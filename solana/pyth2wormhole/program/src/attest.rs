@@ -28,23 +28,28 @@ use bridge::{
 };
 
 use solitaire::{
+    from_account_data,
     trace,
     AccountState,
     Derive,
     ExecutionContext,
     FromAccounts,
+    IdlSchema,
     Info,
     InstructionContext,
     Keyed,
     Mut,
     Peel,
+    Program,
     Result as SoliResult,
     Seeded,
     invoke_seeded,
     Signer,
     SolitaireError,
     Sysvar,
+    SystemProgram,
     ToInstruction,
+    Versioned,
 };
 
 pub type P2WEmitter<'b> = Derive<Info<'b>, "p2w-emitter">;
@@ -53,7 +58,7 @@ pub type P2WEmitter<'b> = Derive<Info<'b>, "p2w-emitter">;
 pub struct Attest<'b> {
     // Payer also used for wormhole
     pub payer: Mut<Signer<Info<'b>>>,
-    pub system_program: Info<'b>,
+    pub system_program: Program<'b, SystemProgram>,
     pub config: P2WConfigAccount<'b, { AccountState::Initialized }>,
     pub pyth_product: Info<'b>,
     pub pyth_price: Info<'b>,
@@ -83,7 +88,7 @@ pub struct Attest<'b> {
     pub wh_rent: Sysvar<'b, Rent>,
 }
 
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, IdlSchema)]
 pub struct AttestData {
     pub nonce: u32,
     pub consistency_level: ConsistencyLevel,
@@ -113,6 +118,7 @@ pub fn attest(ctx: &ExecutionContext, accs: &mut Attest, data: AttestData) -> So
             "Wormhole program account mismatch (expected {:?}, got {:?})",
             accs.config.wh_prog, accs.wh_prog.key
         ));
+        return Err(SolitaireError::InvalidProgram(*accs.wh_prog.key));
     }
 
     let price_attestation = PriceAttestation::from_pyth_price_bytes(
@@ -129,7 +135,9 @@ pub fn attest(ctx: &ExecutionContext, accs: &mut Attest, data: AttestData) -> So
         return Err(ProgramError::InvalidAccountData.into());
     }
 
-    let bridge_config = BridgeData::try_from_slice(&accs.wh_bridge.try_borrow_mut_data()?)?.config;
+    let bridge_config = from_account_data::<Versioned<BridgeData>>(&accs.wh_bridge.try_borrow_data()?)?
+        .0
+        .config;
 
     // Pay wormhole fee
     let transfer_ix = solana_program::system_instruction::transfer(
@@ -1,8 +1,12 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
-use solitaire::{processors::seeded::AccountOwner, AccountState, Data, Derive, Owned};
+use solitaire::{
+    processors::seeded::AccountOwner, AccountState, Data, Derive, Discriminated, IdlSchema,
+    Migrate, Owned, Versioned,
+};
 
-#[derive(Default, BorshDeserialize, BorshSerialize)]
+#[derive(Default, BorshDeserialize, BorshSerialize, IdlSchema, Discriminated)]
+#[discriminator(legacy)]
 pub struct Pyth2WormholeConfig {
     ///  Authority owning this contract
     pub owner: Pubkey,
@@ -18,5 +22,12 @@ impl Owned for Pyth2WormholeConfig {
     }
 }
 
-pub type P2WConfigAccount<'b, const IsInitialized: AccountState> =
-    Derive<Data<'b, Pyth2WormholeConfig, { IsInitialized }>, "pyth2wormhole-config">;
+/// Still the layout of the config accounts deployed before it was versioned.
+impl Migrate for Pyth2WormholeConfig {
+    const VERSION: u8 = 0;
+}
+
+pub type P2WConfigAccount<'b, const IsInitialized: AccountState> = Derive<
+    Data<'b, Versioned<Pyth2WormholeConfig>, { IsInitialized }>,
+    "pyth2wormhole-config",
+>;
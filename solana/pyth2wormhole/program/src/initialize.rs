@@ -23,7 +23,7 @@ pub fn initialize(
 ) -> SoliResult<()> {
     accs.new_config
         .create(ctx, accs.payer.info().key, CreationLamports::Exempt)?;
-    accs.new_config.1 = data;
+    ****accs.new_config = data;
 
     Ok(())
 }
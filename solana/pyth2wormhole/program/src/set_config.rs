@@ -54,7 +54,7 @@ pub fn set_config(
         ));
     }
 
-    accs.config.1 = data;
+    ****accs.config = data;
 
     Ok(())
 }
@@ -5,6 +5,11 @@
 //! Client-specific code
 
 pub use solana_program::pubkey::Pubkey;
+#[cfg(feature = "rpc")]
+use solana_client::{
+    client_error::ClientError,
+    rpc_client::RpcClient,
+};
 use solana_program::sysvar::Sysvar as SolSysvar;
 pub use solana_sdk;
 
@@ -12,14 +17,22 @@ pub use solana_sdk::{
     instruction::{
         AccountMeta,
         Instruction,
+        InstructionError,
     },
     signature::{
         Keypair,
         Signer as SolSigner,
     },
+    transaction::{
+        Transaction,
+        TransactionError,
+    },
 };
 
-use borsh::BorshSerialize;
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
 
 pub use solitaire::{
     processors::seeded::Seeded,
@@ -31,9 +44,21 @@ pub use solitaire::{
 };
 use solitaire::{
     AccountState,
+    Close,
+    CustomError,
     Info,
+    MaybeMut,
     Mut,
+    Optional,
+    Program,
+    ProgramId,
+    SolitaireErrorCode,
+    System,
     Sysvar,
+    View,
+    Viewable,
+    ABSENT,
+    CUSTOM_ERROR_OFFSET,
 };
 
 type StdResult<T, E> = std::result::Result<T, E>;
@@ -65,6 +90,16 @@ pub enum AccEntry {
     Derived(Pubkey),
     /// Key derived from constants and/or program address, read-only.
     DerivedRO(Pubkey),
+
+    /// Accounts of a nested account struct, in field order.
+    Nested(Vec<AccEntry>),
+
+    /// Elements of a list of accounts, in order. Keypairs of signers among them are not returned
+    /// by `to_ix`, the caller signs with them directly.
+    Many(Vec<AccEntry>),
+
+    /// An optional account that is not provided.
+    Absent,
 }
 
 /// Types implementing Wrap are those that can be turned into a
@@ -153,6 +188,21 @@ where
     }
 }
 
+/// Views are read-only, so they are always passed without write access.
+impl<'a, T: Viewable> Wrap for View<'a, T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        match a {
+            AccEntry::UnprivilegedRO(k) => Ok(vec![AccountMeta::new_readonly(*k, false)]),
+            AccEntry::SignerRO(pair) => Ok(vec![AccountMeta::new_readonly(pair.pubkey(), true)]),
+            _other => Err(format!(
+                "{} must be passed as UnprivilegedRO or SignerRO",
+                std::any::type_name::<Self>()
+            )
+            .into()),
+        }
+    }
+}
+
 impl<'b, Var> Wrap for Sysvar<'b, Var>
 where
     Var: SolSysvar,
@@ -180,8 +230,9 @@ impl<'b> Wrap for Info<'b> {
         match a {
             AccEntry::UnprivilegedRO(k) => Ok(vec![AccountMeta::new_readonly(k.clone(), false)]),
             AccEntry::Unprivileged(k) => Ok(vec![AccountMeta::new(k.clone(), false)]),
+            AccEntry::CPIProgram(k) => Ok(vec![AccountMeta::new_readonly(k.clone(), false)]),
             _other => Err(format!(
-                "{} must be passed as Unprivileged or UnprivilegedRO",
+                "{} must be passed as Unprivileged, UnprivilegedRO or CPIProgram",
                 std::any::type_name::<Self>()
             )
             .into()),
@@ -204,6 +255,69 @@ impl<T: Wrap> Wrap for Mut<T> {
     }
 }
 
+/// Write access is decided by the caller, the entry is passed through as is.
+impl<T: Wrap> Wrap for MaybeMut<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
+impl<T: Wrap> Wrap for System<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
+impl<T: Wrap, const Recipient: &'static str> Wrap for Close<T, Recipient> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        T::wrap(a)
+    }
+}
+
+impl<T: Wrap> Wrap for Vec<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        match a {
+            AccEntry::Many(entries) => {
+                let mut metas = Vec::new();
+                for entry in entries {
+                    metas.append(&mut T::wrap(entry)?);
+                }
+                Ok(metas)
+            }
+            _other => Err(format!(
+                "{} must be passed as Many with an entry for each account",
+                std::any::type_name::<Self>()
+            )
+            .into()),
+        }
+    }
+}
+
+impl<T: Wrap> Wrap for Optional<T> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        match a {
+            AccEntry::Absent => Ok(vec![AccountMeta::new_readonly(ABSENT, false)]),
+            other => T::wrap(other),
+        }
+    }
+}
+
+impl<'b, Id: ProgramId> Wrap for Program<'b, Id> {
+    fn wrap(a: &AccEntry) -> StdResult<Vec<AccountMeta>, ErrBox> {
+        match a {
+            AccEntry::CPIProgram(k) if *k == Id::id() => {
+                Ok(vec![AccountMeta::new_readonly(*k, false)])
+            }
+            _other => Err(format!(
+                "{} must be passed as CPIProgram of {}",
+                std::any::type_name::<Self>(),
+                Id::id()
+            )
+            .into()),
+        }
+    }
+}
+
 /// Trait used on client side to easily validate a program accounts + ix_data for a bare Solana call
 pub trait ToInstruction {
     fn to_ix(
@@ -212,3 +326,184 @@ pub trait ToInstruction {
         ix_data: &[u8],
     ) -> StdResult<(Instruction, Vec<Keypair>), ErrBox>;
 }
+
+/// Links an on-chain account struct to the client side struct generated for it by
+/// `#[derive(ToInstruction)]`, which lets `solitaire!` generate typed instruction builders.
+pub trait ClientAccounts {
+    type Accounts: ToInstruction;
+}
+
+/// A custom error code returned by a Solitaire program, mapped back to the error that caused it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodedError<E> {
+    /// Raised by Solitaire itself, e.g. while parsing accounts or instruction data.
+    Solitaire(SolitaireErrorCode),
+    /// Raised by the program, `E` is the program's error enum.
+    Program(E),
+    /// Not part of the scheme, e.g. a code returned by a program called over CPI.
+    Unknown(u32),
+}
+
+/// Map a custom error code back to the framework or program error it stands for.
+pub fn decode_error_code<E: CustomError>(code: u32) -> DecodedError<E> {
+    if code >= CUSTOM_ERROR_OFFSET {
+        if let Some(e) = E::from_code((code - CUSTOM_ERROR_OFFSET) as u64) {
+            return DecodedError::Program(e);
+        }
+    } else if let Some(e) = SolitaireErrorCode::from_code(code) {
+        return DecodedError::Solitaire(e);
+    }
+    DecodedError::Unknown(code)
+}
+
+/// Extract and decode the custom error code of a failed transaction, `None` if the transaction did
+/// not fail with a custom error.
+pub fn decode_transaction_error<E: CustomError>(err: &TransactionError) -> Option<DecodedError<E>> {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            Some(decode_error_code(*code))
+        }
+        _ => None,
+    }
+}
+
+/// Decode a single log line written as `prefix` followed by a hex encoded, Borsh serialized `E`.
+/// Returns `None` for lines that do not carry an event.
+pub fn decode_event<E: BorshDeserialize>(prefix: &str, log: &str) -> Option<E> {
+    let data = hex::decode(log.strip_prefix(prefix)?).ok()?;
+    E::try_from_slice(&data).ok()
+}
+
+/// Collect all events logged by `program_id` from the log messages of a transaction. Invocations
+/// are tracked so that lines logged by other programs, which could otherwise imitate an event, are
+/// ignored.
+pub fn decode_events<E: BorshDeserialize, S: AsRef<str>>(
+    program_id: &Pubkey,
+    prefix: &str,
+    logs: &[S],
+) -> Vec<E> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split(' ');
+            if let (Some(id), Some(action)) = (parts.next(), parts.next()) {
+                match action {
+                    "invoke" => {
+                        stack.push(id);
+                        continue;
+                    }
+                    "success" | "failed:" => {
+                        stack.pop();
+                        continue;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if stack.last() == Some(&program_id.as_str()) {
+            if let Some(event) = decode_event(prefix, log) {
+                events.push(event);
+            }
+        }
+    }
+
+    events
+}
+
+/// Simulate a transaction without committing it, returning the program logs it produced.
+#[cfg(feature = "rpc")]
+pub fn simulate(
+    client: &RpcClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instructions: &[Instruction],
+) -> StdResult<Vec<String>, ClientError> {
+    let mut transaction = Transaction::new_with_payer(instructions, Some(&payer.pubkey()));
+    let recent_blockhash = client.get_recent_blockhash()?.0;
+    transaction.try_sign(&signers.to_vec(), recent_blockhash)?;
+    let result = client.simulate_transaction(&transaction)?;
+    Ok(result.value.logs.unwrap_or_default())
+}
+
+/// Compute units consumed by `program` according to the logs of a transaction, summed over each
+/// top level invocation of it.
+pub fn compute_units<S: AsRef<str>>(program: &Pubkey, logs: &[S]) -> u64 {
+    let prefix = format!("Program {} consumed ", program);
+    logs.iter()
+        .filter_map(|log| log.as_ref().strip_prefix(&prefix))
+        .filter_map(|rest| rest.split(' ').next()?.parse::<u64>().ok())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_many() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let signer = Keypair::new();
+
+        // Elements are wrapped in order, each by its own entry.
+        let metas = Vec::<Info>::wrap(&AccEntry::Many(vec![
+            AccEntry::Unprivileged(a),
+            AccEntry::UnprivilegedRO(b),
+        ]))
+        .unwrap();
+        assert_eq!(
+            metas,
+            vec![AccountMeta::new(a, false), AccountMeta::new_readonly(b, false)]
+        );
+
+        let metas = Vec::<Signer<Info>>::wrap(&AccEntry::Many(vec![AccEntry::Signer(
+            Keypair::from_bytes(&signer.to_bytes()).unwrap(),
+        )]))
+        .unwrap();
+        assert_eq!(metas, vec![AccountMeta::new(signer.pubkey(), true)]);
+
+        // Lists may be empty.
+        let metas = Vec::<Info>::wrap(&AccEntry::Many(vec![])).unwrap();
+        assert!(metas.is_empty());
+    }
+
+    #[test]
+    fn compute_units_of_program() {
+        let (program, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let logs = vec![
+            format!("Program {} invoke [1]", program),
+            format!("Program {} invoke [2]", other),
+            format!("Program {} consumed 1000 of 190000 compute units", other),
+            format!("Program {} success", other),
+            format!("Program {} consumed 5000 of 200000 compute units", program),
+            format!("Program {} success", program),
+            format!("Program {} invoke [1]", program),
+            format!("Program {} consumed 3000 of 200000 compute units", program),
+            format!("Program {} success", program),
+        ];
+
+        // Units consumed by programs it invokes are already part of its own.
+        assert_eq!(compute_units(&program, &logs), 8000);
+        assert_eq!(compute_units(&other, &logs), 1000);
+    }
+
+    #[test]
+    fn wrap_many_rejects() {
+        let a = Pubkey::new_unique();
+
+        // Lists must be passed as Many, even with a single element.
+        assert!(Vec::<Info>::wrap(&AccEntry::Unprivileged(a)).is_err());
+        assert!(Vec::<Info>::wrap(&AccEntry::Nested(vec![AccEntry::Unprivileged(a)])).is_err());
+
+        // Each element is held to the type of the list.
+        assert!(Vec::<Mut<Info>>::wrap(&AccEntry::Many(vec![
+            AccEntry::Unprivileged(a),
+            AccEntry::UnprivilegedRO(a),
+        ]))
+        .is_err());
+    }
+}
@@ -49,6 +49,43 @@ pub enum SolitaireError {
     /// An instruction that wasn't recognised was sent.
     UnknownInstruction(u8),
 
+    /// The account data does not start with the discriminator of the expected type.
+    InvalidDiscriminator(Pubkey),
+
+    /// The instruction ran out of accounts while parsing the named accounts struct field.
+    NotEnoughAccountKeys(&'static str),
+
+    /// The instruction data was empty, so no instruction could be dispatched.
+    EmptyInstruction,
+
+    /// An account was passed after all accounts of a strict accounts struct were parsed.
+    UnexpectedAccount(Pubkey),
+
+    /// The AccountInfo parser expected a specific program, but the key was a different one.
+    InvalidProgram(Pubkey),
+
+    /// An account was to be closed into itself.
+    InvalidCloseRecipient(Pubkey),
+
+    /// The serialized account data no longer fits into the space allocated for the account.
+    AccountTooSmall(Pubkey),
+
+    /// The value of an account was modified, but the account is not writable by the program.
+    UnpersistedChanges(Pubkey),
+
+    /// The named accounts struct takes the lengths of its account lists from its instruction data,
+    /// so it can only be parsed for its instruction and not nested in another struct.
+    InstructionDataRequired(&'static str),
+
+    /// A relation declared on the named accounts struct field does not hold.
+    ConstraintViolated(&'static str),
+
+    /// An amount of lamports moved by the framework overflowed.
+    MathOverflow,
+
+    /// The account has to grow to hold its value, which the runtime can't do.
+    AccountResizeUnsupported(Pubkey),
+
     Custom(u64),
 }
 
@@ -64,11 +101,146 @@ impl From<std::io::Error> for SolitaireError {
     }
 }
 
+impl std::fmt::Display for SolitaireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl std::error::Error for SolitaireError {}
+
+/// Custom error codes in `[SOLITAIRE_ERROR_OFFSET, CUSTOM_ERROR_OFFSET)` are reserved for errors
+/// raised by Solitaire itself.
+pub const SOLITAIRE_ERROR_OFFSET: u32 = 1000;
+
+/// Custom error codes from `CUSTOM_ERROR_OFFSET` onwards belong to the program, the code of a
+/// `SolitaireError::Custom(n)` is `CUSTOM_ERROR_OFFSET + n`.
+pub const CUSTOM_ERROR_OFFSET: u32 = 2000;
+
+/// Stable codes for the framework errors. New variants must only ever be appended.
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolitaireErrorCode {
+    InvalidMutability = SOLITAIRE_ERROR_OFFSET,
+    InvalidSigner,
+    InvalidSysvar,
+    InvalidDerive,
+    InvalidOwner,
+    NonWriteableAccount,
+    InstructionDeserializeFailed,
+    IoError,
+    AmbiguousOwner,
+    AlreadyInitialized,
+    UnknownInstruction,
+    InvalidDiscriminator,
+    NotEnoughAccountKeys,
+    EmptyInstruction,
+    UnexpectedAccount,
+    InvalidProgram,
+    InvalidCloseRecipient,
+    AccountTooSmall,
+    UnpersistedChanges,
+    InstructionDataRequired,
+    ConstraintViolated,
+    MathOverflow,
+    AccountResizeUnsupported,
+}
+
+impl SolitaireErrorCode {
+    pub const ALL: &'static [SolitaireErrorCode] = &[
+        SolitaireErrorCode::InvalidMutability,
+        SolitaireErrorCode::InvalidSigner,
+        SolitaireErrorCode::InvalidSysvar,
+        SolitaireErrorCode::InvalidDerive,
+        SolitaireErrorCode::InvalidOwner,
+        SolitaireErrorCode::NonWriteableAccount,
+        SolitaireErrorCode::InstructionDeserializeFailed,
+        SolitaireErrorCode::IoError,
+        SolitaireErrorCode::AmbiguousOwner,
+        SolitaireErrorCode::AlreadyInitialized,
+        SolitaireErrorCode::UnknownInstruction,
+        SolitaireErrorCode::InvalidDiscriminator,
+        SolitaireErrorCode::NotEnoughAccountKeys,
+        SolitaireErrorCode::EmptyInstruction,
+        SolitaireErrorCode::UnexpectedAccount,
+        SolitaireErrorCode::InvalidProgram,
+        SolitaireErrorCode::InvalidCloseRecipient,
+        SolitaireErrorCode::AccountTooSmall,
+        SolitaireErrorCode::UnpersistedChanges,
+        SolitaireErrorCode::InstructionDataRequired,
+        SolitaireErrorCode::ConstraintViolated,
+        SolitaireErrorCode::MathOverflow,
+        SolitaireErrorCode::AccountResizeUnsupported,
+    ];
+
+    pub fn from_code(code: u32) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|c| **c as u32 == code)
+            .copied()
+    }
+}
+
+/// Implemented by program level error enums, allowing clients to map a custom error code back to
+/// the variant that caused it. Use `custom_error!` to define such an enum.
+pub trait CustomError: Sized {
+    /// Variant names, indexed by code.
+    const NAMES: &'static [&'static str];
+
+    fn from_code(code: u64) -> Option<Self>;
+}
+
+/// For programs without errors of their own.
+impl CustomError for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn from_code(_code: u64) -> Option<Self> {
+        None
+    }
+}
+
+impl SolitaireError {
+    /// The custom code this error is reported as, `None` for errors passed through as-is.
+    pub fn code(&self) -> Option<u32> {
+        use SolitaireErrorCode as Code;
+        let code = match self {
+            SolitaireError::InvalidMutability(..) => Code::InvalidMutability,
+            SolitaireError::InvalidSigner(_) => Code::InvalidSigner,
+            SolitaireError::InvalidSysvar(_) => Code::InvalidSysvar,
+            SolitaireError::InvalidDerive(..) => Code::InvalidDerive,
+            SolitaireError::InvalidOwner(_) => Code::InvalidOwner,
+            SolitaireError::NonWriteableAccount(_) => Code::NonWriteableAccount,
+            SolitaireError::InstructionDeserializeFailed(_) => Code::InstructionDeserializeFailed,
+            SolitaireError::IoError(_) => Code::IoError,
+            SolitaireError::AmbiguousOwner => Code::AmbiguousOwner,
+            SolitaireError::AlreadyInitialized(_) => Code::AlreadyInitialized,
+            SolitaireError::UnknownInstruction(_) => Code::UnknownInstruction,
+            SolitaireError::InvalidDiscriminator(_) => Code::InvalidDiscriminator,
+            SolitaireError::NotEnoughAccountKeys(_) => Code::NotEnoughAccountKeys,
+            SolitaireError::EmptyInstruction => Code::EmptyInstruction,
+            SolitaireError::UnexpectedAccount(_) => Code::UnexpectedAccount,
+            SolitaireError::InvalidProgram(_) => Code::InvalidProgram,
+            SolitaireError::InvalidCloseRecipient(_) => Code::InvalidCloseRecipient,
+            SolitaireError::AccountTooSmall(_) => Code::AccountTooSmall,
+            SolitaireError::UnpersistedChanges(_) => Code::UnpersistedChanges,
+            SolitaireError::InstructionDataRequired(_) => Code::InstructionDataRequired,
+            SolitaireError::ConstraintViolated(_) => Code::ConstraintViolated,
+            SolitaireError::MathOverflow => Code::MathOverflow,
+            SolitaireError::AccountResizeUnsupported(_) => Code::AccountResizeUnsupported,
+            SolitaireError::ProgramError(_) => return None,
+            SolitaireError::Custom(n) => {
+                return Some(CUSTOM_ERROR_OFFSET.saturating_add(*n as u32));
+            }
+        };
+        Some(code as u32)
+    }
+}
+
 impl Into<ProgramError> for SolitaireError {
     fn into(self) -> ProgramError {
         match self {
-            SolitaireError::ProgramError(e) => return e,
-            _ => ProgramError::Custom(0),
+            SolitaireError::ProgramError(e) => e,
+            other => ProgramError::Custom(other.code().unwrap_or(0)),
         }
     }
 }
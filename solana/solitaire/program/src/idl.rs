@@ -0,0 +1,335 @@
+//! IDL.
+//!
+//! A machine readable description of a Solitaire program: its instructions with their Borsh
+//! encoded arguments and ordered account lists, and the errors it can fail with. The `solitaire!`
+//! macro generates an `idl()` function from the same information it uses for dispatching, so the
+//! description can't drift from the program. Clients can be generated from its JSON form.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    CustomError,
+    SolitaireErrorCode,
+    CUSTOM_ERROR_OFFSET,
+};
+
+/// Types that can appear in instruction arguments. Implemented for primitives here, and derived
+/// for structs and enums with `#[derive(IdlSchema)]`.
+pub trait IdlSchema {
+    /// The type as referenced from a field.
+    fn idl_type() -> IdlType;
+
+    /// Collect the definitions of this type and all types it refers to.
+    fn idl_definitions(_definitions: &mut Vec<IdlTypeDef>) {}
+}
+
+/// Reference to a type, mirroring how Borsh encodes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    String,
+    PublicKey,
+    Array(Box<IdlType>, usize),
+    Vec(Box<IdlType>),
+    Option(Box<IdlType>),
+    /// A struct or enum, found by name in the list of type definitions.
+    Defined(String),
+}
+
+/// Definition of a user defined type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdlTypeDef {
+    Struct {
+        name: String,
+        fields: Vec<(String, IdlType)>,
+    },
+    Enum {
+        name: String,
+        variants: Vec<(String, Vec<(String, IdlType)>)>,
+    },
+}
+
+impl IdlTypeDef {
+    pub fn name(&self) -> &str {
+        match self {
+            IdlTypeDef::Struct { name, .. } => name,
+            IdlTypeDef::Enum { name, .. } => name,
+        }
+    }
+}
+
+/// A single account expected by an instruction, described by the layers wrapping it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IdlAccount {
+    /// Field name, nested account structs are joined with a dot.
+    pub name: String,
+    pub writable: bool,
+    pub signer: bool,
+    /// Constant seed of a `Derive` account.
+    pub seed: Option<String>,
+    /// Fixed address, for sysvars and programs.
+    pub address: Option<Pubkey>,
+    /// Type of the account data for `Data` accounts, along with the state it is expected in.
+    pub data: Option<String>,
+    pub state: Option<String>,
+    /// Whether the account can be left out by passing `ABSENT` instead.
+    pub optional: bool,
+    /// Whether the account is an element of a list of any number of accounts.
+    pub many: bool,
+}
+
+impl IdlAccount {
+    pub fn new(name: &str) -> Self {
+        IdlAccount {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Name of the account `field` within an account struct that is itself named `prefix`.
+pub fn account_name(prefix: &str, field: &str) -> String {
+    match prefix {
+        "" => field.to_string(),
+        _ => format!("{}.{}", prefix, field),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlInstruction {
+    pub name: String,
+    pub discriminant: u8,
+    pub args: IdlType,
+    pub accounts: Vec<IdlAccount>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdlError {
+    pub code: u32,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Idl {
+    pub name: String,
+    pub version: String,
+    pub instructions: Vec<IdlInstruction>,
+    pub types: Vec<IdlTypeDef>,
+    pub errors: Vec<IdlError>,
+}
+
+/// All errors a program failing with `E` can report, framework errors first.
+pub fn errors<E: CustomError>() -> Vec<IdlError> {
+    let framework = SolitaireErrorCode::ALL.iter().map(|code| IdlError {
+        code: *code as u32,
+        name: format!("{:?}", code),
+    });
+    let program = E::NAMES.iter().enumerate().map(|(i, name)| IdlError {
+        code: CUSTOM_ERROR_OFFSET + i as u32,
+        name: name.to_string(),
+    });
+    framework.chain(program).collect()
+}
+
+impl Idl {
+    pub fn to_json(&self) -> String {
+        let instructions: Vec<String> = self
+            .instructions
+            .iter()
+            .map(|ix| {
+                let accounts: Vec<String> = ix.accounts.iter().map(account_json).collect();
+                format!(
+                    "{{\"name\":{},\"discriminant\":{},\"args\":{},\"accounts\":[{}]}}",
+                    json_string(&ix.name),
+                    ix.discriminant,
+                    type_json(&ix.args),
+                    accounts.join(","),
+                )
+            })
+            .collect();
+        let types: Vec<String> = self.types.iter().map(type_def_json).collect();
+        let errors: Vec<String> = self
+            .errors
+            .iter()
+            .map(|e| format!("{{\"code\":{},\"name\":{}}}", e.code, json_string(&e.name)))
+            .collect();
+
+        format!(
+            "{{\"name\":{},\"version\":{},\"instructions\":[{}],\"types\":[{}],\"errors\":[{}]}}",
+            json_string(&self.name),
+            json_string(&self.version),
+            instructions.join(","),
+            types.join(","),
+            errors.join(","),
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn type_json(t: &IdlType) -> String {
+    match t {
+        IdlType::Bool => json_string("bool"),
+        IdlType::U8 => json_string("u8"),
+        IdlType::U16 => json_string("u16"),
+        IdlType::U32 => json_string("u32"),
+        IdlType::U64 => json_string("u64"),
+        IdlType::U128 => json_string("u128"),
+        IdlType::I8 => json_string("i8"),
+        IdlType::I16 => json_string("i16"),
+        IdlType::I32 => json_string("i32"),
+        IdlType::I64 => json_string("i64"),
+        IdlType::I128 => json_string("i128"),
+        IdlType::String => json_string("string"),
+        IdlType::PublicKey => json_string("publicKey"),
+        IdlType::Array(t, len) => format!("{{\"array\":[{},{}]}}", type_json(t), len),
+        IdlType::Vec(t) => format!("{{\"vec\":{}}}", type_json(t)),
+        IdlType::Option(t) => format!("{{\"option\":{}}}", type_json(t)),
+        IdlType::Defined(name) => format!("{{\"defined\":{}}}", json_string(name)),
+    }
+}
+
+fn fields_json(fields: &[(String, IdlType)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, t)| format!("{{\"name\":{},\"type\":{}}}", json_string(name), type_json(t)))
+        .collect();
+    format!("[{}]", fields.join(","))
+}
+
+fn type_def_json(def: &IdlTypeDef) -> String {
+    match def {
+        IdlTypeDef::Struct { name, fields } => format!(
+            "{{\"name\":{},\"kind\":\"struct\",\"fields\":{}}}",
+            json_string(name),
+            fields_json(fields),
+        ),
+        IdlTypeDef::Enum { name, variants } => {
+            let variants: Vec<String> = variants
+                .iter()
+                .map(|(name, fields)| {
+                    format!(
+                        "{{\"name\":{},\"fields\":{}}}",
+                        json_string(name),
+                        fields_json(fields)
+                    )
+                })
+                .collect();
+            format!(
+                "{{\"name\":{},\"kind\":\"enum\",\"variants\":[{}]}}",
+                json_string(name),
+                variants.join(","),
+            )
+        }
+    }
+}
+
+fn account_json(account: &IdlAccount) -> String {
+    let mut out = format!(
+        "{{\"name\":{},\"writable\":{},\"signer\":{}",
+        json_string(&account.name),
+        account.writable,
+        account.signer,
+    );
+    if let Some(seed) = &account.seed {
+        out.push_str(&format!(",\"seed\":{}", json_string(seed)));
+    }
+    if let Some(address) = &account.address {
+        out.push_str(&format!(",\"address\":{}", json_string(&address.to_string())));
+    }
+    if let Some(data) = &account.data {
+        out.push_str(&format!(",\"data\":{}", json_string(data)));
+    }
+    if let Some(state) = &account.state {
+        out.push_str(&format!(",\"state\":{}", json_string(state)));
+    }
+    if account.optional {
+        out.push_str(",\"optional\":true");
+    }
+    if account.many {
+        out.push_str(",\"many\":true");
+    }
+    out.push('}');
+    out
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl IdlSchema for $ty {
+                fn idl_type() -> IdlType {
+                    IdlType::$variant
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive! {
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    i128 => I128,
+    String => String,
+    Pubkey => PublicKey,
+}
+
+impl<T: IdlSchema, const N: usize> IdlSchema for [T; N] {
+    fn idl_type() -> IdlType {
+        IdlType::Array(Box::new(T::idl_type()), N)
+    }
+
+    fn idl_definitions(definitions: &mut Vec<IdlTypeDef>) {
+        T::idl_definitions(definitions)
+    }
+}
+
+impl<T: IdlSchema> IdlSchema for Vec<T> {
+    fn idl_type() -> IdlType {
+        IdlType::Vec(Box::new(T::idl_type()))
+    }
+
+    fn idl_definitions(definitions: &mut Vec<IdlTypeDef>) {
+        T::idl_definitions(definitions)
+    }
+}
+
+impl<T: IdlSchema> IdlSchema for Option<T> {
+    fn idl_type() -> IdlType {
+        IdlType::Option(Box::new(T::idl_type()))
+    }
+
+    fn idl_definitions(definitions: &mut Vec<IdlTypeDef>) {
+        T::idl_definitions(definitions)
+    }
+}
@@ -1,11 +1,12 @@
 #![feature(const_generics)]
+#![feature(auto_traits)]
+#![feature(negative_impls)]
 #![allow(warnings)]
 
 pub use rocksalt::*;
 
 // Lacking:
 //
-// - Error is a lacking as its just a basic enum, maybe use errorcode.
 // - Client generation incomplete.
 
 // We need a few Solana things in scope in order to properly abstract Solana.
@@ -54,6 +55,7 @@ pub use borsh::{
 
 // Expose all submodules for consumption.
 pub mod error;
+pub mod idl;
 pub mod macros;
 pub mod processors;
 pub mod types;
@@ -62,12 +64,20 @@ pub mod types;
 // people to be able to use from top-level.
 pub use crate::{
     error::{
+        CustomError,
         ErrBox,
         Result,
         SolitaireError,
+        SolitaireErrorCode,
+        CUSTOM_ERROR_OFFSET,
+        SOLITAIRE_ERROR_OFFSET,
     },
     macros::*,
     processors::{
+        discriminated::{
+            from_account_data,
+            Discriminated,
+        },
         keyed::Keyed,
         peel::Peel,
         persist::Persist,
@@ -79,6 +89,11 @@ pub use crate::{
             Owned,
             Seeded,
         },
+        versioned::{
+            Migrate,
+            Versioned,
+        },
+        view::Viewable,
     },
     types::*,
 };
@@ -102,11 +117,12 @@ pub enum CreationLamports {
 }
 
 impl CreationLamports {
-    /// Amount of lamports to be paid in account creation
-    pub fn amount(self, size: usize) -> u64 {
+    /// Amount of lamports to be paid in account creation, exemption is computed from the rent
+    /// currently configured on the cluster.
+    pub fn amount(self, size: usize) -> Result<u64> {
         match self {
-            CreationLamports::Exempt => Rent::default().minimum_balance(size),
-            CreationLamports::Amount(v) => v,
+            CreationLamports::Exempt => Ok(Sysvar::<Rent>::get()?.minimum_balance(size)),
+            CreationLamports::Amount(v) => Ok(v),
         }
     }
 }
@@ -120,7 +136,67 @@ pub trait InstructionContext<'a> {
 /// Trait definition that describes types that can be constructed from a list of solana account
 /// references. A list of dependent accounts is produced as a side effect of the parsing stage.
 pub trait FromAccounts<'a, 'b: 'a, 'c> {
+    /// Whether accounts left over after parsing are rejected, set with `#[strict]` on the derive.
+    const STRICT: bool = false;
+
     fn from<T>(_: &'a Pubkey, _: &'c mut Iter<'a, AccountInfo<'b>>, _: &'a T) -> Result<Self>
     where
         Self: Sized;
 }
+
+/// Rejects the accounts left over after parsing a strict accounts struct, other than the
+/// dependencies that clients append to its instructions.
+pub fn check_trailing_accounts<'a, 'b: 'a, 'c, A>(
+    _accounts: &A,
+    mut rest: Iter<'a, AccountInfo<'b>>,
+) -> Result<()>
+where
+    A: FromAccounts<'a, 'b, 'c> + Peel<'a, 'b, 'c>,
+{
+    if !A::STRICT {
+        return Ok(());
+    }
+
+    let deps = A::deps();
+    match rest.find(|info| !deps.contains(info.key)) {
+        Some(info) => Err(SolitaireError::UnexpectedAccount(*info.key)),
+        None => Ok(()),
+    }
+}
+
+/// Number of accounts in `rest` before the dependencies `deps`, which clients append to
+/// instructions once each and in the order they were first required.
+pub fn remaining_len(rest: &[AccountInfo], mut deps: Vec<Pubkey>) -> usize {
+    let mut seen = Vec::new();
+    deps.retain(|v| match seen.contains(v) {
+        true => false,
+        _ => {
+            seen.push(*v);
+            true
+        }
+    });
+
+    let trailing = rest.len().saturating_sub(deps.len());
+    match rest[trailing..].iter().map(|info| info.key).eq(deps.iter()) {
+        true => trailing,
+        _ => rest.len(),
+    }
+}
+
+/// Parses the accounts of an instruction along with its data `T`. Accounts structs declaring
+/// `#[instruction(T)]` can take the lengths of their account lists from it, any other struct is
+/// parsed with `FromAccounts`.
+pub trait FromInstruction<'a, 'b: 'a, 'c, T>: Sized {
+    fn from_instruction(
+        program_id: &'a Pubkey,
+        iter: &'c mut Iter<'a, AccountInfo<'b>>,
+        data: &'a T,
+    ) -> Result<Self>;
+}
+
+/// Verifies the program derived addresses of an accounts struct whose seeds are taken from the
+/// instruction data `T`, declared with `#[instruction(T)]` and `#[seeds(..)]` on the FromAccounts
+/// derive. Runs after the accounts are parsed and before the instruction handler.
+pub trait VerifyDerivations<T> {
+    fn verify_derivations(&self, program_id: &Pubkey, data: &T) -> Result<()>;
+}
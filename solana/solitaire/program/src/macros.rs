@@ -22,13 +22,44 @@ macro_rules! trace_impl {
     ( $($arg:tt)* ) => {};
 }
 
+/// Defines a program error enum. Variants are numbered in declaration order, which makes up the
+/// program's part of the error code scheme, so new variants must only ever be appended.
+#[macro_export]
+macro_rules! custom_error {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($variant:ident),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        $vis enum $name {
+            $($variant,)*
+        }
+
+        impl $crate::CustomError for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($variant),)*];
+
+            fn from_code(code: u64) -> Option<Self> {
+                const ALL: &[$name] = &[$($name::$variant,)*];
+                ALL.get(code as usize).copied()
+            }
+        }
+
+        /// Errors thrown by the program bubble up to the solitaire wrapper, which reports them as
+        /// custom codes in the program range.
+        impl From<$name> for $crate::SolitaireError {
+            fn from(e: $name) -> $crate::SolitaireError {
+                $crate::trace!("ProgramError: {:?}", e);
+                $crate::SolitaireError::Custom(e as u64)
+            }
+        }
+    };
+}
+
 /// This is our main codegen macro. It takes as input a list of enum-like variants mapping field
 /// types to function calls. The generated code produces:
 ///
 /// - An `Instruction` enum with the enum variants passed in.
 /// - A set of functions which take as arguments the enum fields.
 /// - A Dispatcher that deserializes bytes into the enum and dispatches the function call.
-/// - A set of client calls scoped to the module `api` that can generate instructions.
+/// - A set of client calls, `instruction::<Variant>::build`, that can generate instructions.
 #[macro_export]
 macro_rules! solitaire {
     { $($row:ident($kind:ty) => $fn:ident),+ $(,)* } => {
@@ -47,10 +78,11 @@ macro_rules! solitaire {
             use solitaire::{
                 trace,
                 ExecutionContext,
-                FromAccounts,
+                FromInstruction,
                 Persist,
                 Result,
                 SolitaireError,
+                VerifyDerivations,
             };
 
             $(
@@ -67,11 +99,28 @@ macro_rules! solitaire {
                     #[inline(never)]
                     pub fn execute<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
                         let ix_data: $kind = BorshDeserialize::try_from_slice(d).map_err(|e| SolitaireError::InstructionDeserializeFailed(e))?;
-                        let mut accounts = FromAccounts::from(p, &mut a.iter(), &())?;
+                        let mut iter = a.iter();
+                        let mut accounts = FromInstruction::from_instruction(p, &mut iter, &ix_data)?;
+                        solitaire::check_trailing_accounts(&accounts, iter)?;
+                        VerifyDerivations::verify_derivations(&accounts, p, &ix_data)?;
                         $fn(&ExecutionContext{program_id: p, accounts: a}, &mut accounts, ix_data)?;
                         Persist::persist(&accounts, p)?;
                         Ok(())
                     }
+
+                    /// Generated:
+                    /// Build this instruction from the client side representation of its account
+                    /// struct and its arguments, so accounts are always passed in the order the
+                    /// program parses them.
+                    #[cfg(feature = "client")]
+                    pub fn build(
+                        program_id: Pubkey,
+                        accounts: <super::super::$row<'static> as solitaire_client::ClientAccounts>::Accounts,
+                        data: $kind,
+                    ) -> std::result::Result<(solitaire_client::Instruction, Vec<solitaire_client::Keypair>), solitaire::ErrBox> {
+                        let ix_data = (Instruction::$row, data).try_to_vec()?;
+                        solitaire_client::ToInstruction::to_ix(accounts, program_id, &ix_data)
+                    }
                 }
             )*
 
@@ -87,9 +136,10 @@ macro_rules! solitaire {
             /// This entrypoint is generated from the enum above, it deserializes incoming bytes
             /// and automatically dispatches to the correct method.
             pub fn dispatch<'a, 'b: 'a, 'c>(p: &Pubkey, a: &'c [AccountInfo<'b>], d: &[u8]) -> Result<()> {
-                match d[0] {
+                let (tag, d) = d.split_first().ok_or(SolitaireError::EmptyInstruction)?;
+                match *tag {
                     $(
-                        n if n == Instruction::$row as u8 => $row::execute(p, a, &d[1..]),
+                        n if n == Instruction::$row as u8 => $row::execute(p, a, d),
                     )*
 
                     other => {
@@ -98,6 +148,32 @@ macro_rules! solitaire {
                 }
             }
 
+            /// Generated:
+            /// Describes this program's instructions, their arguments and accounts, along with the
+            /// errors `E` the program can fail with. Each instruction's accounts are taken from the
+            /// account struct named after it.
+            pub fn idl<E: solitaire::CustomError>() -> solitaire::idl::Idl {
+                use solitaire::idl::IdlSchema;
+
+                let mut types = Vec::new();
+                $(<$kind as IdlSchema>::idl_definitions(&mut types);)*
+
+                solitaire::idl::Idl {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    instructions: vec![$(
+                        solitaire::idl::IdlInstruction {
+                            name: stringify!($row).to_string(),
+                            discriminant: Instruction::$row as u8,
+                            args: <$kind as IdlSchema>::idl_type(),
+                            accounts: <super::$row as solitaire::Peel>::idl_accounts(""),
+                        },
+                    )*],
+                    types,
+                    errors: solitaire::idl::errors::<E>(),
+                }
+            }
+
             pub fn solitaire<'a, 'b: 'a>(p: &Pubkey, a: &'a [AccountInfo<'b>], d: &[u8]) -> ProgramResult {
                 trace!("{} {} built with {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), solitaire::PKG_NAME_VERSION);
                 if let Err(err) = dispatch(p, a, d) {
@@ -149,6 +225,11 @@ macro_rules! pack_type {
             }
         }
 
+        // Stored in the layout of the owning program, which has no room for a tag.
+        impl solitaire::processors::discriminated::Discriminated for $name {
+            const DISCRIMINATOR: &'static [u8] = &[];
+        }
+
         impl std::ops::Deref for $name {
             type Target = $embed;
             fn deref(&self) -> &Self::Target {
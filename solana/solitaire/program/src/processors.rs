@@ -1,4 +1,7 @@
+pub mod discriminated;
 pub mod keyed;
 pub mod peel;
 pub mod persist;
 pub mod seeded;
+pub mod view;
+pub mod versioned;
@@ -0,0 +1,176 @@
+//! Discriminators.
+//!
+//! Every account owned by a program is prefixed with a tag identifying the type stored in it, so
+//! an account of one type cannot be passed where another is expected just because its bytes happen
+//! to deserialize. The tag is written and checked by `Data`, types only declare it, usually with
+//! `#[derive(Discriminated)]`.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+
+use crate::{
+    Result,
+    SolitaireError,
+};
+use solana_program::pubkey::Pubkey;
+
+pub trait Discriminated {
+    /// Tag stored in front of the serialized account data. Empty for types that are stored in the
+    /// layout of another program, such as SPL accounts.
+    const DISCRIMINATOR: &'static [u8];
+
+    /// Also accept accounts without a tag, created before the type had one. Such accounts are
+    /// written back without a tag for as long as they have no room for it, and are tagged once
+    /// they do. Newly created accounts are always tagged.
+    const LEGACY: bool = false;
+
+    /// Tags of the other account types of the program. Untagged data starting with one of them is
+    /// an account of that type, not a legacy account of this one.
+    const KNOWN: &'static [&'static [u8]] = &[];
+
+    /// Tag to write when persisting, for types that change their tag as they move between states.
+    /// Must be as long as `DISCRIMINATOR`.
+    fn discriminator(&self) -> &'static [u8] {
+        Self::DISCRIMINATOR
+    }
+
+    /// Deserialize the data of a legacy account stored without a tag.
+    fn from_untagged(data: &[u8]) -> std::io::Result<Self>
+    where
+        Self: BorshDeserialize,
+    {
+        Self::try_from_slice(data)
+    }
+
+    /// Serialize the value for a legacy account stored without a tag, `None` if the layout of such
+    /// accounts can no longer hold it.
+    fn untagged(&self) -> Option<Vec<u8>>
+    where
+        Self: BorshSerialize,
+    {
+        self.try_to_vec().ok()
+    }
+}
+
+/// Split the tag of `T` off account data, returning whether it was present and the serialized
+/// data that follows.
+pub fn strip_discriminator<'d, T: Discriminated>(
+    key: &Pubkey,
+    data: &'d [u8],
+) -> Result<(bool, &'d [u8])> {
+    let foreign = T::KNOWN
+        .iter()
+        .any(|tag| !tag.is_empty() && data.starts_with(tag));
+    if data.starts_with(T::DISCRIMINATOR) {
+        Ok((true, &data[T::DISCRIMINATOR.len()..]))
+    } else if T::LEGACY && !foreign {
+        Ok((false, data))
+    } else {
+        Err(SolitaireError::InvalidDiscriminator(*key))
+    }
+}
+
+/// Deserialize the data of an account holding a `T`. Untagged data has to be exactly a value in
+/// the legacy layout, so it is written back and compared.
+pub fn deserialize_account<T: Discriminated + BorshDeserialize + BorshSerialize>(
+    key: &Pubkey,
+    data: &[u8],
+) -> Result<T> {
+    let (tagged, data) = strip_discriminator::<T>(key, data)?;
+    if tagged {
+        return Ok(T::try_from_slice(data)?);
+    }
+
+    let value = T::from_untagged(data)?;
+    match value.untagged() {
+        Some(untagged) if untagged == data => Ok(value),
+        _ => Err(SolitaireError::InvalidDiscriminator(*key)),
+    }
+}
+
+/// Deserialize the data of an account holding a `T`, as stored by `Data`. Meant for clients that
+/// read accounts directly.
+pub fn from_account_data<T: Discriminated + BorshDeserialize + BorshSerialize>(
+    data: &[u8],
+) -> Result<T> {
+    deserialize_account(&Pubkey::default(), data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Legacy type, untagged accounts hold just the two fields.
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct Legacy {
+        a: u64,
+        b: u32,
+    }
+
+    impl Discriminated for Legacy {
+        const DISCRIMINATOR: &'static [u8] = b"legacy";
+        const LEGACY: bool = true;
+        const KNOWN: &'static [&'static [u8]] = TAGS;
+    }
+
+    /// Tagged type whose accounts are as long as a legacy one, and deserialize as such.
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct Tagged {
+        a: u64,
+    }
+
+    impl Discriminated for Tagged {
+        const DISCRIMINATOR: &'static [u8] = b"tagd";
+    }
+
+    /// Tagged type unknown to the legacy one.
+    #[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct Other {
+        a: u32,
+        b: u8,
+    }
+
+    impl Discriminated for Other {
+        const DISCRIMINATOR: &'static [u8] = b"other";
+    }
+
+    const TAGS: &[&[u8]] = &[Legacy::DISCRIMINATOR, Tagged::DISCRIMINATOR];
+
+    fn account<T: Discriminated + BorshSerialize>(value: &T) -> Vec<u8> {
+        [T::DISCRIMINATOR, &value.try_to_vec().unwrap()].concat()
+    }
+
+    #[test]
+    fn legacy_accounts() {
+        let value = Legacy { a: 1, b: 2 };
+        let untagged = value.try_to_vec().unwrap();
+        assert_eq!(from_account_data::<Legacy>(&untagged).unwrap(), value);
+        assert_eq!(from_account_data::<Legacy>(&account(&value)).unwrap(), value);
+
+        // Untagged data must be exactly one legacy value.
+        assert!(from_account_data::<Legacy>(&untagged[..11]).is_err());
+        assert!(from_account_data::<Legacy>(&[&untagged[..], &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn wrong_account_type() {
+        // Tagged accounts are only read as their own type.
+        let tagged = account(&Tagged { a: 3 });
+        assert!(from_account_data::<Other>(&tagged).is_err());
+        assert_eq!(from_account_data::<Tagged>(&tagged).unwrap(), Tagged { a: 3 });
+
+        // An account of another type is not a legacy account, even if it would deserialize as one.
+        assert_eq!(tagged.len(), Legacy { a: 0, b: 0 }.try_to_vec().unwrap().len());
+        assert!(Legacy::try_from_slice(&tagged).is_ok());
+        assert!(matches!(
+            from_account_data::<Legacy>(&tagged),
+            Err(SolitaireError::InvalidDiscriminator(_))
+        ));
+
+        // Nor is an account of a type it doesn't know, being of another length.
+        let other = account(&Other { a: 4, b: 5 });
+        assert!(from_account_data::<Legacy>(&other).is_err());
+    }
+}
@@ -4,19 +4,34 @@ use solana_program::{
 };
 
 use crate::{
-    processors::seeded::Owned,
+    processors::seeded::{
+        Bump,
+        Owned,
+    },
     AccountState,
+    Close,
     Data,
     Derive,
     Info,
+    MaybeMut,
     Mut,
+    Program,
+    ProgramId,
     Signer,
     System,
     Sysvar,
+    View,
+    Viewable,
 };
 
 pub trait Keyed<'a, 'b: 'a> {
     fn info(&'a self) -> &Info<'b>;
+
+    /// Slot holding the bump of this account once its derivation is known, for account types that
+    /// keep one.
+    fn bump(&'a self) -> Option<&'a Bump> {
+        None
+    }
 }
 
 impl<'a, 'b: 'a, T: Owned + Default, const IsInitialized: AccountState> Keyed<'a, 'b>
@@ -25,6 +40,10 @@ impl<'a, 'b: 'a, T: Owned + Default, const IsInitialized: AccountState> Keyed<'a
     fn info(&'a self) -> &'a Info<'b> {
         &self.0
     }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        Some(&self.2)
+    }
 }
 
 impl<'a, 'b: 'a, T> Keyed<'a, 'b> for Signer<T>
@@ -34,6 +53,10 @@ where
     fn info(&'a self) -> &'a Info<'b> {
         self.0.info()
     }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        self.0.bump()
+    }
 }
 
 impl<'a, 'b: 'a, Var: SolanaSysvar> Keyed<'a, 'b> for Sysvar<'b, Var> {
@@ -49,6 +72,10 @@ where
     fn info(&'a self) -> &'a Info<'b> {
         self.0.info()
     }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        self.0.bump()
+    }
 }
 
 impl<'a, 'b: 'a, T, const Seed: &'static str> Keyed<'a, 'b> for Derive<T, Seed>
@@ -58,6 +85,10 @@ where
     fn info(&'a self) -> &'a Info<'b> {
         self.0.info()
     }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        Some(&self.1)
+    }
 }
 
 impl<'a, 'b: 'a, T> Keyed<'a, 'b> for Mut<T>
@@ -67,6 +98,42 @@ where
     fn info(&'a self) -> &'a Info<'b> {
         self.0.info()
     }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        self.0.bump()
+    }
+}
+
+impl<'a, 'b: 'a, T> Keyed<'a, 'b> for MaybeMut<T>
+where
+    T: Keyed<'a, 'b>,
+{
+    fn info(&'a self) -> &'a Info<'b> {
+        self.0.info()
+    }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        self.0.bump()
+    }
+}
+
+impl<'a, 'b: 'a, T, const Recipient: &'static str> Keyed<'a, 'b> for Close<T, Recipient>
+where
+    T: Keyed<'a, 'b>,
+{
+    fn info(&'a self) -> &'a Info<'b> {
+        self.0.info()
+    }
+
+    fn bump(&'a self) -> Option<&'a Bump> {
+        self.0.bump()
+    }
+}
+
+impl<'a, 'b: 'a, T: Viewable> Keyed<'a, 'b> for View<'b, T> {
+    fn info(&'a self) -> &'a Info<'b> {
+        &self.0
+    }
 }
 
 impl<'a, 'b: 'a> Keyed<'a, 'b> for Info<'b> {
@@ -74,3 +141,9 @@ impl<'a, 'b: 'a> Keyed<'a, 'b> for Info<'b> {
         self
     }
 }
+
+impl<'a, 'b: 'a, Id: ProgramId> Keyed<'a, 'b> for Program<'b, Id> {
+    fn info(&'a self) -> &'a Info<'b> {
+        &self.0
+    }
+}
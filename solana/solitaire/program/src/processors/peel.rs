@@ -14,12 +14,29 @@ use solana_program::{
         SysvarId,
     },
 };
-use std::marker::PhantomData;
+use std::{
+    io::{
+        ErrorKind,
+        Write,
+    },
+    marker::PhantomData,
+};
 
 use crate::{
-    processors::seeded::{
-        AccountOwner,
-        Owned,
+    idl::IdlAccount,
+    processors::{
+        discriminated::{
+            deserialize_account,
+            strip_discriminator,
+            Discriminated,
+        },
+        seeded::{
+            find_program_address,
+            AccountOwner,
+            Bump,
+            Owned,
+        },
+        view::Viewable,
     },
     types::*,
     AccountState::MaybeInitialized,
@@ -39,6 +56,11 @@ pub trait Peel<'a, 'b: 'a, 'c> {
     fn deps() -> Vec<Pubkey>;
 
     fn persist(&self, program_id: &Pubkey) -> Result<()>;
+
+    /// Describe the accounts consumed by this type, in order, for the program IDL.
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![IdlAccount::new(name)]
+    }
 }
 
 /// Peel a Derived Key
@@ -47,10 +69,11 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>, const Seed: &'static str> Peel<'a, 'b,
 {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
         // Attempt to Derive Seed
-        let (derived, bump) = Pubkey::find_program_address(&[Seed.as_ref()], ctx.this);
-        match derived == *ctx.info().key {
-            true => T::peel(ctx).map(|v| Derive(v)),
-            _ => Err(SolitaireError::InvalidDerive(*ctx.info().key, derived).into()),
+        let info = ctx.info()?;
+        let (derived, bump) = find_program_address(&[Seed.as_ref()], ctx.this);
+        match derived == *info.key {
+            true => T::peel(ctx).map(|v| Derive(v, Bump::new(bump))),
+            _ => Err(SolitaireError::InvalidDerive(*info.key, derived).into()),
         }
     }
 
@@ -61,17 +84,22 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>, const Seed: &'static str> Peel<'a, 'b,
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let mut accounts = T::idl_accounts(name);
+        accounts.iter_mut().for_each(|a| a.seed = Some(Seed.to_string()));
+        accounts
+    }
 }
 
 /// Peel a Mutable key.
 impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Mut<T> {
     fn peel<I>(mut ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
         ctx.immutable = false;
-        match ctx.info().is_writable {
+        let info = ctx.info()?;
+        match info.is_writable {
             true => T::peel(ctx).map(|v| Mut(v)),
-            _ => Err(
-                SolitaireError::InvalidMutability(*ctx.info().key, ctx.info().is_writable).into(),
-            ),
+            _ => Err(SolitaireError::InvalidMutability(*info.key, info.is_writable).into()),
         }
     }
 
@@ -82,11 +110,17 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Mut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let mut accounts = T::idl_accounts(name);
+        accounts.iter_mut().for_each(|a| a.writable = true);
+        accounts
+    }
 }
 
 impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for MaybeMut<T> {
     fn peel<I>(mut ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-        ctx.immutable = !ctx.info().is_writable;
+        ctx.immutable = !ctx.info()?.is_writable;
         T::peel(ctx).map(|v| MaybeMut(v))
     }
 
@@ -97,14 +131,19 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for MaybeMut<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        T::idl_accounts(name)
+    }
 }
 
 /// Peel a Signer.
 impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Signer<T> {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-        match ctx.info().is_signer {
+        let info = ctx.info()?;
+        match info.is_signer {
             true => T::peel(ctx).map(|v| Signer(v)),
-            _ => Err(SolitaireError::InvalidSigner(*ctx.info().key).into()),
+            _ => Err(SolitaireError::InvalidSigner(*info.key).into()),
         }
     }
 
@@ -115,6 +154,12 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Signer<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let mut accounts = T::idl_accounts(name);
+        accounts.iter_mut().for_each(|a| a.signer = true);
+        accounts
+    }
 }
 
 /// Expicitly depend upon the System account.
@@ -133,6 +178,10 @@ impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for System<T> {
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
         T::persist(self, program_id)
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        T::idl_accounts(name)
+    }
 }
 
 /// Peel a Sysvar
@@ -141,12 +190,131 @@ where
     Var: SolanaSysvar,
 {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-        match Var::check_id(ctx.info().key) {
-            true => Ok(Sysvar(
-                ctx.info().clone(),
-                Var::from_account_info(ctx.info())?,
-            )),
-            _ => Err(SolitaireError::InvalidSysvar(*ctx.info().key).into()),
+        let info = ctx.info()?;
+        match Var::check_id(info.key) {
+            true => Ok(Sysvar(info.clone(), Var::from_account_info(info)?)),
+            _ => Err(SolitaireError::InvalidSysvar(*info.key).into()),
+        }
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        vec![]
+    }
+
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
+        Ok(())
+    }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![IdlAccount {
+            address: sysvar_id::<Var>(),
+            ..IdlAccount::new(name)
+        }]
+    }
+}
+
+/// Address of the sysvar `Var`. `SysvarId` can only check an address, so it is looked up among the
+/// known sysvars.
+fn sysvar_id<Var: SysvarId>() -> Option<Pubkey> {
+    let ids = [
+        sysvar::clock::id(),
+        sysvar::epoch_schedule::id(),
+        sysvar::fees::id(),
+        sysvar::instructions::id(),
+        sysvar::recent_blockhashes::id(),
+        sysvar::rent::id(),
+        sysvar::rewards::id(),
+        sysvar::slot_hashes::id(),
+        sysvar::slot_history::id(),
+        sysvar::stake_history::id(),
+    ];
+    ids.iter().copied().find(|id| Var::check_id(id))
+}
+
+/// Peel an account that may be absent.
+impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>> Peel<'a, 'b, 'c> for Optional<T> {
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        match *ctx.info()?.key == ABSENT {
+            true => Ok(Optional(None)),
+            _ => T::peel(ctx).map(|v| Optional(Some(v))),
+        }
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        T::deps()
+    }
+
+    fn persist(&self, program_id: &Pubkey) -> Result<()> {
+        match &self.0 {
+            Some(v) => T::persist(v, program_id),
+            None => Ok(()),
+        }
+    }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let mut accounts = T::idl_accounts(name);
+        accounts.iter_mut().for_each(|a| a.optional = true);
+        accounts
+    }
+}
+
+/// Peel an account to be closed. Rather than persisting it, the accounts struct holding it closes
+/// it into the recipient, see `Close::close`.
+impl<'a, 'b: 'a, 'c, T: Peel<'a, 'b, 'c>, const Recipient: &'static str> Peel<'a, 'b, 'c>
+    for Close<T, Recipient>
+{
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        T::peel(ctx).map(|v| Close(v))
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        T::deps()
+    }
+
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
+        Ok(())
+    }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        T::idl_accounts(name)
+    }
+}
+
+/// Peel a list of accounts, each checked as a `T`. Takes the number of accounts set on the context,
+/// or all remaining accounts.
+impl<'a, 'b: 'a, 'c, T> Peel<'a, 'b, 'c> for Vec<T>
+where
+    T: for<'d> Peel<'a, 'b, 'd>,
+{
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        let len = ctx.len.unwrap_or_else(|| ctx.iter.len());
+        (0..len)
+            .map(|_| T::peel(&mut Context::new(ctx.this, &mut *ctx.iter, ctx.data, ctx.field)))
+            .collect()
+    }
+
+    fn deps() -> Vec<Pubkey> {
+        T::deps()
+    }
+
+    fn persist(&self, program_id: &Pubkey) -> Result<()> {
+        self.iter().try_for_each(|v| v.persist(program_id))
+    }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        let mut accounts = T::idl_accounts(name);
+        accounts.iter_mut().for_each(|a| a.many = true);
+        accounts
+    }
+}
+
+/// Peel a Program
+impl<'a, 'b: 'a, 'c, Id: ProgramId> Peel<'a, 'b, 'c> for Program<'b, Id> {
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        let info = ctx.info()?;
+        match *info.key == Id::id() {
+            true => Ok(Program(info.clone(), PhantomData)),
+            _ => Err(SolitaireError::InvalidProgram(*info.key)),
         }
     }
 
@@ -157,19 +325,25 @@ where
     fn persist(&self, _program_id: &Pubkey) -> Result<()> {
         Ok(())
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![IdlAccount {
+            address: Some(Id::id()),
+            ..IdlAccount::new(name)
+        }]
+    }
 }
 
 /// This is our structural recursion base case, the trait system will stop generating new nested
 /// calls here.
 impl<'a, 'b: 'a, 'c> Peel<'a, 'b, 'c> for Info<'b> {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-        if ctx.immutable && ctx.info().is_writable {
-            return Err(
-                SolitaireError::InvalidMutability(*ctx.info().key, ctx.info().is_writable).into(),
-            );
+        let info = ctx.info()?;
+        if ctx.immutable && info.is_writable {
+            return Err(SolitaireError::InvalidMutability(*info.key, info.is_writable).into());
         }
 
-        Ok(ctx.info().clone())
+        Ok(info.clone())
     }
     fn deps() -> Vec<Pubkey> {
         vec![]
@@ -185,54 +359,43 @@ impl<
         'a,
         'b: 'a,
         'c,
-        T: BorshDeserialize + BorshSerialize + Owned + Default,
+        T: BorshDeserialize + BorshSerialize + Owned + Default + Discriminated,
         const IsInitialized: AccountState,
     > Peel<'a, 'b, 'c> for Data<'b, T, IsInitialized>
 {
     fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
-        if ctx.immutable && ctx.info().is_writable {
-            return Err(
-                SolitaireError::InvalidMutability(*ctx.info().key, ctx.info().is_writable).into(),
-            );
+        let info = ctx.info()?;
+        if ctx.immutable && info.is_writable {
+            return Err(SolitaireError::InvalidMutability(*info.key, info.is_writable).into());
         }
 
         // If we're initializing the type, we should emit system/rent as deps.
         let (initialized, data): (bool, T) = match IsInitialized {
             AccountState::Uninitialized => {
-                if **ctx.info().lamports.borrow() != 0 {
-                    return Err(SolitaireError::AlreadyInitialized(*ctx.info().key));
+                if is_created(info) {
+                    return Err(SolitaireError::AlreadyInitialized(*info.key));
                 }
                 (false, T::default())
             }
-            AccountState::Initialized => {
-                (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
-            }
+            AccountState::Initialized => (true, deserialize_data(info)?),
             AccountState::MaybeInitialized => {
-                if **ctx.info().lamports.borrow() == 0 {
+                if !is_created(info) {
                     (false, T::default())
                 } else {
-                    (true, T::try_from_slice(&mut *ctx.info().data.borrow_mut())?)
+                    (true, deserialize_data(info)?)
                 }
             }
         };
 
         if initialized {
-            match data.owner() {
-                AccountOwner::This => {
-                    if ctx.info().owner != ctx.this {
-                        return Err(SolitaireError::InvalidOwner(*ctx.info().owner));
-                    }
-                }
-                AccountOwner::Other(v) => {
-                    if *ctx.info().owner != v {
-                        return Err(SolitaireError::InvalidOwner(*ctx.info().owner));
-                    }
-                }
-                AccountOwner::Any => {}
-            };
+            check_owner(info, data.owner(), ctx.this)?;
         }
 
-        Ok(Data(Box::new(ctx.info().clone()), data))
+        let dirty = match initialized {
+            true => Dirty::Clean,
+            _ => Dirty::Fresh,
+        };
+        Ok(Data(Box::new(info.clone()), data, Bump::default(), dirty))
     }
 
     fn deps() -> Vec<Pubkey> {
@@ -244,18 +407,191 @@ impl<
     }
 
     fn persist(&self, program_id: &Pubkey) -> Result<()> {
-        // TODO: Introduce Mut<> to solve the check we really want to make here.
-        if self.0.owner != program_id {
-            return Ok(());
+        // Only accounts this program owns and is allowed to write to can be written back. Losing
+        // changes to any other account is a bug in the handler.
+        let writable = self.0.owner == program_id && self.0.is_writable;
+        match self.3 {
+            Dirty::Clean => return Ok(()),
+            Dirty::Fresh if !writable => return Ok(()),
+            Dirty::Modified if !writable => {
+                return Err(SolitaireError::UnpersistedChanges(*self.0.key));
+            }
+            _ => {}
+        }
+
+        // Accounts of legacy types that predate their tag have no room for it, and are kept in
+        // their untagged layout for as long as the value fits it.
+        let tag = self.1.discriminator();
+        let mut body = self.1.try_to_vec()?;
+        let mut data = self.0.data.borrow_mut();
+        let mut writer: &mut [u8] = &mut *data;
+        if !T::LEGACY || writer.len() >= tag.len() + body.len() {
+            writer.write_all(tag)?;
+        } else {
+            body = self
+                .1
+                .untagged()
+                .ok_or(SolitaireError::AccountTooSmall(*self.0.key))?;
+        }
+        if writer.len() < body.len() {
+            return Err(SolitaireError::AccountTooSmall(*self.0.key));
         }
+        writer.write_all(&body)?;
+
+        Ok(())
+    }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![data_idl_account::<T>(name, IsInitialized)]
+    }
+}
 
-        // It is also a malformed program to attempt to write to a non-writeable account.
-        if !self.0.is_writable {
-            return Ok(());
+/// Peel a read-only view of an initialized account, deserializing only its header.
+impl<'a, 'b: 'a, 'c, T: Viewable> Peel<'a, 'b, 'c> for View<'b, T> {
+    fn peel<I>(ctx: &'c mut Context<'a, 'b, 'c, I>) -> Result<Self> {
+        let info = ctx.info()?;
+        if info.is_writable {
+            return Err(SolitaireError::InvalidMutability(*info.key, info.is_writable));
         }
+        check_owner(info, T::default().owner(), ctx.this)?;
+
+        let (header, payload) = {
+            let data = info.data.borrow();
+            let (_, mut rest) = strip_discriminator::<T>(info.key, &data)?;
+            let header = T::Header::deserialize(&mut rest)?;
+            let len = u32::deserialize(&mut rest)? as usize;
+            if rest.len() < len {
+                return Err(SolitaireError::IoError(ErrorKind::UnexpectedEof.into()));
+            }
+            let start = data.len() - rest.len();
+            (header, start..start + len)
+        };
+
+        Ok(View(info.clone(), header, payload, PhantomData))
+    }
 
-        self.1.serialize(&mut *self.0.data.borrow_mut())?;
+    fn deps() -> Vec<Pubkey> {
+        vec![]
+    }
 
+    fn persist(&self, _program_id: &Pubkey) -> Result<()> {
         Ok(())
     }
+
+    fn idl_accounts(name: &str) -> Vec<IdlAccount> {
+        vec![data_idl_account::<T>(name, AccountState::Initialized)]
+    }
+}
+
+fn data_idl_account<T>(name: &str, state: AccountState) -> IdlAccount {
+    // Only the type name itself, the full path is an implementation detail of the program.
+    let data = std::any::type_name::<T>();
+    let data = data.rsplit("::").next().unwrap_or(data).trim_end_matches('>');
+    IdlAccount {
+        data: Some(data.to_string()),
+        state: Some(format!("{:?}", state)),
+        ..IdlAccount::new(name)
+    }
+}
+
+fn check_owner(info: &Info, owner: AccountOwner, this: &Pubkey) -> Result<()> {
+    let valid = match owner {
+        AccountOwner::This => info.owner == this,
+        AccountOwner::Other(v) => *info.owner == v,
+        AccountOwner::Any => true,
+    };
+    match valid {
+        true => Ok(()),
+        _ => Err(SolitaireError::InvalidOwner(*info.owner)),
+    }
+}
+
+/// Deserialize the data of an initialized account, checking its tag.
+fn deserialize_data<T: BorshDeserialize + BorshSerialize + Discriminated>(
+    info: &Info,
+) -> Result<T> {
+    deserialize_account(info.key, &info.data.borrow())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::account_info::AccountInfo;
+
+    #[derive(Default, BorshSerialize, BorshDeserialize)]
+    struct Value {
+        a: u64,
+    }
+
+    impl Owned for Value {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl Discriminated for Value {
+        const DISCRIMINATOR: &'static [u8] = b"val";
+    }
+
+    /// Persist an account holding `data`, after setting its value if given. Returns the data after.
+    fn persist(
+        writable: bool,
+        dirty: Dirty,
+        value: Option<u64>,
+        data: &[u8],
+    ) -> (Result<()>, Vec<u8>) {
+        let (key, program) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut data) = (1, data.to_vec());
+        let info = AccountInfo::new(
+            &key,
+            false,
+            writable,
+            &mut lamports,
+            &mut data,
+            &program,
+            false,
+            0,
+        );
+        let mut account: Data<'_, Value, { AccountState::Initialized }> =
+            Data(Box::new(info), Value { a: 1 }, Bump::default(), dirty);
+        if let Some(value) = value {
+            account.a = value;
+        }
+        let result = account.persist(&program);
+        let data = account.0.data.borrow().to_vec();
+        (result, data)
+    }
+
+    #[test]
+    fn persist_modified() {
+        let (result, data) = persist(true, Dirty::Clean, Some(2), &[0; 11]);
+        result.unwrap();
+        assert_eq!(data, [&b"val"[..], &2u64.to_le_bytes()].concat());
+
+        // Changes to an account the program can't write must not be dropped silently.
+        let (result, data) = persist(false, Dirty::Clean, Some(2), &[0; 11]);
+        assert!(matches!(result, Err(SolitaireError::UnpersistedChanges(_))));
+        assert_eq!(data, [0; 11]);
+    }
+
+    #[test]
+    fn persist_clean() {
+        // Values only read are not written back, even though the account holds something else.
+        let (result, data) = persist(true, Dirty::Clean, None, &[7; 11]);
+        result.unwrap();
+        assert_eq!(data, [7; 11]);
+
+        let (result, data) = persist(false, Dirty::Clean, None, &[7; 11]);
+        result.unwrap();
+        assert_eq!(data, [7; 11]);
+
+        // Default values of accounts that weren't created are only written when writable.
+        let (result, data) = persist(false, Dirty::Fresh, None, &[7; 11]);
+        result.unwrap();
+        assert_eq!(data, [7; 11]);
+
+        let (result, data) = persist(true, Dirty::Fresh, None, &[7; 11]);
+        result.unwrap();
+        assert_eq!(data, [&b"val"[..], &1u64.to_le_bytes()].concat());
+    }
 }
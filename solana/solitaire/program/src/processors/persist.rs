@@ -1,5 +1,110 @@
 use solana_program::pubkey::Pubkey;
 
+use crate::{
+    Close,
+    Info,
+    Keyed,
+    Result,
+    SolitaireError,
+};
+
 pub trait Persist {
     fn persist(&self, program_id: &Pubkey) -> crate::Result<()>;
 }
+
+impl<'a, 'b: 'a, T: Keyed<'a, 'b>, const Recipient: &'static str> Close<T, Recipient> {
+    /// Zero the account and move its lamports to `recipient`, in place of persisting it. The
+    /// runtime purges the drained account once the transaction completes.
+    pub fn close(&'a self, recipient: &Info<'b>) -> Result<()> {
+        let account = self.0.info();
+        if account.key == recipient.key {
+            return Err(SolitaireError::InvalidCloseRecipient(*recipient.key));
+        }
+
+        let lamports = recipient
+            .lamports()
+            .checked_add(account.lamports())
+            .ok_or(SolitaireError::MathOverflow)?;
+
+        account.data.borrow_mut().iter_mut().for_each(|b| *b = 0);
+
+        **recipient.lamports.borrow_mut() = lamports;
+        **account.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_program::account_info::AccountInfo;
+
+    #[test]
+    fn close() {
+        let (key, recipient_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let owner = Pubkey::new_unique();
+        let (mut lamports, mut data) = (10, vec![1, 2, 3]);
+        let (mut recipient_lamports, mut recipient_data) = (5, vec![]);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let recipient = AccountInfo::new(
+            &recipient_key,
+            false,
+            true,
+            &mut recipient_lamports,
+            &mut recipient_data,
+            &owner,
+            false,
+            0,
+        );
+
+        let account: Close<Info, "recipient"> = Close(info);
+        account.close(&recipient).unwrap();
+        assert_eq!(account.0.lamports(), 0);
+        assert_eq!(*account.0.data.borrow(), &[0, 0, 0]);
+        assert_eq!(recipient.lamports(), 15);
+    }
+
+    #[test]
+    fn close_into_itself() {
+        let (key, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (mut lamports, mut data) = (10, vec![1]);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+
+        let account: Close<Info, "recipient"> = Close(info.clone());
+        assert!(matches!(
+            account.close(&info),
+            Err(SolitaireError::InvalidCloseRecipient(_))
+        ));
+        assert_eq!(info.lamports(), 10);
+        assert_eq!(*info.data.borrow(), &[1]);
+    }
+
+    #[test]
+    fn close_overflow() {
+        let (key, recipient_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let owner = Pubkey::new_unique();
+        let (mut lamports, mut data) = (2, vec![1]);
+        let (mut recipient_lamports, mut recipient_data) = (u64::MAX - 1, vec![]);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let recipient = AccountInfo::new(
+            &recipient_key,
+            false,
+            true,
+            &mut recipient_lamports,
+            &mut recipient_data,
+            &owner,
+            false,
+            0,
+        );
+
+        let account: Close<Info, "recipient"> = Close(info);
+        assert!(matches!(
+            account.close(&recipient),
+            Err(SolitaireError::MathOverflow)
+        ));
+        assert_eq!(account.0.lamports(), 2);
+        assert_eq!(*account.0.data.borrow(), &[1]);
+        assert_eq!(recipient.lamports(), u64::MAX - 1);
+    }
+}
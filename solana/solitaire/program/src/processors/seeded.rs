@@ -1,12 +1,14 @@
 use super::keyed::Keyed;
 use crate::{
     system_instruction,
+    types::is_created,
     AccountInfo,
     AccountState,
     CreationLamports,
     Data,
     Deref,
     Derive,
+    Discriminated,
     ExecutionContext,
     FromAccounts,
     Info,
@@ -25,9 +27,16 @@ use solana_program::{
     entrypoint::ProgramResult,
     instruction::Instruction,
     msg,
-    program::invoke_signed,
-    pubkey::Pubkey,
+    program::{
+        invoke,
+        invoke_signed,
+    },
+    pubkey::{
+        Pubkey,
+        PubkeyError,
+    },
 };
+use std::cell::Cell;
 
 pub trait AccountSize {
     fn size(&self) -> usize;
@@ -59,6 +68,49 @@ impl<'a, T: Owned + Default, const IsInitialized: AccountState> Owned
     }
 }
 
+/// Bump seed of a derived account, kept by the account once its derivation is known so signing for
+/// it or re-checking it later in the instruction doesn't search for the bump again.
+#[derive(Default)]
+pub struct Bump(Cell<Option<u8>>);
+
+impl Bump {
+    pub fn new(bump: u8) -> Self {
+        Bump(Cell::new(Some(bump)))
+    }
+
+    pub fn get(&self) -> Option<u8> {
+        self.0.get()
+    }
+
+    pub fn set(&self, bump: u8) {
+        self.0.set(Some(bump))
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Addresses derived on this thread, each costing the runtime one `create_program_address`.
+    static ATTEMPTS: Cell<u64> = Cell::new(0);
+}
+
+/// `Pubkey::find_program_address`, which derives an address for every bump from the highest down
+/// until one is valid.
+pub(crate) fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> (Pubkey, u8) {
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    #[cfg(test)]
+    ATTEMPTS.with(|attempts| attempts.set(attempts.get() + u64::from(u8::MAX - bump) + 1));
+    (address, bump)
+}
+
+fn create_program_address(
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> std::result::Result<Pubkey, PubkeyError> {
+    #[cfg(test)]
+    ATTEMPTS.with(|attempts| attempts.set(attempts.get() + 1));
+    Pubkey::create_program_address(seeds, program_id)
+}
+
 pub trait Seeded<I> {
     fn seeds(accs: I) -> Vec<Vec<u8>>;
 
@@ -67,12 +119,15 @@ pub trait Seeded<I> {
     }
 
     fn key(accs: I, program_id: &Pubkey) -> Pubkey {
-        let mut seeds = Self::seeds(accs);
-        let mut s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
-        let mut seed_slice = s.as_slice();
-        let (addr, _) = Pubkey::find_program_address(seed_slice, program_id);
+        Self::key_and_bump(accs, program_id).0
+    }
 
-        addr
+    /// Address of the account along with the bump it is derived with, which clients can pass in
+    /// instruction data to spare the program searching for it.
+    fn key_and_bump(accs: I, program_id: &Pubkey) -> (Pubkey, u8) {
+        let seeds = Self::seeds(accs);
+        let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        Pubkey::find_program_address(s.as_slice(), program_id)
     }
 
     fn bumped_seeds(accs: I, program_id: &Pubkey) -> Vec<Vec<u8>> {
@@ -85,24 +140,90 @@ pub trait Seeded<I> {
         seeds
     }
 
-    fn self_bumped_seeds(&self, accs: I, program_id: &Pubkey) -> Vec<Vec<u8>> {
-        Self::bumped_seeds(accs, program_id)
+    /// Seeds of this account including its bump, reusing the bump found by an earlier derivation.
+    fn self_bumped_seeds<'a, 'b: 'a>(&'a self, accs: I, program_id: &Pubkey) -> Vec<Vec<u8>>
+    where
+        Self: Keyed<'a, 'b>,
+    {
+        let mut seeds = Self::seeds(accs);
+        let bump = match self.bump().and_then(Bump::get) {
+            Some(bump) => bump,
+            None => {
+                let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+                let (derived, bump) = find_program_address(s.as_slice(), program_id);
+                // Only remember bumps of seeds that actually belong to this account.
+                let _ = self.check_derived(derived, bump);
+                bump
+            }
+        };
+        seeds.push(vec![bump]);
+
+        seeds
     }
 
     fn verify_derivation<'a, 'b: 'a>(&'a self, program_id: &'a Pubkey, accs: I) -> Result<()>
     where
         Self: Keyed<'a, 'b>,
     {
+        if let Some(bump) = self.bump().and_then(Bump::get) {
+            return self.verify_derivation_with_bump(program_id, accs, bump);
+        }
+
         let seeds = Self::seeds(accs);
         let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
         let seed_slice = s.as_slice();
 
-        let (derived, bump) = Pubkey::find_program_address(seed_slice, program_id);
-        if &derived == self.info().key {
-            Ok(())
-        } else {
-            Err(SolitaireError::InvalidDerive(*self.info().key, derived))
+        let (derived, bump) = find_program_address(seed_slice, program_id);
+        self.check_derived(derived, bump)
+    }
+
+    /// Verify the derivation against a known bump, such as one passed in instruction data, which
+    /// is much cheaper than searching for it.
+    fn verify_derivation_with_bump<'a, 'b: 'a>(
+        &'a self,
+        program_id: &'a Pubkey,
+        accs: I,
+        bump: u8,
+    ) -> Result<()>
+    where
+        Self: Keyed<'a, 'b>,
+    {
+        let mut seeds = Self::seeds(accs);
+        seeds.push(vec![bump]);
+        let derive = |seeds: &[Vec<u8>]| {
+            let s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+            create_program_address(s.as_slice(), program_id)
+        };
+
+        // Bumps that don't yield a valid program address can never match the account.
+        let derived = derive(&seeds).unwrap_or_default();
+
+        // Seeds have several valid bumps but accounts are only ever created at the canonical one,
+        // the highest, otherwise the same seeds could create a second account. Existing accounts
+        // were checked when they were created.
+        if &derived == self.info().key && !is_created(self.info()) {
+            for higher in (bump..u8::MAX).map(|b| b + 1) {
+                *seeds.last_mut().unwrap() = vec![higher];
+                if let Ok(canonical) = derive(&seeds) {
+                    return Err(SolitaireError::InvalidDerive(*self.info().key, canonical));
+                }
+            }
         }
+
+        self.check_derived(derived, bump)
+    }
+
+    fn check_derived<'a, 'b: 'a>(&'a self, derived: Pubkey, bump: u8) -> Result<()>
+    where
+        Self: Keyed<'a, 'b>,
+    {
+        if &derived != self.info().key {
+            return Err(SolitaireError::InvalidDerive(*self.info().key, derived));
+        }
+        if let Some(cached) = self.bump() {
+            cached.set(bump);
+        }
+        Ok(())
     }
 }
 
@@ -116,11 +237,11 @@ pub trait Creatable<'a, I> {
     ) -> Result<()>;
 }
 
-impl<T: BorshSerialize + Owned + Default, const IsInitialized: AccountState> AccountSize
-    for Data<'_, T, IsInitialized>
+impl<T: BorshSerialize + Owned + Default + Discriminated, const IsInitialized: AccountState>
+    AccountSize for Data<'_, T, IsInitialized>
 {
     fn size(&self) -> usize {
-        self.1.try_to_vec().unwrap().len()
+        self.1.discriminator().len() + self.1.try_to_vec().unwrap().len()
     }
 }
 
@@ -132,22 +253,53 @@ impl<'a, 'b: 'a, K, T: AccountSize + Seeded<K> + Keyed<'a, 'b> + Owned> Creatabl
         payer: &'a Pubkey,
         lamports: CreationLamports,
     ) -> Result<()> {
-        let seeds = T::bumped_seeds(accs, ctx.program_id);
+        let seeds = self.self_bumped_seeds(accs, ctx.program_id);
         let size = self.size();
 
         let mut s: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
         let mut seed_slice = s.as_slice();
 
-        let ix = system_instruction::create_account(
+        create_account_seeded(
+            ctx,
             payer,
-            self.info().key,
-            lamports.amount(size),
-            size as u64,
+            self.info(),
+            lamports.amount(size)?,
+            size,
             &self.owner_pubkey(ctx.program_id)?,
-        );
+            seed_slice,
+        )
+    }
+}
+
+/// Create the account at a program address, signing for it with `seeds`. Anyone can transfer
+/// lamports to an address before it is created, which makes `create_account` fail, so an account
+/// already holding lamports is topped up to `lamports`, then allocated and assigned instead.
+pub fn create_account_seeded(
+    ctx: &ExecutionContext,
+    payer: &Pubkey,
+    account: &AccountInfo,
+    lamports: u64,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let balance = account.lamports();
+    if balance == 0 {
+        let ix =
+            system_instruction::create_account(payer, account.key, lamports, space as u64, owner);
+        return Ok(invoke_signed(&ix, ctx.accounts, &[seeds])?);
+    }
 
-        Ok(invoke_signed(&ix, ctx.accounts, &[seed_slice])?)
+    if balance < lamports {
+        let ix = system_instruction::transfer(payer, account.key, lamports - balance);
+        invoke(&ix, ctx.accounts)?;
     }
+    let ix = system_instruction::allocate(account.key, space as u64);
+    invoke_signed(&ix, ctx.accounts, &[seeds])?;
+    let ix = system_instruction::assign(account.key, owner);
+    invoke_signed(&ix, ctx.accounts, &[seeds])?;
+
+    Ok(())
 }
 
 impl<'a, const Seed: &'static str, T> Seeded<Option<()>> for Derive<T, Seed> {
@@ -156,10 +308,10 @@ impl<'a, const Seed: &'static str, T> Seeded<Option<()>> for Derive<T, Seed> {
     }
 }
 
-pub fn invoke_seeded<I, T: Seeded<I>>(
+pub fn invoke_seeded<'a, 'b: 'a, I, T: Seeded<I> + Keyed<'a, 'b>>(
     instruction: &Instruction,
     context: &ExecutionContext,
-    seeded_acc: &T,
+    seeded_acc: &'a T,
     accs: I,
 ) -> ProgramResult {
     let seeds = seeded_acc.self_bumped_seeds(accs, context.program_id);
@@ -167,3 +319,88 @@ pub fn invoke_seeded<I, T: Seeded<I>>(
     let seed_slice = s.as_slice();
     invoke_signed(instruction, context.accounts, &[seed_slice])
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Seed<'b> = Derive<Info<'b>, "seed">;
+
+    /// A program for which the seed has a valid bump below its canonical one.
+    fn program_with_bumps() -> (Pubkey, u8, u8) {
+        loop {
+            let program = Pubkey::new_unique();
+            let (_, canonical) = Pubkey::find_program_address(&[b"seed"], &program);
+            let lower = (0..canonical).rev().find(|bump| {
+                Pubkey::create_program_address(&[b"seed", &[*bump]], &program).is_ok()
+            });
+            if let Some(lower) = lower {
+                return (program, canonical, lower);
+            }
+        }
+    }
+
+    #[test]
+    fn non_canonical_bump() {
+        let (program, canonical, lower) = program_with_bumps();
+        let key = Pubkey::create_program_address(&[b"seed", &[lower]], &program).unwrap();
+        let (mut lamports, mut data) = (0, vec![]);
+        let system = solana_program::system_program::id();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &system, false, 0);
+
+        // The account could be created at an address no other instruction derives.
+        let account: Seed = Derive(info, Bump::default());
+        assert!(matches!(
+            account.verify_derivation_with_bump(&program, None, lower),
+            Err(SolitaireError::InvalidDerive(_, _))
+        ));
+        assert_eq!(account.1.get(), None);
+
+        let key = Pubkey::create_program_address(&[b"seed", &[canonical]], &program).unwrap();
+        let (mut lamports, mut data) = (0, vec![]);
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &system, false, 0);
+        let account: Seed = Derive(info, Bump::default());
+        account
+            .verify_derivation_with_bump(&program, None, canonical)
+            .unwrap();
+        assert_eq!(account.1.get(), Some(canonical));
+    }
+
+    /// Addresses derived while running `f`.
+    fn attempts(f: impl FnOnce()) -> u64 {
+        let before = ATTEMPTS.with(Cell::get);
+        f();
+        ATTEMPTS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn cached_bump_is_cheaper() {
+        // The more bumps the search tries, the more a known bump saves.
+        let (program, canonical) = loop {
+            let program = Pubkey::new_unique();
+            let (_, bump) = Pubkey::find_program_address(&[b"seed"], &program);
+            if bump < u8::MAX - 4 {
+                break (program, bump);
+            }
+        };
+        let (key, _) = Pubkey::find_program_address(&[b"seed"], &program);
+        let (mut lamports, mut data) = (1, vec![0]);
+        let info =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program, false, 0);
+
+        // The bump is searched for once, signing for the account reuses it.
+        let account: Seed = Derive(info.clone(), Bump::default());
+        let searched = attempts(|| {
+            account.verify_derivation(&program, None).unwrap();
+            account.self_bumped_seeds(None, &program);
+        });
+        assert_eq!(searched, u64::from(u8::MAX - canonical) + 1);
+
+        let account: Seed = Derive(info, Bump::new(canonical));
+        let cached = attempts(|| {
+            account.verify_derivation(&program, None).unwrap();
+            account.self_bumped_seeds(None, &program);
+        });
+        assert_eq!(cached, 1);
+    }
+}
@@ -0,0 +1,285 @@
+//! Versioned accounts.
+//!
+//! Borsh structs carry no description of their layout, so adding a field to an account type makes
+//! every existing account of it fail to deserialize. `Versioned` stores a layout version in front
+//! of the value and upgrades data stored in an older layout when it is read. The upgraded value
+//! is written back in the current layout the next time the account is persisted.
+//!
+//! Resizing accounts is not supported, as the runtime this is built for can't, so a layout can
+//! only grow into space the account already has. Writing a value that no longer fits fails with
+//! `AccountTooSmall`.
+
+use borsh::{
+    BorshDeserialize,
+    BorshSerialize,
+};
+use std::{
+    io::{
+        Error,
+        ErrorKind,
+        Write,
+    },
+    ops::{
+        Deref,
+        DerefMut,
+    },
+};
+
+use crate::processors::{
+    discriminated::Discriminated,
+    seeded::{
+        AccountOwner,
+        Owned,
+    },
+};
+
+/// Account types whose layout is versioned.
+///
+/// Version 0 is the layout of accounts stored before the type was versioned, which have neither a
+/// version nor a tag. Bump `VERSION` whenever the layout changes, and teach `migrate` to read the
+/// previous one, usually by deserializing the old struct and converting it:
+///
+/// ```ignore
+/// fn migrate(version: u8, data: &mut &[u8]) -> io::Result<Self> {
+///     match version {
+///         0 => ConfigV0::deserialize(data).map(Into::into),
+///         1 => ConfigV1::deserialize(data).map(Into::into),
+///         _ => Err(io::ErrorKind::InvalidData.into()),
+///     }
+/// }
+/// ```
+///
+/// Legacy accounts are sized for version 0 and can't grow, implement `downgrade` to keep writing
+/// them for as long as the value still fits that layout.
+pub trait Migrate: BorshDeserialize {
+    /// Version of the layout `Self` serializes to.
+    const VERSION: u8;
+
+    /// Read data stored in the layout of an older `version`, upgrading it to `Self`. Types still in
+    /// the layout of version 0 have no older layouts to read.
+    fn migrate(version: u8, data: &mut &[u8]) -> std::io::Result<Self> {
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unknown layout version {}", version),
+        ))
+    }
+
+    /// Serialize the value in the layout of version 0, for legacy accounts that have no room for
+    /// the current layout. `None` if the value can't be represented in it, such as when a field
+    /// added since holds anything but the value `migrate` fills in.
+    fn downgrade(&self) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+/// A `T` stored behind its layout version.
+#[derive(Default)]
+pub struct Versioned<T>(pub T);
+
+impl<T> Deref for Versioned<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Versioned<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: Migrate> Versioned<T> {
+    /// Read a value in the given layout `version`.
+    fn read(version: u8, data: &mut &[u8]) -> std::io::Result<Self> {
+        if version == T::VERSION {
+            T::deserialize(data).map(Versioned)
+        } else if version < T::VERSION {
+            T::migrate(version, data).map(Versioned)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("unknown layout version {}", version),
+            ))
+        }
+    }
+}
+
+impl<T: Migrate + BorshSerialize> BorshSerialize for Versioned<T> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        T::VERSION.serialize(writer)?;
+        self.0.serialize(writer)
+    }
+}
+
+impl<T: Migrate> BorshDeserialize for Versioned<T> {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let version = u8::deserialize(buf)?;
+        Self::read(version, buf)
+    }
+}
+
+impl<T: Owned> Owned for Versioned<T> {
+    fn owner(&self) -> AccountOwner {
+        self.0.owner()
+    }
+}
+
+/// Versioned accounts keep the tag of `T`. Legacy accounts without a tag predate versioning, so
+/// they are in the layout of version 0.
+impl<T: Discriminated + Migrate + BorshSerialize> Discriminated for Versioned<T> {
+    const DISCRIMINATOR: &'static [u8] = T::DISCRIMINATOR;
+    const LEGACY: bool = T::LEGACY;
+    const KNOWN: &'static [&'static [u8]] = T::KNOWN;
+
+    fn discriminator(&self) -> &'static [u8] {
+        self.0.discriminator()
+    }
+
+    fn from_untagged(data: &[u8]) -> std::io::Result<Self> {
+        let mut data = data;
+        let value = Self::read(0, &mut data)?;
+        match data.is_empty() {
+            true => Ok(value),
+            _ => Err(ErrorKind::InvalidData.into()),
+        }
+    }
+
+    fn untagged(&self) -> Option<Vec<u8>> {
+        match T::VERSION {
+            0 => self.0.try_to_vec().ok(),
+            _ => self.0.downgrade(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        from_account_data,
+        processors::seeded::Bump,
+        AccountState,
+        Data,
+        Dirty,
+        Peel,
+        SolitaireError,
+    };
+    use solana_program::{
+        account_info::AccountInfo,
+        pubkey::Pubkey,
+    };
+
+    #[derive(BorshSerialize, BorshDeserialize)]
+    struct ConfigV0 {
+        a: u32,
+    }
+
+    #[derive(Debug, Default, PartialEq, BorshSerialize, BorshDeserialize)]
+    struct Config {
+        a: u32,
+        b: u64,
+    }
+
+    impl Owned for Config {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl Discriminated for Config {
+        const DISCRIMINATOR: &'static [u8] = b"cfg";
+        const LEGACY: bool = true;
+    }
+
+    impl Migrate for Config {
+        const VERSION: u8 = 1;
+
+        fn migrate(version: u8, data: &mut &[u8]) -> std::io::Result<Self> {
+            match version {
+                0 => ConfigV0::deserialize(data).map(|v0| Config { a: v0.a, b: 7 }),
+                _ => Err(ErrorKind::InvalidData.into()),
+            }
+        }
+
+        fn downgrade(&self) -> Option<Vec<u8>> {
+            match self.b {
+                7 => ConfigV0 { a: self.a }.try_to_vec().ok(),
+                _ => None,
+            }
+        }
+    }
+
+    fn persist(data: &mut [u8], value: Config) -> crate::Result<()> {
+        let (key, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut lamports = 1;
+        let info = AccountInfo::new(&key, false, true, &mut lamports, data, &owner, false, 0);
+        let account: Data<'_, Versioned<Config>, { AccountState::Initialized }> = Data(
+            Box::new(info),
+            Versioned(value),
+            Bump::default(),
+            Dirty::Modified,
+        );
+        account.persist(&owner)
+    }
+
+    #[test]
+    fn from_untagged() {
+        let legacy = ConfigV0 { a: 1 }.try_to_vec().unwrap();
+        let config = Versioned::<Config>::from_untagged(&legacy).unwrap();
+        assert_eq!(config.0, Config { a: 1, b: 7 });
+
+        // Legacy data must be exactly one version 0 value.
+        let mut trailing = legacy.clone();
+        trailing.push(0);
+        assert!(Versioned::<Config>::from_untagged(&trailing).is_err());
+        assert!(Versioned::<Config>::from_untagged(&legacy[..3]).is_err());
+    }
+
+    #[test]
+    fn upgrade_on_read() {
+        // Tagged data in an older layout is upgraded.
+        let mut data = b"cfg".to_vec();
+        data.push(0);
+        data.extend(ConfigV0 { a: 2 }.try_to_vec().unwrap());
+        let config: Versioned<Config> = from_account_data(&data).unwrap();
+        assert_eq!(config.0, Config { a: 2, b: 7 });
+
+        // Untagged legacy data is in the layout of version 0.
+        let legacy = ConfigV0 { a: 3 }.try_to_vec().unwrap();
+        let config: Versioned<Config> = from_account_data(&legacy).unwrap();
+        assert_eq!(config.0, Config { a: 3, b: 7 });
+
+        // Data in the current layout is read as is.
+        let mut data = b"cfg".to_vec();
+        data.extend(Versioned(Config { a: 4, b: 5 }).try_to_vec().unwrap());
+        let config: Versioned<Config> = from_account_data(&data).unwrap();
+        assert_eq!(config.0, Config { a: 4, b: 5 });
+
+        // Layouts newer than the program are rejected.
+        data[3] = 2;
+        assert!(from_account_data::<Versioned<Config>>(&data).is_err());
+    }
+
+    #[test]
+    fn persist_legacy_account() {
+        // Legacy accounts are kept in the version 0 layout while the value fits it.
+        let mut data = ConfigV0 { a: 1 }.try_to_vec().unwrap();
+        persist(&mut data, Config { a: 2, b: 7 }).unwrap();
+        assert_eq!(data, ConfigV0 { a: 2 }.try_to_vec().unwrap());
+
+        // Values the version 0 layout can't hold don't fit the account.
+        let mut data = ConfigV0 { a: 1 }.try_to_vec().unwrap();
+        assert!(matches!(
+            persist(&mut data, Config { a: 2, b: 8 }),
+            Err(SolitaireError::AccountTooSmall(_))
+        ));
+
+        // Accounts with room for the current layout are tagged and upgraded.
+        let mut data = vec![0u8; 3 + 1 + 12];
+        persist(&mut data, Config { a: 2, b: 8 }).unwrap();
+        let config: Versioned<Config> = from_account_data(&data).unwrap();
+        assert_eq!(&data[..4], b"cfg\x01");
+        assert_eq!(config.0, Config { a: 2, b: 8 });
+    }
+}
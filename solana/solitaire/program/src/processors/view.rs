@@ -0,0 +1,20 @@
+//! Views.
+//!
+//! `Data` deserializes the whole account when it is peeled, which for accounts carrying large
+//! payloads costs heap and compute that instructions reading only a few fields never need. A
+//! `View` deserializes just the header of such an account and borrows its payload straight from
+//! the account data.
+
+use borsh::BorshDeserialize;
+
+use crate::processors::{
+    discriminated::Discriminated,
+    seeded::Owned,
+};
+
+/// Types stored as a fixed layout header followed by a length prefixed payload, as Borsh writes a
+/// `Vec<u8>` field. Fields after the payload are not readable through a view.
+pub trait Viewable: Discriminated + Owned + Default {
+    /// Fields stored in front of the payload, in the order `Self` serializes them.
+    type Header: BorshDeserialize;
+}
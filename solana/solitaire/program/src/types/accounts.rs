@@ -7,22 +7,38 @@
 use borsh::BorshSerialize;
 use solana_program::{
     account_info::AccountInfo,
-    program::invoke_signed,
+    program::invoke,
     pubkey::Pubkey,
     system_instruction,
+    system_program,
     sysvar::Sysvar as SolanaSysvar,
 };
-use std::ops::{
-    Deref,
-    DerefMut,
+use std::{
+    cell::Ref,
+    marker::PhantomData,
+    ops::{
+        Deref,
+        DerefMut,
+        Range,
+    },
 };
 
 use crate::{
-    processors::seeded::Owned,
+    processors::{
+        discriminated::Discriminated,
+        seeded::{
+            create_account_seeded,
+            Bump,
+            Owned,
+            Seeded,
+        },
+        view::Viewable,
+    },
     CreationLamports,
     Derive,
     ExecutionContext,
     Result,
+    SolitaireError,
 };
 
 /// A short alias for AccountInfo.
@@ -35,7 +51,20 @@ pub enum AccountState {
     MaybeInitialized,
 }
 
-/// An account that is known to contain serialized data.
+/// How the value of a `Data` account relates to what is stored in the account.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Dirty {
+    /// The value was read from the account and hasn't been borrowed mutably since.
+    Clean,
+    /// The account didn't exist when it was parsed and holds the default value, which only needs
+    /// writing if the instruction created the account.
+    Fresh,
+    /// The value was borrowed mutably and must be written back.
+    Modified,
+}
+
+/// An account that is known to contain serialized data. Mutable borrows of the value mark it as
+/// modified, so it is only written back when it may have changed.
 ///
 /// Note on const generics:
 ///
@@ -48,6 +77,8 @@ pub enum AccountState {
 pub struct Data<'r, T: Owned + Default, const IsInitialized: AccountState> (
     pub Box<Info<'r>>,
     pub T,
+    pub Bump,
+    pub Dirty,
 );
 
 impl<'r, T: Owned + Default, const IsInitialized: AccountState> Deref
@@ -63,6 +94,7 @@ impl<'r, T: Owned + Default, const IsInitialized: AccountState> DerefMut
     for Data<'r, T, IsInitialized>
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
+        self.3 = Dirty::Modified;
         &mut self.1
     }
 }
@@ -70,7 +102,69 @@ impl<'r, T: Owned + Default, const IsInitialized: AccountState> DerefMut
 impl<'r, T: Owned + Default, const IsInitialized: AccountState> Data<'r, T, IsInitialized> {
     /// Is the account already initialized / created
     pub fn is_initialized(&self) -> bool {
-        **self.0.lamports.borrow() != 0
+        is_created(&self.0)
+    }
+}
+
+/// Whether the account was created. Lamports alone don't tell, anyone can transfer them to an
+/// address before it is created, leaving it empty and owned by the system program.
+pub(crate) fn is_created(info: &Info) -> bool {
+    **info.lamports.borrow() != 0 && (info.owner != &system_program::id() || !info.data_is_empty())
+}
+
+impl<'r, T: BorshSerialize + Owned + Default + Discriminated, const IsInitialized: AccountState>
+    Data<'r, T, IsInitialized>
+{
+    /// Make room for the current value before it is persisted, with `payer` topping up the rent
+    /// when its serialized size has grown.
+    ///
+    /// The runtime this is built for only lets the system program change the length of account
+    /// data, and only while the account is still empty. So the value can only grow into space
+    /// allocated when the account was created, a value that outgrew it fails with
+    /// `AccountResizeUnsupported` before any lamports move. Accounts that are expected to grow
+    /// have to be created with room to spare.
+    pub fn realloc(
+        &self,
+        ctx: &ExecutionContext,
+        payer: &Pubkey,
+        lamports: CreationLamports,
+    ) -> Result<()> {
+        let size = self.1.discriminator().len() + self.1.try_to_vec()?.len();
+        if size > self.0.data_len() {
+            return Err(SolitaireError::AccountResizeUnsupported(*self.0.key));
+        }
+
+        let required = lamports.amount(size)?;
+        let balance = self.0.lamports();
+        if required > balance {
+            let ix = system_instruction::transfer(payer, self.0.key, required - balance);
+            invoke(&ix, ctx.accounts)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A read-only account holding a `T`, of which only the header is deserialized. The third field is
+/// the location of the payload within the account data.
+pub struct View<'r, T: Viewable>(
+    pub Info<'r>,
+    pub T::Header,
+    pub Range<usize>,
+    pub PhantomData<T>,
+);
+
+impl<'r, T: Viewable> Deref for View<'r, T> {
+    type Target = T::Header;
+    fn deref(&self) -> &Self::Target {
+        &self.1
+    }
+}
+
+impl<'r, T: Viewable> View<'r, T> {
+    /// Borrow the payload from the account data.
+    pub fn payload(&self) -> Ref<[u8]> {
+        Ref::map(self.0.data.borrow(), |data| &data[self.2.clone()])
     }
 }
 
@@ -83,6 +177,14 @@ impl<'b, Var: SolanaSysvar> Deref for Sysvar<'b, Var> {
     }
 }
 
+impl<'b, Var: SolanaSysvar> Sysvar<'b, Var> {
+    /// Read the sysvar through its syscall, so instructions don't need to take its account. Only
+    /// `Clock`, `EpochSchedule`, `Fees` and `Rent` can be read this way.
+    pub fn get() -> Result<Var> {
+        Ok(Var::get()?)
+    }
+}
+
 impl<const Seed: &'static str> Derive<AccountInfo<'_>, Seed> {
     pub fn create(
         &self,
@@ -92,20 +194,25 @@ impl<const Seed: &'static str> Derive<AccountInfo<'_>, Seed> {
         space: usize,
         owner: &Pubkey,
     ) -> Result<()> {
-        let ix = system_instruction::create_account(
+        let seeds = self.self_bumped_seeds(None, ctx.program_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        create_account_seeded(
+            ctx,
             payer,
-            self.0.key,
-            lamports.amount(space),
-            space as u64,
+            &self.0,
+            lamports.amount(space)?,
+            space,
             owner,
-        );
-        let (_, bump_seed) = Pubkey::find_program_address(&[Seed.as_bytes()][..], ctx.program_id);
-        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[bump_seed]]]).map_err(|e| e.into())
+            &seeds,
+        )
     }
 }
 
-impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
-    Derive<Data<'_, T, { AccountState::Uninitialized }>, Seed>
+impl<
+        const Seed: &'static str,
+        T: BorshSerialize + Owned + Default + Discriminated,
+        const IsInitialized: AccountState,
+    > Derive<Data<'_, T, IsInitialized>, Seed>
 {
     pub fn create(
         &self,
@@ -113,16 +220,261 @@ impl<const Seed: &'static str, T: BorshSerialize + Owned + Default>
         payer: &Pubkey,
         lamports: CreationLamports,
     ) -> Result<()> {
-        // Get serialized struct size
-        let size = self.0.try_to_vec().unwrap().len();
-        let ix = system_instruction::create_account(
+        // Get serialized struct size, including its tag
+        let size = self.0.discriminator().len() + self.0.try_to_vec().unwrap().len();
+        let seeds = self.self_bumped_seeds(None, ctx.program_id);
+        let seeds: Vec<&[u8]> = seeds.iter().map(|item| item.as_slice()).collect();
+        create_account_seeded(
+            ctx,
             payer,
-            self.0 .0.key,
-            lamports.amount(size),
-            size as u64,
+            &self.0 .0,
+            lamports.amount(size)?,
+            size,
             ctx.program_id,
+            &seeds,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        processors::seeded::AccountOwner,
+        SolitaireError,
+    };
+    use borsh::BorshDeserialize;
+    use solana_program::{
+        entrypoint::{
+            ProgramResult,
+            SUCCESS,
+        },
+        instruction::Instruction,
+        program_stubs::{
+            set_syscall_stubs,
+            SyscallStubs,
+        },
+        system_instruction,
+        sysvar::{
+            clock::Clock,
+            rent::Rent,
+        },
+    };
+    use std::{
+        cell::RefCell,
+        sync::Once,
+    };
+
+    #[derive(Default, BorshSerialize, BorshDeserialize)]
+    struct Value {
+        a: u64,
+    }
+
+    impl Owned for Value {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl Discriminated for Value {
+        const DISCRIMINATOR: &'static [u8] = b"val";
+    }
+
+    #[derive(Default, BorshSerialize, BorshDeserialize)]
+    struct List {
+        items: Vec<u8>,
+    }
+
+    impl Owned for List {
+        fn owner(&self) -> AccountOwner {
+            AccountOwner::This
+        }
+    }
+
+    impl Discriminated for List {
+        const DISCRIMINATOR: &'static [u8] = b"lst";
+    }
+
+    thread_local! {
+        static INVOKED: RefCell<Vec<Instruction>> = RefCell::new(Vec::new());
+    }
+
+    /// Rent of the cluster the tests run on, which differs from the default.
+    fn rent() -> Rent {
+        Rent {
+            lamports_per_byte_year: Rent::default().lamports_per_byte_year * 2,
+            ..Rent::default()
+        }
+    }
+
+    /// Clock of the cluster the tests run on.
+    fn clock() -> Clock {
+        Clock {
+            slot: 42,
+            unix_timestamp: 1_600_000_000,
+            ..Clock::default()
+        }
+    }
+
+    /// Serves `rent()` and `clock()` through their syscalls and records the instructions invoked.
+    struct Cluster;
+
+    impl SyscallStubs for Cluster {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Clock) = clock() };
+            SUCCESS
+        }
+
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = rent() };
+            SUCCESS
+        }
+
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    fn stub_syscalls() {
+        static STUBS: Once = Once::new();
+        STUBS.call_once(|| {
+            set_syscall_stubs(Box::new(Cluster));
+        });
+        INVOKED.with(|invoked| invoked.borrow_mut().clear());
+    }
+
+    /// Create a `Value` account at an address funded with `lamports`. Returns the program, payer
+    /// and account keys, along with the instructions invoked to create it.
+    fn create(lamports: u64) -> (Pubkey, Pubkey, Pubkey, Vec<Instruction>) {
+        stub_syscalls();
+        let (program, payer, key) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let mut lamports = lamports;
+        let mut data = vec![];
+        let system = system_program::id();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &system, false, 0);
+        let account: Derive<Data<'_, Value, { AccountState::Uninitialized }>, "value"> = Derive(
+            Data(Box::new(info), Value::default(), Bump::default(), Dirty::Fresh),
+            Bump::default(),
+        );
+
+        let ctx = ExecutionContext {
+            program_id: &program,
+            accounts: &[],
+        };
+        account.create(&ctx, &payer, CreationLamports::Exempt).unwrap();
+        let invoked = INVOKED.with(|invoked| invoked.borrow().clone());
+        (program, payer, key, invoked)
+    }
+
+    #[test]
+    fn sysvar_get() {
+        stub_syscalls();
+        assert_eq!(Sysvar::<Rent>::get().unwrap(), rent());
+        assert_eq!(Sysvar::<Clock>::get().unwrap(), clock());
+    }
+
+    #[test]
+    fn creation_lamports() {
+        stub_syscalls();
+        assert_eq!(
+            CreationLamports::Exempt.amount(100).unwrap(),
+            rent().minimum_balance(100)
+        );
+        assert_ne!(
+            CreationLamports::Exempt.amount(100).unwrap(),
+            Rent::default().minimum_balance(100)
+        );
+        assert_eq!(CreationLamports::Amount(7).amount(100).unwrap(), 7);
+    }
+
+    #[test]
+    fn create_with_live_rent() {
+        // The tag and a u64.
+        let size = 3 + 8;
+        let exempt = rent().minimum_balance(size);
+
+        let (program, payer, key, invoked) = create(0);
+        assert_eq!(
+            invoked,
+            vec![system_instruction::create_account(
+                &payer,
+                &key,
+                exempt,
+                size as u64,
+                &program
+            )]
         );
-        let (_, bump_seed) = Pubkey::find_program_address(&[Seed.as_bytes()][..], ctx.program_id);
-        invoke_signed(&ix, ctx.accounts, &[&[Seed.as_bytes(), &[bump_seed]]]).map_err(|e| e.into())
+
+        // Prefunded accounts are topped up to the live minimum.
+        let (program, payer, key, invoked) = create(1);
+        assert_eq!(
+            invoked,
+            vec![
+                system_instruction::transfer(&payer, &key, exempt - 1),
+                system_instruction::allocate(&key, size as u64),
+                system_instruction::assign(&key, &program),
+            ]
+        );
+    }
+
+    #[test]
+    fn realloc() {
+        stub_syscalls();
+        let (program, payer, key) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+
+        // An empty list, created with room for 32 items but paid for its current size.
+        let empty = 3 + 4;
+        let mut lamports = rent().minimum_balance(empty);
+        let mut data = vec![0; empty + 32];
+        let info =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data, &program, false, 0);
+        let mut account: Data<'_, List, { AccountState::Initialized }> =
+            Data(Box::new(info), List::default(), Bump::default(), Dirty::Clean);
+        let ctx = ExecutionContext {
+            program_id: &program,
+            accounts: &[],
+        };
+
+        // Growing the list has the payer top up the rent for its new size.
+        account.items = vec![1; 32];
+        account
+            .realloc(&ctx, &payer, CreationLamports::Exempt)
+            .unwrap();
+        let grown = rent().minimum_balance(empty + 32);
+        let topup = grown - rent().minimum_balance(empty);
+        assert_eq!(
+            INVOKED.with(|invoked| invoked.borrow().clone()),
+            vec![system_instruction::transfer(&payer, &key, topup)]
+        );
+
+        // Accounts that already hold enough aren't topped up.
+        stub_syscalls();
+        **account.0.lamports.borrow_mut() = grown;
+        account
+            .realloc(&ctx, &payer, CreationLamports::Exempt)
+            .unwrap();
+        assert!(INVOKED.with(|invoked| invoked.borrow().is_empty()));
+
+        // The account can't grow past the space it was created with, nothing is paid for it.
+        account.items.push(1);
+        assert!(matches!(
+            account.realloc(&ctx, &payer, CreationLamports::Exempt),
+            Err(SolitaireError::AccountResizeUnsupported(k)) if k == key
+        ));
+        assert!(INVOKED.with(|invoked| invoked.borrow().is_empty()));
     }
 }
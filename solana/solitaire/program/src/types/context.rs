@@ -1,4 +1,8 @@
-use crate::trace;
+use crate::{
+    trace,
+    Result,
+    SolitaireError,
+};
 use solana_program::{
     account_info::{
         next_account_info,
@@ -27,28 +31,42 @@ pub struct Context<'a, 'b: 'a, 'c, T> {
 
     /// Whether to enforce immutability.
     pub immutable: bool,
+
+    /// Accounts struct field being peeled, as `Struct.field`, to name it in errors.
+    pub field: &'static str,
+
+    /// Number of elements to peel for collections, all remaining accounts when unset.
+    pub len: Option<usize>,
 }
 
 impl<'a, 'b: 'a, 'c, T> Context<'a, 'b, 'c, T> {
-    pub fn new(program: &'a Pubkey, iter: &'c mut Iter<'a, AccountInfo<'b>>, data: &'a T) -> Self {
+    pub fn new(
+        program: &'a Pubkey,
+        iter: &'c mut Iter<'a, AccountInfo<'b>>,
+        data: &'a T,
+        field: &'static str,
+    ) -> Self {
         Context {
             this: program,
             info: None,
             immutable: true,
             iter,
             data,
+            field,
+            len: None,
         }
     }
 
-    pub fn info<'d>(&'d mut self) -> &'a AccountInfo<'b> {
+    pub fn info<'d>(&'d mut self) -> Result<&'a AccountInfo<'b>> {
         match self.info {
             None => {
-                let info = next_account_info(self.iter).unwrap();
+                let info = next_account_info(self.iter)
+                    .map_err(|_| SolitaireError::NotEnoughAccountKeys(self.field))?;
                 trace!("{}", info.key);
                 self.info = Some(info);
-                info
+                Ok(info)
             }
-            Some(v) => v,
+            Some(v) => Ok(v),
         }
     }
 }
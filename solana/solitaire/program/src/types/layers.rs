@@ -1,9 +1,9 @@
 //! This file contains several single-field wrapper structs. Each one represents a layer that must
 //! be checked in order to parse a Solana account.
 //!
-//! These structs are always single field (or single + PhantomData) and so can be represented with
-//! the transparent repr layout. When each layer is removed the data can be transmuted safely to
-//! the layer below, allowing for optimized recursion.
+//! These structs are always single field (or single + PhantomData, or the bump of a Derive) and so
+//! the checking layers can be represented with the transparent repr layout. When each layer is
+//! removed the data can be transmuted safely to the layer below, allowing for optimized recursion.
 
 use std::{
     io::{
@@ -17,7 +17,11 @@ use std::{
     },
 };
 
-use crate::Info;
+use crate::{
+    processors::seeded::Bump,
+    Info,
+};
+use solana_program::pubkey::Pubkey;
 use borsh::{
     BorshDeserialize,
     BorshSerialize,
@@ -35,8 +39,80 @@ pub struct Signer<Next>(pub Next);
 #[repr(transparent)]
 pub struct System<Next>(pub Next);
 
-#[repr(transparent)]
-pub struct Derive<Next, const Seed: &'static str>(pub Next);
+/// Derived accounts also keep the bump of `Seed` found while peeling them.
+pub struct Derive<Next, const Seed: &'static str>(pub Next, pub Bump);
+
+/// An account closed once the instruction succeeds. Its data is zeroed and its lamports move to the
+/// field of the accounts struct named `Recipient`, which must be spelled out in the field's type.
+///
+/// ```
+/// # #![feature(const_generics)]
+/// # use solitaire::*;
+/// #[derive(FromAccounts)]
+/// pub struct CloseAccount<'b> {
+///     pub payer: Mut<Signer<Info<'b>>>,
+///     pub account: Close<Mut<Info<'b>>, "payer">,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// `Close` has to be the outermost layer of the field's type:
+///
+/// ```compile_fail
+/// # #![feature(const_generics)]
+/// # use solitaire::*;
+/// #[derive(FromAccounts)]
+/// pub struct CloseAccount<'b> {
+///     pub payer: Mut<Signer<Info<'b>>>,
+///     pub account: Mut<Close<Info<'b>, "payer">>,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// and can't be hidden behind an alias:
+///
+/// ```compile_fail
+/// # #![feature(const_generics)]
+/// # use solitaire::*;
+/// type Closed<'b> = Close<Mut<Info<'b>>, "payer">;
+///
+/// #[derive(FromAccounts)]
+/// pub struct CloseAccount<'b> {
+///     pub payer: Mut<Signer<Info<'b>>>,
+///     pub account: Closed<'b>,
+/// }
+/// # fn main() {}
+/// ```
+pub struct Close<Next, const Recipient: &'static str>(pub Next);
+
+/// Accounts persisted once the instruction succeeds, which is all of them but those behind a
+/// `Close` layer. Accounts structs require it of every field they don't close, so a `Close` they
+/// can't see, behind an alias or another layer, fails to compile instead of being persisted.
+pub auto trait Persistent {}
+
+impl<T, const Recipient: &'static str> !Persistent for Close<T, Recipient> {}
+
+/// An account that callers may leave out by passing `ABSENT` in its place.
+pub struct Optional<Next>(pub Option<Next>);
+
+/// Key passed in place of an `Optional` account that is not provided.
+pub const ABSENT: Pubkey = Pubkey::new_from_array([0; 32]);
+
+/// A program account, whose key must be the id of `Id`.
+pub struct Program<'b, Id: ProgramId>(pub Info<'b>, pub PhantomData<Id>);
+
+/// Programs that can be required with the `Program` layer.
+pub trait ProgramId {
+    fn id() -> Pubkey;
+}
+
+pub struct SystemProgram;
+
+impl ProgramId for SystemProgram {
+    fn id() -> Pubkey {
+        solana_program::system_program::id()
+    }
+}
 
 // Several traits are required for types defined here, they cannot be defined in another file due
 // to orphan instance limitations.
@@ -105,3 +181,36 @@ impl<T, const Seed: &'static str> DerefMut for Derive<T, Seed> {
         unsafe { std::mem::transmute(&mut self.0) }
     }
 }
+
+impl<T> Deref for Optional<T> {
+    type Target = Option<T>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Optional<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<'b, Id: ProgramId> Deref for Program<'b, Id> {
+    type Target = Info<'b>;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const Recipient: &'static str> Deref for Close<T, Recipient> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, const Recipient: &'static str> DerefMut for Close<T, Recipient> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
@@ -0,0 +1,168 @@
+#![feature(const_generics)]
+#![allow(incomplete_features)]
+
+use solana_program::{
+    account_info::AccountInfo,
+    pubkey::Pubkey,
+    system_program,
+    sysvar,
+};
+use solitaire::*;
+
+#[derive(Default, BorshSerialize, BorshDeserialize)]
+pub struct Value {
+    pub a: u64,
+}
+
+impl Owned for Value {
+    fn owner(&self) -> AccountOwner {
+        AccountOwner::This
+    }
+}
+
+impl Discriminated for Value {
+    const DISCRIMINATOR: &'static [u8] = b"val";
+}
+
+pub struct BatchData {
+    pub count: u8,
+}
+
+#[derive(FromAccounts)]
+#[instruction(BatchData)]
+pub struct Batch<'b> {
+    pub payer: Mut<Signer<Info<'b>>>,
+
+    #[len(data.count)]
+    pub listed: Vec<Mut<Info<'b>>>,
+
+    pub created: Vec<Mut<Data<'b, Value, { AccountState::Uninitialized }>>>,
+}
+
+#[derive(FromAccounts)]
+pub struct Nested<'b> {
+    pub batch: Batch<'b>,
+}
+
+/// Empty writable accounts with the given keys, as a runtime would pass them.
+fn accounts(keys: &[Pubkey]) -> Vec<AccountInfo<'static>> {
+    keys.iter()
+        .map(|key| {
+            AccountInfo::new(
+                Box::leak(Box::new(*key)),
+                true,
+                true,
+                Box::leak(Box::new(0)),
+                Box::leak(Vec::new().into_boxed_slice()),
+                &system_program::ID,
+                false,
+                0,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn len_from_instruction_data() {
+    let program = Pubkey::new_unique();
+    let keys_in: Vec<Pubkey> = (0..6).map(|_| Pubkey::new_unique()).collect();
+    let infos = accounts(&keys_in);
+
+    let data = BatchData { count: 2 };
+    let batch = Batch::from_instruction(&program, &mut infos.iter(), &data).unwrap();
+    assert_eq!(*batch.payer.key, keys_in[0]);
+    assert_eq!(
+        batch
+            .listed
+            .iter()
+            .map(|info| *info.key)
+            .collect::<Vec<_>>(),
+        keys_in[1..3]
+    );
+    assert_eq!(
+        batch
+            .created
+            .iter()
+            .map(|d| *d.info().key)
+            .collect::<Vec<_>>(),
+        keys_in[3..]
+    );
+
+    // The instruction data decides where the first list ends.
+    let data = BatchData { count: 4 };
+    let batch = Batch::from_instruction(&program, &mut infos.iter(), &data).unwrap();
+    assert_eq!(batch.listed.len(), 4);
+    assert_eq!(batch.created.len(), 1);
+
+    // Lists longer than the accounts passed fail instead of reading past them.
+    let data = BatchData { count: 6 };
+    assert!(Batch::from_instruction(&program, &mut infos.iter(), &data).is_err());
+}
+
+#[test]
+fn len_requires_instruction_data() {
+    let program = Pubkey::new_unique();
+    let keys_in: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let infos = accounts(&keys_in);
+
+    let mut iter = infos.iter();
+    assert!(matches!(
+        <Batch as FromAccounts>::from(&program, &mut iter, &()),
+        Err(SolitaireError::InstructionDataRequired("Batch"))
+    ));
+
+    // Nesting doesn't provide it either.
+    let mut iter = infos.iter();
+    assert!(matches!(
+        <Nested as FromAccounts>::from(&program, &mut iter, &()),
+        Err(SolitaireError::InstructionDataRequired("Batch"))
+    ));
+}
+
+#[test]
+fn trailing_deps_are_not_listed() {
+    let program = Pubkey::new_unique();
+    let mut keys_in: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    keys_in.extend(vec![sysvar::rent::ID, system_program::ID]);
+    let infos = accounts(&keys_in);
+
+    // The rent sysvar and system program appended by clients stay out of the unbounded list.
+    let data = BatchData { count: 1 };
+    let batch = Batch::from_instruction(&program, &mut infos.iter(), &data).unwrap();
+    assert_eq!(
+        batch
+            .created
+            .iter()
+            .map(|d| *d.info().key)
+            .collect::<Vec<_>>(),
+        keys_in[2..4]
+    );
+}
+
+#[test]
+fn remaining_len() {
+    let rest: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let deps = vec![sysvar::rent::ID, system_program::ID];
+
+    // No dependencies, every account is in the list.
+    let infos = accounts(&rest);
+    assert_eq!(solitaire::remaining_len(&infos, vec![]), 3);
+
+    // Dependencies passed last are not.
+    let infos = accounts(&[rest.clone(), deps.clone()].concat());
+    assert_eq!(solitaire::remaining_len(&infos, deps.clone()), 3);
+
+    // Clients pass each dependency once, however many accounts require it.
+    let repeated = [deps.clone(), deps.clone()].concat();
+    assert_eq!(solitaire::remaining_len(&infos, repeated), 3);
+
+    // Dependencies not passed last, or not in order, are part of the list.
+    let infos = accounts(&[deps.clone(), rest.clone()].concat());
+    assert_eq!(solitaire::remaining_len(&infos, deps.clone()), 5);
+    let infos = accounts(&[rest.clone(), vec![deps[1], deps[0]]].concat());
+    assert_eq!(solitaire::remaining_len(&infos, deps.clone()), 5);
+
+    // Lists shorter than the dependencies are left alone.
+    let infos = accounts(&rest[..1]);
+    assert_eq!(solitaire::remaining_len(&infos, deps), 1);
+}
@@ -67,13 +67,53 @@ pub fn derive_to_instruction(input: TokenStream) -> TokenStream {
     }
     let (combined_impl_g, _, _) = combined_generics.split_for_impl();
 
-    let expanded = generate_to_instruction(&name, &combined_impl_g, &input.data);
+    let expanded = generate_to_instruction(
+        &name,
+        &combined_impl_g,
+        &type_impl_g,
+        &type_g,
+        &input.data,
+    );
     TokenStream::from(expanded)
 }
 
 /// Generate a FromAccounts implementation for a product of accounts. Each field is constructed by
 /// a call to the Verify::verify instance of its type.
-#[proc_macro_derive(FromAccounts)]
+///
+/// Fields that are program derived addresses can declare their seeds with `#[seeds(expr)]`, where
+/// `expr` is the argument passed to the field's `Seeded::verify_derivation`. The expression can
+/// refer to any other field of the struct by name. The derivation is then checked as soon as the
+/// accounts are parsed. If the seeds depend on the instruction data, its type is declared on the
+/// struct with `#[instruction(Type)]`, which makes it available to the expressions as `data`. The
+/// checks then run right after parsing, from the `solitaire!` dispatcher, so such structs cannot
+/// be nested in other account structs.
+///
+/// Searching for the bump of a derivation is expensive, so a known bump can be passed with
+/// `#[seeds(expr, bump = expr)]`, usually read from the instruction data. The bump is either a `u8`
+/// or an `Option<u8>`, so clients that don't know it can leave it out and have it searched for. The
+/// derivation of an existing account is then checked with a single `create_program_address`,
+/// accounts yet to be created are rejected unless the bump is the canonical one. Either way the
+/// bump is kept by the account for creating and signing for it later in the instruction.
+///
+/// Fields of type `Close<T, "recipient">` are closed instead of persisted once the instruction
+/// succeeds, moving their lamports to the field named `recipient`. The `Close` layer has to be
+/// spelled out as the outermost layer of the field type, as it is recognised by name. Every other
+/// field has to be `Persistent`, so a `Close` behind an alias or another layer fails to compile.
+///
+/// Relations between the accounts are declared on fields as well, and checked once all accounts
+/// are parsed. `#[has_one(a, b = other)]` requires the field's value to hold the key of field `a`
+/// as `a`, and the key of field `other` as `b`, such as the mint and owner of a token account.
+/// `#[key(expr)]` requires the field's key to equal `expr`. A failed check names the field.
+///
+/// A `Vec<T>` field takes a list of accounts, each checked as a `T`. Its length is given with
+/// `#[len(expr)]`, where `expr` can refer to the fields before it and, in structs declaring
+/// `#[instruction(Type)]`, to the instruction data as `data`. Such structs cannot be nested in
+/// other account structs either. Without a length the list takes all remaining accounts but the
+/// dependencies appended by clients, so it has to be the last field of an instruction's accounts.
+///
+/// Accounts passed after those of the struct are ignored, unless it is marked `#[strict]`. The
+/// instruction is then rejected if it has any other than the dependencies of its accounts.
+#[proc_macro_derive(FromAccounts, attributes(seeds, instruction, strict, len, has_one, key))]
 pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
@@ -106,12 +146,113 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
     }
     let (combined_impl_g, _, _) = combined_generics.split_for_impl();
 
-    let from_method = generate_fields(&name, &input.data);
+    // Type of the instruction data that seeds are derived from, if any.
+    let instruction: Option<syn::Type> = input
+        .attrs
+        .iter()
+        .find(|a| a.path.is_ident("instruction"))
+        .map(|a| a.parse_args().expect("expected #[instruction(Type)]"));
+
+    // Whether trailing accounts are rejected when this struct is parsed for an instruction.
+    let strict = input.attrs.iter().any(|a| a.path.is_ident("strict"));
+
+    let derivations = generate_derivations(&input.data);
+    let fields: Vec<&Option<syn::Ident>> = match input.data {
+        Data::Struct(ref data) => data.fields.iter().map(|f| &f.ident).collect(),
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    };
+    let (from_derivations, verify_derivations) = match instruction {
+        Some(ref data_type) => (
+            quote!(),
+            quote! {
+                /// Macro generated verification of the declared seeds by Solitaire.
+                impl #type_impl_g solitaire::VerifyDerivations<#data_type> for #name #type_g {
+                    #[allow(unused_variables)]
+                    fn verify_derivations(&self, pid: &solana_program::pubkey::Pubkey, data: &#data_type) -> solitaire::Result<()> {
+                        use solitaire::trace;
+                        let #name { #(#fields,)* } = self;
+                        #derivations
+                        Ok(())
+                    }
+                }
+            },
+        ),
+        None => {
+            let mut verify_g = type_generics.clone();
+            verify_g.params.push(parse_quote!(DataType));
+            let (verify_impl_g, _, _) = verify_g.split_for_impl();
+            (
+                derivations,
+                quote! {
+                    /// Macro generated by Solitaire, seeds of this struct are verified while parsing.
+                    impl #verify_impl_g solitaire::VerifyDerivations<DataType> for #name #type_g {
+                        fn verify_derivations(&self, _pid: &solana_program::pubkey::Pubkey, _data: &DataType) -> solitaire::Result<()> {
+                            Ok(())
+                        }
+                    }
+                },
+            )
+        }
+    };
+
+    // Lengths of account lists may refer to the instruction data, which only the parser of an
+    // instruction's own accounts has.
+    let lengths = match input.data {
+        Data::Struct(ref data) => data
+            .fields
+            .iter()
+            .any(|f| f.attrs.iter().any(|a| a.path.is_ident("len"))),
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    };
+
+    let constraints = generate_constraints(&name, &input.data);
+    let from_derivations = quote! {
+        #from_derivations
+        #constraints
+    };
+    let from_method = generate_fields(&name, &input.data, from_derivations);
+    let (from_method, from_instruction) = match instruction {
+        Some(ref data_type) => (
+            match lengths {
+                true => quote! {
+                    Err(solitaire::SolitaireError::InstructionDataRequired(stringify!(#name)))
+                },
+                false => from_method.clone(),
+            },
+            quote! {
+                /// Macro generated parsing of the accounts of an instruction by Solitaire.
+                impl #combined_impl_g solitaire::FromInstruction<'a, 'b, 'c, #data_type> for #name #type_g {
+                    fn from_instruction(pid: &'a solana_program::pubkey::Pubkey, iter: &'c mut std::slice::Iter<'a, solana_program::account_info::AccountInfo<'b>>, data: &'a #data_type) -> solitaire::Result<Self> {
+                        #from_method
+                    }
+                }
+            },
+        ),
+        None => {
+            let mut from_g = combined_generics.clone();
+            from_g.params.push(parse_quote!(DataType));
+            let (from_impl_g, _, _) = from_g.split_for_impl();
+            (
+                from_method,
+                quote! {
+                    /// Macro generated by Solitaire, this struct is parsed the same for any instruction.
+                    impl #from_impl_g solitaire::FromInstruction<'a, 'b, 'c, DataType> for #name #type_g {
+                        fn from_instruction(pid: &'a solana_program::pubkey::Pubkey, iter: &'c mut std::slice::Iter<'a, solana_program::account_info::AccountInfo<'b>>, data: &'a DataType) -> solitaire::Result<Self> {
+                            solitaire::FromAccounts::from(pid, iter, data)
+                        }
+                    }
+                },
+            )
+        }
+    };
     let persist_method = generate_persist(&name, &input.data);
     let deps_method = generate_deps_fields(&name, &input.data);
+    let idl_method = generate_idl_accounts(&name, &input.data);
     let expanded = quote! {
         /// Macro generated implementation of FromAccounts by Solitaire.
         impl #combined_impl_g solitaire::FromAccounts #peel_type_g for #name #type_g {
+            const STRICT: bool = #strict;
+
             fn from<DataType>(pid: &'a solana_program::pubkey::Pubkey, iter: &'c mut std::slice::Iter<'a, solana_program::account_info::AccountInfo<'b>>, data: &'a DataType) -> solitaire::Result<Self> {
                 #from_method
             }
@@ -130,14 +271,25 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
             fn persist(&self, program_id: &solana_program::pubkey::Pubkey) -> solitaire::Result<()> {
                 solitaire::Persist::persist(self, program_id)
             }
+
+            fn idl_accounts(name: &str) -> Vec<solitaire::idl::IdlAccount> {
+                #idl_method
+            }
         }
 
+        /// Accounts structs close their own `Close` fields when persisted.
+        impl #type_impl_g solitaire::Persistent for #name #type_g {}
+
         /// Macro generated implementation of Persist by Solitaire.
         impl #type_impl_g solitaire::Persist for #name #type_g {
             fn persist(&self, program_id: &solana_program::pubkey::Pubkey) -> solitaire::Result<()> {
                 #persist_method
             }
         }
+
+        #from_instruction
+
+        #verify_derivations
     };
 
     // Hand the output tokens back to the compiler
@@ -145,7 +297,7 @@ pub fn derive_from_accounts(input: TokenStream) -> TokenStream {
 }
 
 /// This function does the heavy lifting of generating the field parsers.
-fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
+fn generate_fields(name: &syn::Ident, data: &Data, derivations: TokenStream2) -> TokenStream2 {
     match *data {
         // We only care about structures.
         Data::Struct(ref data) => {
@@ -158,16 +310,39 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
                     // the work.
                     let recurse = fields.named.iter().map(|f| {
                         // Field name, to assign to.
-                        let name = &f.ident;
+                        let field = &f.ident;
                         let ty = &f.ty;
 
+                        // Account lists take their length from `#[len(expr)]`, or all remaining
+                        // accounts other than the dependencies appended by clients.
+                        let len = match f.attrs.iter().find(|a| a.path.is_ident("len")) {
+                            Some(attr) => {
+                                let len: syn::Expr =
+                                    attr.parse_args().expect("expected #[len(expr)]");
+                                quote!(Some((#len) as usize))
+                            }
+                            None if is_vec(ty) => quote! {
+                                Some(solitaire::remaining_len(
+                                    iter.as_slice(),
+                                    <Self as solitaire::Peel>::deps(),
+                                ))
+                            },
+                            None => quote!(None),
+                        };
+
                         quote! {
-                            trace!(stringify!(#name));
-                            let #name: #ty = solitaire::Peel::peel(&mut solitaire::Context::new(
-                                pid,
-                                iter,
-                                data,
-                            ))?;
+                            trace!(stringify!(#field));
+                            let #field: #ty = {
+                                let len = #len;
+                                let mut ctx = solitaire::Context::new(
+                                    pid,
+                                    iter,
+                                    data,
+                                    concat!(stringify!(#name), ".", stringify!(#field)),
+                                );
+                                ctx.len = len;
+                                solitaire::Peel::peel(&mut ctx)?
+                            };
                         }
                     });
 
@@ -176,12 +351,14 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
                         quote!(#name)
                     });
 
-                    // Write out our iterator and return the filled structure.
+                    // Write out our iterator and return the filled structure, once all fields are
+                    // parsed their declared derivations can be checked.
                     quote! {
                         use solana_program::account_info::next_account_info;
                         use solitaire::trace;
                         trace!("Peeling:");
                         #(#recurse;)*
+                        #derivations
                         Ok(#name { #(#names,)* })
                     }
                 }
@@ -200,6 +377,155 @@ fn generate_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     }
 }
 
+/// Generate the checks of the relations declared on fields with `#[has_one(..)]` and `#[key(..)]`.
+/// Fields are expected to be bound to locals of the same name.
+fn generate_constraints(name: &syn::Ident, data: &Data) -> TokenStream2 {
+    let fields = match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => fields,
+            _ => unimplemented!(),
+        },
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    };
+
+    let checks = fields.named.iter().flat_map(|f| {
+        let field = &f.ident;
+        let violated = quote! {
+            return Err(solitaire::SolitaireError::ConstraintViolated(
+                concat!(stringify!(#name), ".", stringify!(#field)),
+            ));
+        };
+
+        f.attrs.iter().filter_map(move |attr| {
+            if attr.path.is_ident("key") {
+                let key: syn::Expr = attr.parse_args().expect("expected #[key(expr)]");
+                return Some(quote! {
+                    if *solitaire::Keyed::info(&#field).key != #key {
+                        #violated
+                    }
+                });
+            }
+
+            if !attr.path.is_ident("has_one") {
+                return None;
+            }
+            type Args = syn::punctuated::Punctuated<syn::Expr, syn::Token![,]>;
+            let args = attr
+                .parse_args_with(Args::parse_terminated)
+                .expect("expected #[has_one(field, ..)] or #[has_one(field = other, ..)]");
+            let checks = args.into_iter().map(|arg| {
+                let (value, other) = match arg {
+                    syn::Expr::Assign(ref a) => (a.left.clone(), a.right.clone()),
+                    other => (Box::new(other.clone()), Box::new(other)),
+                };
+                quote! {
+                    if #field.#value != *solitaire::Keyed::info(&#other).key {
+                        #violated
+                    }
+                }
+            });
+            Some(quote!(#(#checks)*))
+        })
+    });
+
+    quote! {
+        #(#checks)*
+    }
+}
+
+/// Whether a field is a `Vec` of accounts.
+fn is_vec(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) => p.path.segments.last().map_or(false, |s| s.ident == "Vec"),
+        _ => false,
+    }
+}
+
+/// The recipient named by a `Close<T, "recipient">` field type.
+fn close_recipient(ty: &syn::Type) -> Option<syn::LitStr> {
+    let segment = match ty {
+        syn::Type::Path(p) => p.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != "Close" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            syn::GenericArgument::Const(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(s),
+                ..
+            })) => Some(s.clone()),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+fn is_ident(expr: &syn::Expr, ident: &str) -> bool {
+    matches!(expr, syn::Expr::Path(p) if p.path.is_ident(ident))
+}
+
+/// Generate a `verify_derivation` call for each field with declared seeds. Fields are expected to
+/// be bound to locals of the same name.
+fn generate_derivations(data: &Data) -> TokenStream2 {
+    match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let checks = fields.named.iter().filter_map(|f| {
+                    let name = &f.ident;
+                    let attr = f.attrs.iter().find(|a| a.path.is_ident("seeds"))?;
+                    type Args = syn::punctuated::Punctuated<syn::Expr, syn::Token![,]>;
+                    let mut args = attr
+                        .parse_args_with(Args::parse_terminated)
+                        .expect("expected #[seeds(expr)] or #[seeds(expr, bump = expr)]")
+                        .into_iter();
+                    let seeds = args.next().expect("expected #[seeds(expr)]");
+                    let verify = match args.next() {
+                        None => quote!(#name.verify_derivation(pid, #seeds)?;),
+                        Some(syn::Expr::Assign(ref a)) if is_ident(&a.left, "bump") => {
+                            let bump = &a.right;
+                            quote! {
+                                match Option::<u8>::from(#bump) {
+                                    Some(bump) => #name.verify_derivation_with_bump(pid, #seeds, bump)?,
+                                    None => #name.verify_derivation(pid, #seeds)?,
+                                }
+                            }
+                        }
+                        Some(other) => {
+                            let error = quote_spanned! { other.span() =>
+                                compile_error!("expected `bump = expr`");
+                            };
+                            return Some(error);
+                        }
+                    };
+                    Some(quote! {
+                        trace!(concat!("Verifying derivation of ", stringify!(#name)));
+                        {
+                            use solitaire::processors::seeded::Seeded;
+                            #verify
+                        }
+                    })
+                });
+
+                quote! {
+                    #(#checks)*
+                }
+            }
+
+            Fields::Unnamed(_) => {
+                unimplemented!()
+            }
+
+            Fields::Unit => {
+                unimplemented!()
+            }
+        },
+
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    }
+}
+
 /// This function does the heavy lifting of generating the field parsers.
 fn generate_deps_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     match *data {
@@ -239,6 +565,196 @@ fn generate_deps_fields(name: &syn::Ident, data: &Data) -> TokenStream2 {
     }
 }
 
+/// Generate the IDL description of the accounts, in the order they are peeled.
+fn generate_idl_accounts(name: &syn::Ident, data: &Data) -> TokenStream2 {
+    match *data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => {
+                let recurse = fields.named.iter().map(|f| {
+                    let name = &f.ident;
+                    let ty = &f.ty;
+                    quote! {
+                        accounts.append(&mut <#ty as solitaire::Peel>::idl_accounts(
+                            &solitaire::idl::account_name(name, stringify!(#name)),
+                        ));
+                    }
+                });
+
+                quote! {
+                    let mut accounts = Vec::new();
+                    #(#recurse;)*
+                    accounts
+                }
+            }
+
+            Fields::Unnamed(_) => {
+                unimplemented!()
+            }
+
+            Fields::Unit => {
+                unimplemented!()
+            }
+        },
+
+        Data::Enum(_) | Data::Union(_) => unimplemented!(),
+    }
+}
+
+/// Generate an IdlSchema implementation describing the Borsh layout of a struct or enum, so it
+/// can be used as instruction data in the program IDL.
+#[proc_macro_derive(IdlSchema)]
+pub fn derive_idl_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_g, type_g, where_clause) = input.generics.split_for_impl();
+
+    let (definition, types) = match input.data {
+        Data::Struct(ref data) => {
+            let (fields, types) = generate_idl_fields(&data.fields);
+            (
+                quote! {
+                    solitaire::idl::IdlTypeDef::Struct {
+                        name: stringify!(#name).to_string(),
+                        fields: #fields,
+                    }
+                },
+                types,
+            )
+        }
+
+        Data::Enum(ref data) => {
+            let mut types = Vec::new();
+            let variants: Vec<TokenStream2> = data
+                .variants
+                .iter()
+                .map(|v| {
+                    let variant = &v.ident;
+                    let (fields, mut variant_types) = generate_idl_fields(&v.fields);
+                    types.append(&mut variant_types);
+                    quote!((stringify!(#variant).to_string(), #fields))
+                })
+                .collect();
+            (
+                quote! {
+                    solitaire::idl::IdlTypeDef::Enum {
+                        name: stringify!(#name).to_string(),
+                        variants: vec![#(#variants,)*],
+                    }
+                },
+                types,
+            )
+        }
+
+        Data::Union(_) => unimplemented!(),
+    };
+
+    let expanded = quote! {
+        /// Macro generated implementation of IdlSchema by Solitaire.
+        impl #impl_g solitaire::idl::IdlSchema for #name #type_g #where_clause {
+            fn idl_type() -> solitaire::idl::IdlType {
+                solitaire::idl::IdlType::Defined(stringify!(#name).to_string())
+            }
+
+            fn idl_definitions(definitions: &mut Vec<solitaire::idl::IdlTypeDef>) {
+                if definitions.iter().any(|d| d.name() == stringify!(#name)) {
+                    return;
+                }
+                definitions.push(#definition);
+                #(<#types as solitaire::idl::IdlSchema>::idl_definitions(definitions);)*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Generate a Discriminated implementation. The tag defaults to the first 8 bytes of the Keccak256
+/// hash of `account:<Name>`, an explicit one can be given with `#[discriminator(b"tag")]`. Types
+/// with accounts created before they were tagged add `#[discriminator(legacy(TAGS))]`, where
+/// `TAGS` lists the tags of the other account types of the program, or `#[discriminator(legacy)]`
+/// if there are none.
+#[proc_macro_derive(Discriminated, attributes(discriminator))]
+pub fn derive_discriminated(input: TokenStream) -> TokenStream {
+    use sha3::Digest;
+
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let (impl_g, type_g, where_clause) = input.generics.split_for_impl();
+
+    let hash = sha3::Keccak256::digest(format!("account:{}", name).as_bytes());
+    let default_tag = &hash[..8];
+    let mut tag = quote!(&[#(#default_tag),*]);
+    let mut legacy = false;
+    let mut known = quote!(&[]);
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path.is_ident("discriminator"))
+    {
+        let args = attr
+            .parse_args_with(
+                syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+            )
+            .expect("expected #[discriminator(legacy(TAGS))] or #[discriminator(b\"tag\")]");
+        for arg in args {
+            match arg {
+                syn::Expr::Path(ref p) if p.path.is_ident("legacy") => legacy = true,
+                syn::Expr::Call(ref c) if is_ident(&c.func, "legacy") && c.args.len() == 1 => {
+                    let tags = &c.args[0];
+                    legacy = true;
+                    known = quote!(#tags);
+                }
+                expr => tag = quote!(#expr),
+            }
+        }
+    }
+
+    let expanded = quote! {
+        /// Macro generated implementation of Discriminated by Solitaire.
+        impl #impl_g solitaire::Discriminated for #name #type_g #where_clause {
+            const DISCRIMINATOR: &'static [u8] = #tag;
+            const LEGACY: bool = #legacy;
+            const KNOWN: &'static [&'static [u8]] = #known;
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Field list of a struct or enum variant for the IDL, along with the field types.
+fn generate_idl_fields(fields: &Fields) -> (TokenStream2, Vec<syn::Type>) {
+    let entries: Vec<(TokenStream2, syn::Type)> = match fields {
+        Fields::Named(ref fields) => fields
+            .named
+            .iter()
+            .map(|f| {
+                let name = f.ident.as_ref().unwrap().to_string();
+                (quote!(#name), f.ty.clone())
+            })
+            .collect(),
+        Fields::Unnamed(ref fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let name = i.to_string();
+                (quote!(#name), f.ty.clone())
+            })
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let fields = entries.iter().map(|(name, ty)| {
+        quote! {
+            (#name.to_string(), <#ty as solitaire::idl::IdlSchema>::idl_type())
+        }
+    });
+    let types = entries.iter().map(|(_, ty)| ty.clone()).collect();
+
+    (quote!(vec![#(#fields,)*]), types)
+}
+
 /// This function does the heavy lifting of generating the field parsers.
 fn generate_persist(name: &syn::Ident, data: &Data) -> TokenStream2 {
     match *data {
@@ -256,8 +772,18 @@ fn generate_persist(name: &syn::Ident, data: &Data) -> TokenStream2 {
                         let name = &f.ident;
                         let ty = &f.ty;
 
+                        // Accounts to be closed are drained into their recipient instead.
+                        if let Some(recipient) = close_recipient(ty) {
+                            let recipient = syn::Ident::new(&recipient.value(), recipient.span());
+                            return quote! {
+                                trace!(stringify!(#name));
+                                solitaire::Close::close(&self.#name, solitaire::Keyed::info(&self.#recipient))?;
+                            };
+                        }
+
                         quote! {
                             trace!(stringify!(#name));
+                            persistent(&self.#name);
                             Peel::persist(&self.#name, program_id)?;
                         }
                     });
@@ -265,6 +791,7 @@ fn generate_persist(name: &syn::Ident, data: &Data) -> TokenStream2 {
                     // Write out our iterator and return the filled structure.
                     quote! {
                         use solitaire::trace;
+                        fn persistent<T: solitaire::Persistent>(_: &T) {}
                         trace!("Persisting:");
                         #(#recurse;)*
                         Ok(())
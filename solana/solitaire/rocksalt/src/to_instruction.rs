@@ -25,6 +25,8 @@ use syn::{
 pub fn generate_to_instruction(
     name: &syn::Ident,
     impl_generics: &syn::ImplGenerics,
+    type_impl_generics: &syn::ImplGenerics,
+    type_generics: &syn::TypeGenerics,
     data: &Data,
 ) -> TokenStream2 {
     match *data {
@@ -71,8 +73,9 @@ pub fn generate_to_instruction(
 
             #(#expanded_appends;)*
 
-            // Add dependencies
-            deps.dedup();
+            // Add dependencies, once each and in the order they were first required
+            let mut seen = Vec::new();
+            deps.retain(|v| if seen.contains(v) { false } else { seen.push(*v); true });
             let mut dep_ams = deps.iter().map(|v| solana_program::instruction::AccountMeta::new_readonly(*v, false)).collect();
             account_metas.append(&mut dep_ams);
 
@@ -83,6 +86,12 @@ pub fn generate_to_instruction(
                     }
 
                 }
+
+                /// Solitaire-generated link to the client-side #name representation
+            #[cfg(feature = "client")]
+                impl #type_impl_generics solitaire_client::ClientAccounts for #name #type_generics {
+                    type Accounts = #client_struct_name;
+                }
                 }
         }
         _ => unimplemented!(),
@@ -49,6 +49,7 @@ use crate::{
         GuardianSetUpgrade,
         ParsedVAA,
         SetFee,
+        SetGuardianSetExpiration,
         TransferFee,
     },
 };
@@ -168,6 +169,7 @@ fn handle_governance_payload(deps: DepsMut, env: Env, data: &Vec<u8>) -> StdResu
         2u8 => vaa_update_guardian_set(deps, env, &gov_packet.payload),
         3u8 => handle_set_fee(deps, env, &gov_packet.payload),
         4u8 => handle_transfer_fee(deps, env, &gov_packet.payload),
+        6u8 => handle_set_guardian_set_expiration(deps, env, &gov_packet.payload),
         _ => ContractError::InvalidVAAAction.std_err(),
     }
 }
@@ -318,6 +320,26 @@ pub fn handle_transfer_fee(deps: DepsMut, _env: Env, data: &Vec<u8>) -> StdResul
     })))
 }
 
+pub fn handle_set_guardian_set_expiration(
+    deps: DepsMut,
+    _env: Env,
+    data: &Vec<u8>,
+) -> StdResult<Response> {
+    let msg = SetGuardianSetExpiration::deserialize(&data)?;
+
+    // Only applies to guardian sets replaced from now on
+    let mut state = config_read(deps.storage).load()?;
+    state.guardian_set_expirity = u64::from(msg.guardian_set_expirity);
+    config(deps.storage).save(&state)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "guardian_set_expiration_change")
+        .add_attribute(
+            "guardian_set_expirity",
+            state.guardian_set_expirity.to_string(),
+        ))
+}
+
 fn handle_post_message(
     deps: DepsMut,
     env: Env,
@@ -415,3 +437,63 @@ fn keys_equal(a: &VerifyingKey, b: &GuardianAddress) -> bool {
     }
     true
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{
+        mock_dependencies,
+        mock_env,
+        mock_info,
+    };
+
+    /// Governance packet of the core module addressed to Terra.
+    fn governance_packet(action: u8, payload: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; 28];
+        data.extend_from_slice(b"Core");
+        data.push(action);
+        data.extend_from_slice(&CHAIN_ID.to_be_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    #[test]
+    fn guardian_set_expiration_applies_to_next_rotation() {
+        let mut deps = mock_dependencies(&[]);
+        let env = mock_env();
+        let msg = InstantiateMsg {
+            gov_chain: 1,
+            gov_address: vec![0u8; 32].into(),
+            initial_guardian_set: GuardianSetInfo {
+                addresses: vec![GuardianAddress {
+                    bytes: vec![1u8; 20].into(),
+                }],
+                expiration_time: 0,
+            },
+            guardian_set_expirity: 86400,
+        };
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), msg).unwrap();
+
+        let set_expiration = governance_packet(6, &3600u32.to_be_bytes());
+        handle_governance_payload(deps.as_mut(), env.clone(), &set_expiration).unwrap();
+        let truncated = governance_packet(6, &[1]);
+        assert!(handle_governance_payload(deps.as_mut(), env.clone(), &truncated).is_err());
+
+        // The current guardian set only starts expiring once it is replaced.
+        assert_eq!(
+            guardian_set_get(&deps.storage, 0).unwrap().expiration_time,
+            0
+        );
+
+        let mut upgrade = 1u32.to_be_bytes().to_vec();
+        upgrade.push(1);
+        upgrade.extend_from_slice(&[2u8; 20]);
+        handle_governance_payload(deps.as_mut(), env.clone(), &governance_packet(2, &upgrade))
+            .unwrap();
+
+        assert_eq!(
+            guardian_set_get(&deps.storage, 0).unwrap().expiration_time,
+            env.block.time.seconds() + 3600
+        );
+    }
+}
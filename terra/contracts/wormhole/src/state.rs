@@ -392,6 +392,25 @@ impl TransferFee {
     }
 }
 
+// action 6
+pub struct SetGuardianSetExpiration {
+    pub guardian_set_expirity: u32,
+}
+
+impl SetGuardianSetExpiration {
+    pub fn deserialize(data: &Vec<u8>) -> StdResult<Self> {
+        let data = data.as_slice();
+        if data.len() != 4 {
+            return ContractError::InvalidVAA.std_err();
+        }
+
+        let guardian_set_expirity = data.get_u32(0);
+        Ok(SetGuardianSetExpiration {
+            guardian_set_expirity,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -429,6 +448,18 @@ mod tests {
         assert_eq!(build_guardian_set(100).quorum(), 67);
     }
 
+    #[test]
+    fn test_deserialize_set_guardian_set_expiration() {
+        let data = hex::decode("00015180").unwrap();
+        let msg = SetGuardianSetExpiration::deserialize(&data).unwrap();
+        assert_eq!(msg.guardian_set_expirity, 86400);
+
+        let short = hex::decode("015180").unwrap();
+        assert!(SetGuardianSetExpiration::deserialize(&short).is_err());
+        let long = hex::decode("0001518000").unwrap();
+        assert!(SetGuardianSetExpiration::deserialize(&long).is_err());
+    }
+
     #[test]
     fn test_deserialize() {
         let x = hex::decode("080000000901007bfa71192f886ab6819fa4862e34b4d178962958d9b2e3d9437338c9e5fde1443b809d2886eaa69e0f0158ea517675d96243c9209c3fe1d94d5b19866654c6980000000b150000000500020001020304000000000000000000000000000000000000000000000000000000000000000000000a0261626364").unwrap();